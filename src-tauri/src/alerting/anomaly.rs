@@ -0,0 +1,316 @@
+//! 统计异常检测
+//! 基于历史数据计算基线（指数加权移动平均或按“星期几 + 小时”的季节性基线），
+//! 当前值偏离基线超过 sensitivity 个标准差时视为异常
+
+use chrono::{DateTime, Datelike, Local, TimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+// 基线计算方法
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyMethod {
+    /// 指数加权移动平均与方差（EWMA + z-score）
+    Ewma,
+    /// 以往数周同一“星期几 + 小时”的均值与标准差
+    Seasonal,
+}
+
+// 异常方向
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyDirection {
+    Above,
+    Below,
+    #[default]
+    Both,
+}
+
+// 异常检测规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AnomalyDetection {
+    pub method: AnomalyMethod,
+    /// 允许偏离的标准差倍数，越小越灵敏
+    #[serde(default = "default_sensitivity")]
+    pub sensitivity: f64,
+    #[serde(default)]
+    pub direction: AnomalyDirection,
+    /// 使用多少小时的历史数据计算基线，默认 EWMA 为 24 小时，季节性为 4 周
+    #[serde(default)]
+    pub lookback_hours: Option<u32>,
+    /// EWMA 平滑系数，默认 0.1
+    #[serde(default)]
+    pub ewma_alpha: Option<f64>,
+}
+
+fn default_sensitivity() -> f64 {
+    3.0
+}
+
+// 计算基线所需的最少样本数
+const MIN_EWMA_SAMPLES: usize = 10;
+const MIN_SEASONAL_SAMPLES: usize = 3;
+
+/// 回看时长的上限，8 周
+pub const MAX_ANOMALY_LOOKBACK_HOURS: u32 = 24 * 7 * 8;
+
+impl AnomalyDetection {
+    /// 校验检测参数，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.sensitivity.is_finite() || self.sensitivity <= 0.0 || self.sensitivity > 10.0 {
+            return Err(format!(
+                "Anomaly sensitivity must be between 0 and 10 standard deviations, got {}",
+                self.sensitivity
+            ));
+        }
+        if let Some(alpha) = self.ewma_alpha {
+            if !(alpha > 0.0 && alpha <= 1.0) {
+                return Err(format!("EWMA alpha must be in (0, 1], got {}", alpha));
+            }
+        }
+        if self.lookback_hours == Some(0) {
+            return Err("Anomaly lookback must be at least one hour".to_string());
+        }
+        if let Some(hours) = self.lookback_hours.filter(|hours| *hours > MAX_ANOMALY_LOOKBACK_HOURS) {
+            return Err(format!(
+                "Anomaly lookback must not exceed {} hours (8 weeks), got {}",
+                MAX_ANOMALY_LOOKBACK_HOURS, hours
+            ));
+        }
+        if self.method == AnomalyMethod::Seasonal && self.lookback_hours.is_some_and(|hours| hours < 24 * 7) {
+            return Err("Seasonal baselines need a lookback of at least one week (168 hours)".to_string());
+        }
+        Ok(())
+    }
+
+    /// 计算基线所需的历史时长（小时）
+    pub fn lookback_hours(&self) -> u32 {
+        self.lookback_hours.unwrap_or(match self.method {
+            AnomalyMethod::Ewma => 24,
+            AnomalyMethod::Seasonal => 24 * 7 * 4,
+        })
+    }
+
+    /// 计算 `now` 时刻的基线需要从何时开始加载历史数据，超出时间范围时返回 None
+    pub fn lookback_start(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        now.checked_sub_signed(chrono::Duration::hours(self.lookback_hours() as i64))
+    }
+
+    fn alpha(&self) -> f64 {
+        self.ewma_alpha.unwrap_or(0.1)
+    }
+
+    /// 根据历史样本计算 `at` 时刻的基线，样本不足时返回 None
+    pub fn baseline(&self, samples: &[(DateTime<Utc>, f64)], at: DateTime<Utc>) -> Option<Baseline> {
+        self.baseline_in(samples, at, &Local)
+    }
+
+    /// 按指定时区计算基线，季节性基线的“星期几 + 小时”以该时区为准
+    pub fn baseline_in<Tz: TimeZone>(
+        &self,
+        samples: &[(DateTime<Utc>, f64)],
+        at: DateTime<Utc>,
+        tz: &Tz,
+    ) -> Option<Baseline> {
+        let history = samples.iter().filter(|(timestamp, _)| *timestamp < at);
+        match self.method {
+            AnomalyMethod::Ewma => {
+                let mut stats = EwmaStats::new(self.alpha());
+                for (_, value) in history {
+                    stats.push(*value);
+                }
+                stats.baseline()
+            }
+            AnomalyMethod::Seasonal => {
+                let slot = hour_of_week(at, tz);
+                let mut stats = RunningStats::default();
+                for (_, value) in history.filter(|(timestamp, _)| hour_of_week(*timestamp, tz) == slot) {
+                    stats.push(*value);
+                }
+                stats.baseline(MIN_SEASONAL_SAMPLES)
+            }
+        }
+    }
+
+    /// 判断当前值是否偏离基线
+    pub fn evaluate(&self, baseline: &Baseline, value: f64) -> AnomalyVerdict {
+        let z_score = (value - baseline.expected) / baseline.effective_std_dev();
+        let is_anomaly = match self.direction {
+            AnomalyDirection::Above => z_score > self.sensitivity,
+            AnomalyDirection::Below => z_score < -self.sensitivity,
+            AnomalyDirection::Both => z_score.abs() > self.sensitivity,
+        };
+        let (lower, upper) = baseline.range(self.sensitivity);
+        AnomalyVerdict {
+            value,
+            expected: baseline.expected,
+            lower,
+            upper,
+            z_score,
+            is_anomaly,
+        }
+    }
+
+    /// 扫描一段历史数据，每个点只与它之前的数据比较，返回被判定为异常的点
+    pub fn scan(&self, samples: &[(DateTime<Utc>, f64)]) -> Vec<AnomalyPoint> {
+        self.scan_in(samples, &Local)
+    }
+
+    /// 按指定时区扫描历史数据
+    pub fn scan_in<Tz: TimeZone>(&self, samples: &[(DateTime<Utc>, f64)], tz: &Tz) -> Vec<AnomalyPoint> {
+        self.verdicts_in(samples, tz)
+            .into_iter()
+            .filter_map(|(timestamp, verdict)| {
+                verdict
+                    .filter(|verdict| verdict.is_anomaly)
+                    .map(|verdict| AnomalyPoint { timestamp, verdict })
+            })
+            .collect()
+    }
+
+    /// 按时间顺序对每个样本与之前的样本形成的基线比较，样本不足以建立基线时为空
+    pub fn verdicts_in<Tz: TimeZone>(
+        &self,
+        samples: &[(DateTime<Utc>, f64)],
+        tz: &Tz,
+    ) -> Vec<(DateTime<Utc>, Option<AnomalyVerdict>)> {
+        let mut sorted: Vec<(DateTime<Utc>, f64)> = samples.to_vec();
+        sorted.sort_by_key(|(timestamp, _)| *timestamp);
+
+        let mut verdicts = Vec::with_capacity(sorted.len());
+        match self.method {
+            AnomalyMethod::Ewma => {
+                let mut stats = EwmaStats::new(self.alpha());
+                for (timestamp, value) in sorted {
+                    let verdict = stats.baseline().map(|baseline| self.evaluate(&baseline, value));
+                    verdicts.push((timestamp, verdict));
+                    stats.push(value);
+                }
+            }
+            AnomalyMethod::Seasonal => {
+                let mut slots: std::collections::HashMap<u32, RunningStats> = std::collections::HashMap::new();
+                for (timestamp, value) in sorted {
+                    let stats = slots.entry(hour_of_week(timestamp, tz)).or_default();
+                    let verdict = stats
+                        .baseline(MIN_SEASONAL_SAMPLES)
+                        .map(|baseline| self.evaluate(&baseline, value));
+                    verdicts.push((timestamp, verdict));
+                    stats.push(value);
+                }
+            }
+        }
+        verdicts
+    }
+}
+
+/// 计算一周内的小时序号（周一 0 点为 0，周日 23 点为 167）
+fn hour_of_week<Tz: TimeZone>(timestamp: DateTime<Utc>, tz: &Tz) -> u32 {
+    let local = timestamp.with_timezone(tz);
+    local.weekday().num_days_from_monday() * 24 + local.hour()
+}
+
+// 基线
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct Baseline {
+    pub expected: f64,
+    pub std_dev: f64,
+    pub samples: usize,
+}
+
+impl Baseline {
+    /// 参与计算的标准差，下限为期望值的 1%，避免恒定序列出现任何波动都被判为异常
+    fn effective_std_dev(&self) -> f64 {
+        self.std_dev.max(self.expected.abs() * 0.01).max(f64::EPSILON)
+    }
+
+    /// 期望范围
+    pub fn range(&self, sensitivity: f64) -> (f64, f64) {
+        let spread = self.effective_std_dev() * sensitivity;
+        (self.expected - spread, self.expected + spread)
+    }
+}
+
+// 异常判定结果
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct AnomalyVerdict {
+    pub value: f64,
+    pub expected: f64,
+    pub lower: f64,
+    pub upper: f64,
+    pub z_score: f64,
+    pub is_anomaly: bool,
+}
+
+// 历史数据中的异常点
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AnomalyPoint {
+    pub timestamp: DateTime<Utc>,
+    pub verdict: AnomalyVerdict,
+}
+
+// 指数加权均值与方差
+struct EwmaStats {
+    alpha: f64,
+    mean: f64,
+    variance: f64,
+    count: usize,
+}
+
+impl EwmaStats {
+    fn new(alpha: f64) -> Self {
+        Self {
+            alpha,
+            mean: 0.0,
+            variance: 0.0,
+            count: 0,
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.count == 0 {
+            self.mean = value;
+        } else {
+            let diff = value - self.mean;
+            let increment = self.alpha * diff;
+            self.mean += increment;
+            self.variance = (1.0 - self.alpha) * (self.variance + diff * increment);
+        }
+        self.count += 1;
+    }
+
+    fn baseline(&self) -> Option<Baseline> {
+        (self.count >= MIN_EWMA_SAMPLES).then(|| Baseline {
+            expected: self.mean,
+            std_dev: self.variance.sqrt(),
+            samples: self.count,
+        })
+    }
+}
+
+// Welford 算法计算均值与标准差
+#[derive(Default)]
+struct RunningStats {
+    count: usize,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    fn push(&mut self, value: f64) {
+        self.count += 1;
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+    }
+
+    fn baseline(&self, min_samples: usize) -> Option<Baseline> {
+        (self.count >= min_samples).then(|| Baseline {
+            expected: self.mean,
+            std_dev: if self.count > 1 {
+                (self.m2 / (self.count - 1) as f64).sqrt()
+            } else {
+                0.0
+            },
+            samples: self.count,
+        })
+    }
+}
@@ -0,0 +1,116 @@
+//! 警报升级策略
+//! 警报在指定时间内未被确认时，按步骤通过更多通知方式重新通知或提升严重程度，确认后停止升级
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AlertSeverity;
+
+// 升级步骤
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EscalationStep {
+    /// 触发后多少分钟仍未确认时执行此步骤
+    pub after_minutes: u32,
+    /// 重新通知时追加使用的通知方式
+    #[serde(default)]
+    pub notification_methods: Vec<String>,
+    /// 提升到的严重程度
+    #[serde(default)]
+    pub raise_severity_to: Option<AlertSeverity>,
+}
+
+// 升级策略
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EscalationPolicy {
+    pub id: String,
+    pub name: String,
+    pub steps: Vec<EscalationStep>,
+}
+
+impl EscalationPolicy {
+    /// 校验升级策略，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Escalation policy name must not be empty".to_string());
+        }
+        if self.steps.is_empty() {
+            return Err("Escalation policy needs at least one step".to_string());
+        }
+
+        let mut previous = 0;
+        for (index, step) in self.steps.iter().enumerate() {
+            let number = index + 1;
+            if step.after_minutes == 0 {
+                return Err(format!("Step {} must wait at least one minute", number));
+            }
+            if step.after_minutes <= previous {
+                return Err(format!(
+                    "Step {} must wait longer than the previous step ({} minutes)",
+                    number, previous
+                ));
+            }
+            if step.notification_methods.is_empty() && step.raise_severity_to.is_none() {
+                return Err(format!(
+                    "Step {} must add notification methods or raise the severity",
+                    number
+                ));
+            }
+            if step.notification_methods.iter().any(|method| method.trim().is_empty()) {
+                return Err(format!("Step {} contains an empty notification method", number));
+            }
+            previous = step.after_minutes;
+        }
+
+        Ok(())
+    }
+
+    /// 计算经过 `elapsed_minutes` 后应达到的升级级别（1 起始，0 表示尚未升级）
+    pub fn level_after(&self, elapsed_minutes: f64) -> u32 {
+        self.steps
+            .iter()
+            .take_while(|step| step.after_minutes as f64 <= elapsed_minutes)
+            .count() as u32
+    }
+
+    /// 从当前级别升级到目标级别时需要执行的步骤，多个步骤同时到期时合并为一次通知
+    pub fn escalate(
+        &self,
+        current_level: u32,
+        target_level: u32,
+        current_severity: Option<AlertSeverity>,
+    ) -> Option<(Vec<String>, Option<AlertSeverity>)> {
+        if target_level <= current_level {
+            return None;
+        }
+
+        let mut methods: Vec<String> = Vec::new();
+        let mut severity = current_severity;
+        for step in &self.steps[current_level as usize..target_level as usize] {
+            for method in &step.notification_methods {
+                if !methods.contains(method) {
+                    methods.push(method.clone());
+                }
+            }
+            if let Some(raise_to) = step.raise_severity_to {
+                severity = Some(severity.map_or(raise_to, |current| current.max(raise_to)));
+            }
+        }
+
+        Some((methods, severity))
+    }
+}
+
+// 已执行的升级记录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertEscalation {
+    pub id: String,
+    pub alert_history_id: String,
+    pub rule_id: String,
+    pub policy_id: String,
+    pub step: u32,
+    pub total_steps: u32,
+    pub notification_methods: Vec<String>,
+    pub severity: Option<AlertSeverity>,
+    pub escalated_at: DateTime<Utc>,
+    pub message: String,
+}
@@ -0,0 +1,874 @@
+//! 警报条件表达式
+//! 支持布尔运算（AND/OR/NOT）、算术运算、比较、指标引用和历史窗口函数，
+//! 例如 `cpu > 90 AND load > cores * 2` 或 `avg_over(memory, 10m) > 85 OR swap > 50`
+
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+
+use super::{AlertComparison, AlertMetric, MetricSnapshot};
+use crate::database::HistoricalSystemData;
+
+// 表达式解析或校验错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExpressionError {
+    pub message: String,
+    pub position: Option<usize>,
+}
+
+impl ExpressionError {
+    fn at(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: Some(position),
+        }
+    }
+
+    fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            position: None,
+        }
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position {
+            Some(position) => write!(f, "{} (at position {})", self.message, position),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+// 历史窗口函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowFunction {
+    AvgOver,
+    MaxOver,
+    Rate,
+}
+
+impl WindowFunction {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "avg_over" => Some(Self::AvgOver),
+            "max_over" => Some(Self::MaxOver),
+            "rate" => Some(Self::Rate),
+            _ => None,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::AvgOver => "avg_over",
+            Self::MaxOver => "max_over",
+            Self::Rate => "rate",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalOp {
+    And,
+    Or,
+}
+
+// 表达式语法树
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Number(f64),
+    Metric(AlertMetric),
+    Window {
+        function: WindowFunction,
+        metric: AlertMetric,
+        window_secs: f64,
+    },
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Arithmetic {
+        op: ArithmeticOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Compare {
+        op: AlertComparison,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Logical {
+        op: LogicalOp,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+}
+
+// 表达式的值类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ValueKind {
+    Number,
+    Boolean,
+}
+
+impl Expr {
+    fn kind(&self) -> ValueKind {
+        match self {
+            Expr::Number(_) | Expr::Metric(_) | Expr::Window { .. } | Expr::Neg(_) | Expr::Arithmetic { .. } => {
+                ValueKind::Number
+            }
+            Expr::Not(_) | Expr::Compare { .. } | Expr::Logical { .. } => ValueKind::Boolean,
+        }
+    }
+
+    /// 收集表达式中所有窗口函数所需的最长历史时间（秒）
+    fn max_window_secs(&self) -> f64 {
+        match self {
+            Expr::Window { window_secs, .. } => *window_secs,
+            Expr::Neg(inner) | Expr::Not(inner) => inner.max_window_secs(),
+            Expr::Arithmetic { left, right, .. }
+            | Expr::Compare { left, right, .. }
+            | Expr::Logical { left, right, .. } => left.max_window_secs().max(right.max_window_secs()),
+            Expr::Number(_) | Expr::Metric(_) => 0.0,
+        }
+    }
+
+    fn collect_metrics(&self, metrics: &mut Vec<AlertMetric>) {
+        match self {
+            Expr::Metric(metric) | Expr::Window { metric, .. } => {
+                if !metrics.contains(metric) {
+                    metrics.push(*metric);
+                }
+            }
+            Expr::Neg(inner) | Expr::Not(inner) => inner.collect_metrics(metrics),
+            Expr::Arithmetic { left, right, .. }
+            | Expr::Compare { left, right, .. }
+            | Expr::Logical { left, right, .. } => {
+                left.collect_metrics(metrics);
+                right.collect_metrics(metrics);
+            }
+            Expr::Number(_) => {}
+        }
+    }
+
+    fn eval_number(&self, ctx: &EvaluationContext<'_>) -> Option<f64> {
+        match self {
+            Expr::Number(value) => Some(*value),
+            Expr::Metric(metric) => ctx.snapshot.value(*metric),
+            Expr::Window {
+                function,
+                metric,
+                window_secs,
+            } => ctx.window_value(*function, *metric, *window_secs),
+            Expr::Neg(inner) => inner.eval_number(ctx).map(|value| -value),
+            Expr::Arithmetic { op, left, right } => {
+                let left = left.eval_number(ctx)?;
+                let right = right.eval_number(ctx)?;
+                match op {
+                    ArithmeticOp::Add => Some(left + right),
+                    ArithmeticOp::Sub => Some(left - right),
+                    ArithmeticOp::Mul => Some(left * right),
+                    ArithmeticOp::Div => {
+                        if right == 0.0 {
+                            None
+                        } else {
+                            Some(left / right)
+                        }
+                    }
+                }
+            }
+            Expr::Not(_) | Expr::Compare { .. } | Expr::Logical { .. } => None,
+        }
+    }
+
+    // 数据缺失（如历史记录不足或除以零）时返回 None，规则不会触发
+    fn eval_bool(&self, ctx: &EvaluationContext<'_>, trigger_value: &mut Option<f64>) -> Option<bool> {
+        match self {
+            Expr::Not(inner) => inner.eval_bool(ctx, &mut None).map(|value| !value),
+            Expr::Compare { op, left, right } => {
+                let left = left.eval_number(ctx)?;
+                let right = right.eval_number(ctx)?;
+                let result = op.apply(left, right);
+                if result && trigger_value.is_none() {
+                    *trigger_value = Some(left);
+                }
+                Some(result)
+            }
+            Expr::Logical { op, left, right } => {
+                let left = left.eval_bool(ctx, trigger_value);
+                match op {
+                    LogicalOp::And => {
+                        if left == Some(false) {
+                            return Some(false);
+                        }
+                        let right = right.eval_bool(ctx, trigger_value);
+                        match (left, right) {
+                            (_, Some(false)) => Some(false),
+                            (Some(true), Some(true)) => Some(true),
+                            _ => None,
+                        }
+                    }
+                    LogicalOp::Or => {
+                        if left == Some(true) {
+                            return Some(true);
+                        }
+                        let right = right.eval_bool(ctx, trigger_value);
+                        match (left, right) {
+                            (_, Some(true)) => Some(true),
+                            (Some(false), Some(false)) => Some(false),
+                            _ => None,
+                        }
+                    }
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match self {
+            Expr::Logical { op: LogicalOp::Or, .. } => 1,
+            Expr::Logical { op: LogicalOp::And, .. } => 2,
+            Expr::Not(_) => 3,
+            Expr::Compare { .. } => 4,
+            Expr::Arithmetic {
+                op: ArithmeticOp::Add | ArithmeticOp::Sub,
+                ..
+            } => 5,
+            Expr::Arithmetic { .. } => 6,
+            Expr::Neg(_) => 7,
+            _ => 8,
+        }
+    }
+
+    fn fmt_child(&self, child: &Expr, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if child.precedence() <= self.precedence() && child.precedence() < 8 {
+            write!(f, "({})", child)
+        } else {
+            write!(f, "{}", child)
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expr::Number(value) => write!(f, "{}", value),
+            Expr::Metric(metric) => write!(f, "{}", metric.name()),
+            Expr::Window {
+                function,
+                metric,
+                window_secs,
+            } => write!(f, "{}({}, {}s)", function.name(), metric.name(), window_secs),
+            Expr::Neg(inner) => {
+                write!(f, "-")?;
+                self.fmt_child(inner, f)
+            }
+            Expr::Not(inner) => {
+                write!(f, "NOT ")?;
+                self.fmt_child(inner, f)
+            }
+            Expr::Arithmetic { op, left, right } => {
+                let symbol = match op {
+                    ArithmeticOp::Add => "+",
+                    ArithmeticOp::Sub => "-",
+                    ArithmeticOp::Mul => "*",
+                    ArithmeticOp::Div => "/",
+                };
+                self.fmt_child(left, f)?;
+                write!(f, " {} ", symbol)?;
+                self.fmt_child(right, f)
+            }
+            Expr::Compare { op, left, right } => {
+                self.fmt_child(left, f)?;
+                write!(f, " {} ", op.symbol())?;
+                self.fmt_child(right, f)
+            }
+            Expr::Logical { op, left, right } => {
+                let keyword = match op {
+                    LogicalOp::And => "AND",
+                    LogicalOp::Or => "OR",
+                };
+                self.fmt_child(left, f)?;
+                write!(f, " {} ", keyword)?;
+                self.fmt_child(right, f)
+            }
+        }
+    }
+}
+
+/// 窗口函数允许的最长窗口，与回测范围的上限（31 天）相同
+const MAX_WINDOW_SECS: f64 = 31.0 * 24.0 * 60.0 * 60.0;
+
+// 表达式求值上下文
+pub struct EvaluationContext<'a> {
+    pub snapshot: &'a MetricSnapshot,
+    pub history: &'a [HistoricalSystemData],
+    pub now: DateTime<Utc>,
+}
+
+impl EvaluationContext<'_> {
+    fn window_value(&self, function: WindowFunction, metric: AlertMetric, window_secs: f64) -> Option<f64> {
+        let window_start = chrono::Duration::try_milliseconds((window_secs * 1000.0) as i64)
+            .and_then(|window| self.now.checked_sub_signed(window))?;
+        let samples: Vec<(DateTime<Utc>, f64)> = self
+            .history
+            .iter()
+            .filter(|record| record.timestamp >= window_start && record.timestamp <= self.now)
+            .filter_map(|record| metric.historical_value(record).map(|value| (record.timestamp, value)))
+            .collect();
+
+        match function {
+            WindowFunction::AvgOver => {
+                if samples.is_empty() {
+                    None
+                } else {
+                    Some(samples.iter().map(|(_, value)| value).sum::<f64>() / samples.len() as f64)
+                }
+            }
+            WindowFunction::MaxOver => samples.iter().map(|(_, value)| *value).reduce(f64::max),
+            WindowFunction::Rate => {
+                let first = samples.iter().min_by_key(|(timestamp, _)| *timestamp)?;
+                let last = samples.iter().max_by_key(|(timestamp, _)| *timestamp)?;
+                let elapsed = (last.0 - first.0).num_milliseconds() as f64 / 1000.0;
+                if elapsed <= 0.0 {
+                    None
+                } else {
+                    Some((last.1 - first.1) / elapsed)
+                }
+            }
+        }
+    }
+}
+
+// 表达式求值结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpressionOutcome {
+    pub fired: bool,
+    /// 第一个成立的比较式左侧的值，用于写入警报历史
+    pub value: f64,
+}
+
+// 已解析并通过校验的警报表达式
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertExpression {
+    source: String,
+    root: Expr,
+}
+
+impl AlertExpression {
+    /// 解析并校验表达式，要求结果为布尔值
+    pub fn parse(source: &str) -> Result<Self, ExpressionError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser { tokens, index: 0 };
+        let root = parser.parse_or()?;
+        if let Some(token) = parser.peek() {
+            return Err(ExpressionError::at(
+                token.position,
+                format!("unexpected {}", token.kind.describe()),
+            ));
+        }
+        if root.kind() != ValueKind::Boolean {
+            return Err(ExpressionError::new(format!(
+                "expression `{}` must be a condition (use a comparison such as `> 90`)",
+                root
+            )));
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            root,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn root(&self) -> &Expr {
+        &self.root
+    }
+
+    /// 表达式引用的指标，按首次出现的顺序排列
+    pub fn metrics(&self) -> Vec<AlertMetric> {
+        let mut metrics = Vec::new();
+        self.root.collect_metrics(&mut metrics);
+        metrics
+    }
+
+    /// 求值所需的最长历史窗口（秒），不使用窗口函数时为 0
+    pub fn history_window_secs(&self) -> f64 {
+        self.root.max_window_secs()
+    }
+
+    /// 对表达式求值，数据不足时返回 None
+    pub fn evaluate(&self, ctx: &EvaluationContext<'_>) -> Option<ExpressionOutcome> {
+        let mut trigger_value = None;
+        let fired = self.root.eval_bool(ctx, &mut trigger_value)?;
+        let value = trigger_value.or_else(|| first_comparison_value(&self.root, ctx)).unwrap_or(0.0);
+        Some(ExpressionOutcome { fired, value })
+    }
+}
+
+impl fmt::Display for AlertExpression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.root)
+    }
+}
+
+fn first_comparison_value(expr: &Expr, ctx: &EvaluationContext<'_>) -> Option<f64> {
+    match expr {
+        Expr::Compare { left, .. } => left.eval_number(ctx),
+        Expr::Not(inner) => first_comparison_value(inner, ctx),
+        Expr::Logical { left, right, .. } => {
+            first_comparison_value(left, ctx).or_else(|| first_comparison_value(right, ctx))
+        }
+        _ => None,
+    }
+}
+
+// 词法单元
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    Duration(f64),
+    Ident(String),
+    LParen,
+    RParen,
+    Comma,
+    Plus,
+    Minus,
+    Star,
+    Slash,
+    Compare(AlertComparison),
+    And,
+    Or,
+    Not,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Number(value) => format!("number `{}`", value),
+            TokenKind::Duration(secs) => format!("duration `{}s`", secs),
+            TokenKind::Ident(name) => format!("identifier `{}`", name),
+            TokenKind::LParen => "`(`".to_string(),
+            TokenKind::RParen => "`)`".to_string(),
+            TokenKind::Comma => "`,`".to_string(),
+            TokenKind::Plus => "`+`".to_string(),
+            TokenKind::Minus => "`-`".to_string(),
+            TokenKind::Star => "`*`".to_string(),
+            TokenKind::Slash => "`/`".to_string(),
+            TokenKind::Compare(op) => format!("`{}`", op.symbol()),
+            TokenKind::And => "`AND`".to_string(),
+            TokenKind::Or => "`OR`".to_string(),
+            TokenKind::Not => "`NOT`".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: usize,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ExpressionError> {
+    let chars: Vec<char> = source.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let start = i;
+
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let kind = if c.is_ascii_digit() || (c == '.' && chars.get(i + 1).is_some_and(|n| n.is_ascii_digit())) {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            let value = text
+                .parse::<f64>()
+                .map_err(|_| ExpressionError::at(start, format!("invalid number `{}`", text)))?;
+
+            // 数字后紧跟 s/m/h/d 视为时长
+            let unit = chars.get(i).copied();
+            let unit_is_standalone = !chars.get(i + 1).is_some_and(|n| n.is_alphanumeric() || *n == '_');
+            match unit {
+                Some(unit @ ('s' | 'm' | 'h' | 'd')) if unit_is_standalone => {
+                    i += 1;
+                    let multiplier = match unit {
+                        's' => 1.0,
+                        'm' => 60.0,
+                        'h' => 3600.0,
+                        _ => 86400.0,
+                    };
+                    TokenKind::Duration(value * multiplier)
+                }
+                Some(next) if next.is_alphabetic() || next == '_' => {
+                    return Err(ExpressionError::at(
+                        i,
+                        format!("unknown duration unit after `{}` (expected s, m, h or d)", text),
+                    ));
+                }
+                _ => TokenKind::Number(value),
+            }
+        } else if c.is_alphabetic() || c == '_' {
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let text: String = chars[start..i].iter().collect();
+            match text.to_ascii_uppercase().as_str() {
+                "AND" => TokenKind::And,
+                "OR" => TokenKind::Or,
+                "NOT" => TokenKind::Not,
+                _ => TokenKind::Ident(text),
+            }
+        } else {
+            let next = chars.get(i + 1).copied();
+            let (kind, len) = match (c, next) {
+                ('>', Some('=')) => (TokenKind::Compare(AlertComparison::GreaterOrEqual), 2),
+                ('<', Some('=')) => (TokenKind::Compare(AlertComparison::LessOrEqual), 2),
+                ('=', Some('=')) => (TokenKind::Compare(AlertComparison::Equals), 2),
+                ('!', Some('=')) => (TokenKind::Compare(AlertComparison::NotEquals), 2),
+                ('&', Some('&')) => (TokenKind::And, 2),
+                ('|', Some('|')) => (TokenKind::Or, 2),
+                ('>', _) => (TokenKind::Compare(AlertComparison::GreaterThan), 1),
+                ('<', _) => (TokenKind::Compare(AlertComparison::LessThan), 1),
+                ('=', _) => (TokenKind::Compare(AlertComparison::Equals), 1),
+                ('!', _) => (TokenKind::Not, 1),
+                ('(', _) => (TokenKind::LParen, 1),
+                (')', _) => (TokenKind::RParen, 1),
+                (',', _) => (TokenKind::Comma, 1),
+                ('+', _) => (TokenKind::Plus, 1),
+                ('-', _) => (TokenKind::Minus, 1),
+                ('*', _) => (TokenKind::Star, 1),
+                ('/', _) => (TokenKind::Slash, 1),
+                _ => return Err(ExpressionError::at(start, format!("unexpected character `{}`", c))),
+            };
+            i += len;
+            kind
+        };
+
+        tokens.push(Token { kind, position: start });
+    }
+
+    Ok(tokens)
+}
+
+// 递归下降解析器，优先级从低到高：OR、AND、NOT、比较、加减、乘除、取负
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        if token.is_some() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn end_position(&self) -> usize {
+        self.tokens
+            .last()
+            .map(|token| token.position + 1)
+            .unwrap_or(0)
+    }
+
+    fn expect(&mut self, kind: TokenKind) -> Result<(), ExpressionError> {
+        match self.next() {
+            Some(token) if token.kind == kind => Ok(()),
+            Some(token) => Err(ExpressionError::at(
+                token.position,
+                format!("expected {} but found {}", kind.describe(), token.kind.describe()),
+            )),
+            None => Err(ExpressionError::at(
+                self.end_position(),
+                format!("expected {} but the expression ended", kind.describe()),
+            )),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_and()?;
+        while let Some(position) = self.peek().filter(|t| t.kind == TokenKind::Or).map(|t| t.position) {
+            self.index += 1;
+            let right = self.parse_and()?;
+            left = logical(LogicalOp::Or, left, right, position)?;
+        }
+        Ok(left)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_not()?;
+        while let Some(position) = self.peek().filter(|t| t.kind == TokenKind::And).map(|t| t.position) {
+            self.index += 1;
+            let right = self.parse_not()?;
+            left = logical(LogicalOp::And, left, right, position)?;
+        }
+        Ok(left)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr, ExpressionError> {
+        if let Some(position) = self.peek().filter(|t| t.kind == TokenKind::Not).map(|t| t.position) {
+            self.index += 1;
+            let inner = self.parse_not()?;
+            if inner.kind() != ValueKind::Boolean {
+                return Err(ExpressionError::at(
+                    position,
+                    format!("NOT needs a condition, but `{}` is a number", inner),
+                ));
+            }
+            return Ok(Expr::Not(Box::new(inner)));
+        }
+        self.parse_comparison()
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expr, ExpressionError> {
+        let left = self.parse_additive()?;
+        let op = match self.peek() {
+            Some(Token {
+                kind: TokenKind::Compare(op),
+                position,
+            }) => (*op, *position),
+            _ => return Ok(left),
+        };
+        self.index += 1;
+        let right = self.parse_additive()?;
+        for side in [&left, &right] {
+            if side.kind() != ValueKind::Number {
+                return Err(ExpressionError::at(
+                    op.1,
+                    format!("`{}` compares numbers, but `{}` is a condition", op.0.symbol(), side),
+                ));
+            }
+        }
+        if let Some(token) = self.peek().filter(|t| matches!(t.kind, TokenKind::Compare(_))) {
+            return Err(ExpressionError::at(
+                token.position,
+                "comparisons cannot be chained; combine them with AND",
+            ));
+        }
+        Ok(Expr::Compare {
+            op: op.0,
+            left: Box::new(left),
+            right: Box::new(right),
+        })
+    }
+
+    fn parse_additive(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_multiplicative()?;
+        loop {
+            let op = match self.peek().map(|t| (&t.kind, t.position)) {
+                Some((TokenKind::Plus, position)) => (ArithmeticOp::Add, position),
+                Some((TokenKind::Minus, position)) => (ArithmeticOp::Sub, position),
+                _ => break,
+            };
+            self.index += 1;
+            let right = self.parse_multiplicative()?;
+            left = arithmetic(op.0, left, right, op.1)?;
+        }
+        Ok(left)
+    }
+
+    fn parse_multiplicative(&mut self) -> Result<Expr, ExpressionError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.peek().map(|t| (&t.kind, t.position)) {
+                Some((TokenKind::Star, position)) => (ArithmeticOp::Mul, position),
+                Some((TokenKind::Slash, position)) => (ArithmeticOp::Div, position),
+                _ => break,
+            };
+            self.index += 1;
+            let right = self.parse_unary()?;
+            left = arithmetic(op.0, left, right, op.1)?;
+        }
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ExpressionError> {
+        if let Some(position) = self.peek().filter(|t| t.kind == TokenKind::Minus).map(|t| t.position) {
+            self.index += 1;
+            let inner = self.parse_unary()?;
+            if inner.kind() != ValueKind::Number {
+                return Err(ExpressionError::at(position, format!("cannot negate condition `{}`", inner)));
+            }
+            return Ok(Expr::Neg(Box::new(inner)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ExpressionError> {
+        let token = match self.next() {
+            Some(token) => token,
+            None => {
+                return Err(ExpressionError::at(
+                    self.end_position(),
+                    "expected a metric, number or `(` but the expression ended",
+                ))
+            }
+        };
+
+        match token.kind {
+            TokenKind::Number(value) => Ok(Expr::Number(value)),
+            TokenKind::Duration(secs) => Ok(Expr::Number(secs)),
+            TokenKind::LParen => {
+                let inner = self.parse_or()?;
+                self.expect(TokenKind::RParen)?;
+                Ok(inner)
+            }
+            TokenKind::Ident(name) => {
+                if self.peek().is_some_and(|t| t.kind == TokenKind::LParen) {
+                    self.parse_call(&name, token.position)
+                } else {
+                    AlertMetric::from_name(&name).map(Expr::Metric).ok_or_else(|| {
+                        ExpressionError::at(
+                            token.position,
+                            format!(
+                                "unknown metric `{}` (expected one of cpu, memory, disk, network, load, swap, cores, fds)",
+                                name
+                            ),
+                        )
+                    })
+                }
+            }
+            other => Err(ExpressionError::at(
+                token.position,
+                format!("expected a metric, number or `(` but found {}", other.describe()),
+            )),
+        }
+    }
+
+    fn parse_call(&mut self, name: &str, position: usize) -> Result<Expr, ExpressionError> {
+        let function = WindowFunction::from_name(name).ok_or_else(|| {
+            ExpressionError::at(
+                position,
+                format!("unknown function `{}` (expected avg_over, max_over or rate)", name),
+            )
+        })?;
+        self.expect(TokenKind::LParen)?;
+
+        let metric = match self.next() {
+            Some(Token {
+                kind: TokenKind::Ident(metric_name),
+                position,
+            }) => {
+                let metric = AlertMetric::from_name(&metric_name).ok_or_else(|| {
+                    ExpressionError::at(position, format!("unknown metric `{}`", metric_name))
+                })?;
+                if !metric.has_history() {
+                    return Err(ExpressionError::at(
+                        position,
+                        format!("metric `{}` has no stored history and cannot be used with {}", metric.name(), function.name()),
+                    ));
+                }
+                metric
+            }
+            Some(token) => {
+                return Err(ExpressionError::at(
+                    token.position,
+                    format!("{} expects a metric as its first argument", function.name()),
+                ))
+            }
+            None => {
+                return Err(ExpressionError::at(
+                    self.end_position(),
+                    format!("{} expects a metric as its first argument", function.name()),
+                ))
+            }
+        };
+
+        self.expect(TokenKind::Comma)?;
+
+        let window_secs = match self.next() {
+            Some(Token {
+                kind: TokenKind::Duration(secs) | TokenKind::Number(secs),
+                position,
+            }) => {
+                if secs <= 0.0 {
+                    return Err(ExpressionError::at(position, "window must be greater than zero"));
+                }
+                if secs > MAX_WINDOW_SECS {
+                    return Err(ExpressionError::at(position, "window must not exceed 31 days"));
+                }
+                secs
+            }
+            Some(token) => {
+                return Err(ExpressionError::at(
+                    token.position,
+                    format!("{} expects a window such as `5m` as its second argument", function.name()),
+                ))
+            }
+            None => {
+                return Err(ExpressionError::at(
+                    self.end_position(),
+                    format!("{} expects a window such as `5m` as its second argument", function.name()),
+                ))
+            }
+        };
+
+        self.expect(TokenKind::RParen)?;
+
+        Ok(Expr::Window {
+            function,
+            metric,
+            window_secs,
+        })
+    }
+}
+
+fn arithmetic(op: ArithmeticOp, left: Expr, right: Expr, position: usize) -> Result<Expr, ExpressionError> {
+    for side in [&left, &right] {
+        if side.kind() != ValueKind::Number {
+            return Err(ExpressionError::at(
+                position,
+                format!("arithmetic needs numbers, but `{}` is a condition", side),
+            ));
+        }
+    }
+    Ok(Expr::Arithmetic {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
+
+fn logical(op: LogicalOp, left: Expr, right: Expr, position: usize) -> Result<Expr, ExpressionError> {
+    let keyword = match op {
+        LogicalOp::And => "AND",
+        LogicalOp::Or => "OR",
+    };
+    for side in [&left, &right] {
+        if side.kind() != ValueKind::Boolean {
+            return Err(ExpressionError::at(
+                position,
+                format!("{} needs conditions on both sides, but `{}` is a number", keyword, side),
+            ));
+        }
+    }
+    Ok(Expr::Logical {
+        op,
+        left: Box::new(left),
+        right: Box::new(right),
+    })
+}
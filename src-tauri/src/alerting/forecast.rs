@@ -0,0 +1,219 @@
+//! 资源耗尽预测
+//! 对回看窗口内的磁盘或内存使用率做最小二乘线性拟合，估算到达阈值（默认 100%）的剩余时间，
+//! 置信区间由斜率的标准误差给出
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AlertMetric;
+
+// 95% 置信区间对应的正态分位数
+const CONFIDENCE_Z: f64 = 1.96;
+// 拟合所需的最少样本数
+const MIN_SAMPLES: usize = 3;
+
+/// 预测条件允许的最长剩余时间（小时），约十年
+pub const MAX_FORECAST_HOURS: f64 = 24.0 * 365.0 * 10.0;
+
+/// 拟合趋势允许的最长回看时长（小时），8 周
+pub const MAX_FORECAST_LOOKBACK_HOURS: u32 = 24 * 7 * 8;
+
+// 预测条件，作为警报规则使用时在剩余时间少于 within_hours 时触发
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ForecastCondition {
+    /// 拟合趋势使用的历史时长（小时）
+    #[serde(default = "default_lookback_hours")]
+    pub lookback_hours: u32,
+    /// 视为耗尽的使用率
+    #[serde(default = "default_threshold")]
+    pub threshold: f64,
+    /// 预计剩余时间少于该值时触发
+    pub within_hours: f64,
+}
+
+fn default_lookback_hours() -> u32 {
+    24
+}
+
+fn default_threshold() -> f64 {
+    100.0
+}
+
+impl ForecastCondition {
+    /// 校验预测条件，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.lookback_hours == 0 {
+            return Err("Forecast lookback must be at least one hour".to_string());
+        }
+        if self.lookback_hours > MAX_FORECAST_LOOKBACK_HOURS {
+            return Err(format!(
+                "Forecast lookback must not exceed {} hours (8 weeks), got {}",
+                MAX_FORECAST_LOOKBACK_HOURS, self.lookback_hours
+            ));
+        }
+        if !(0.0..=100.0).contains(&self.threshold) {
+            return Err(format!(
+                "Forecast threshold is a percentage and must be between 0 and 100, got {}",
+                self.threshold
+            ));
+        }
+        if !self.within_hours.is_finite() || self.within_hours <= 0.0 {
+            return Err(format!(
+                "Forecast horizon must be a positive number of hours, got {}",
+                self.within_hours
+            ));
+        }
+        if self.within_hours > MAX_FORECAST_HOURS {
+            return Err(format!(
+                "Forecast horizon must not exceed {} hours, got {}",
+                MAX_FORECAST_HOURS, self.within_hours
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// 指标是否支持耗尽预测
+pub fn supports_forecast(metric: AlertMetric) -> bool {
+    matches!(metric, AlertMetric::Disk | AlertMetric::Memory)
+}
+
+// 线性趋势
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct TrendFit {
+    /// 每小时变化量
+    pub slope_per_hour: f64,
+    /// 斜率的标准误差
+    pub slope_std_error: f64,
+    /// 拟合线在当前时刻的值
+    pub fitted_now: f64,
+    pub r_squared: f64,
+    pub samples: usize,
+}
+
+impl TrendFit {
+    /// 对样本做最小二乘拟合，横轴为相对 `now` 的小时数；样本不足或时间跨度为零时返回 None
+    pub fn fit(samples: &[(DateTime<Utc>, f64)], now: DateTime<Utc>) -> Option<Self> {
+        if samples.len() < MIN_SAMPLES {
+            return None;
+        }
+
+        let points: Vec<(f64, f64)> = samples
+            .iter()
+            .map(|(timestamp, value)| ((*timestamp - now).num_seconds() as f64 / 3600.0, *value))
+            .collect();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+        let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+        let sxx: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+        let sxy: f64 = points.iter().map(|(x, y)| (x - mean_x) * (y - mean_y)).sum();
+        let syy: f64 = points.iter().map(|(_, y)| (y - mean_y).powi(2)).sum();
+        if sxx <= f64::EPSILON {
+            return None;
+        }
+
+        let slope = sxy / sxx;
+        let intercept = mean_y - slope * mean_x;
+        let residual: f64 = points
+            .iter()
+            .map(|(x, y)| (y - (intercept + slope * x)).powi(2))
+            .sum();
+        let slope_std_error = (residual / (n - 2.0) / sxx).sqrt();
+        let r_squared = if syy > f64::EPSILON { 1.0 - residual / syy } else { 1.0 };
+
+        Some(Self {
+            slope_per_hour: slope,
+            slope_std_error,
+            fitted_now: intercept,
+            r_squared,
+            samples: points.len(),
+        })
+    }
+
+    /// 按给定斜率计算到达阈值所需的小时数，趋势不上升时返回 None
+    fn hours_at_slope(&self, threshold: f64, slope: f64) -> Option<f64> {
+        if self.fitted_now >= threshold {
+            return Some(0.0);
+        }
+        (slope > f64::EPSILON).then(|| (threshold - self.fitted_now) / slope)
+    }
+}
+
+// 资源耗尽预测结果
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ResourceForecast {
+    pub metric: AlertMetric,
+    /// 磁盘为挂载点，内存为空
+    pub target: Option<String>,
+    /// 最近一次记录的使用率
+    pub current: f64,
+    pub threshold: f64,
+    pub lookback_hours: u32,
+    pub trend: TrendFit,
+    /// 预计到达阈值的小时数，趋势不上升时为空
+    pub hours_to_threshold: Option<f64>,
+    /// 95% 置信区间下限（最早）
+    pub earliest_hours: Option<f64>,
+    /// 95% 置信区间上限（最晚），斜率可能不为正时为空
+    pub latest_hours: Option<f64>,
+    pub estimated_at: Option<DateTime<Utc>>,
+}
+
+impl ResourceForecast {
+    /// 根据历史样本预测指标到达阈值的时间
+    pub fn compute(
+        metric: AlertMetric,
+        target: Option<String>,
+        samples: &[(DateTime<Utc>, f64)],
+        threshold: f64,
+        lookback_hours: u32,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let trend = TrendFit::fit(samples, now)?;
+        let current = samples.iter().max_by_key(|(timestamp, _)| *timestamp)?.1;
+        let spread = trend.slope_std_error * CONFIDENCE_Z;
+        let hours_to_threshold = trend.hours_at_slope(threshold, trend.slope_per_hour);
+
+        Some(Self {
+            metric,
+            target,
+            current,
+            threshold,
+            lookback_hours,
+            trend,
+            hours_to_threshold,
+            earliest_hours: trend.hours_at_slope(threshold, trend.slope_per_hour + spread),
+            latest_hours: trend.hours_at_slope(threshold, trend.slope_per_hour - spread),
+            // 趋势接近水平时剩余时间可能超出可表示的时间范围
+            estimated_at: hours_to_threshold
+                .and_then(|hours| chrono::Duration::try_seconds((hours * 3600.0) as i64))
+                .and_then(|remaining| now.checked_add_signed(remaining)),
+        })
+    }
+
+    /// 预测对象的可读名称，例如 `disk /home`
+    pub fn subject(&self) -> String {
+        match &self.target {
+            Some(target) => format!("{} {}", self.metric, target),
+            None => self.metric.to_string(),
+        }
+    }
+
+    /// 预测是否满足触发条件
+    pub fn fires(&self, condition: &ForecastCondition) -> bool {
+        self.hours_to_threshold
+            .is_some_and(|hours| hours < condition.within_hours)
+    }
+
+    /// 从多个对象的预测中选出满足条件且最先耗尽的一个
+    pub fn soonest_firing(forecasts: Vec<Self>, condition: &ForecastCondition) -> Option<Self> {
+        forecasts
+            .into_iter()
+            .filter(|forecast| forecast.fires(condition))
+            .min_by(|a, b| {
+                a.hours_to_threshold
+                    .partial_cmp(&b.hours_to_threshold)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+    }
+}
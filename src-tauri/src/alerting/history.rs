@@ -0,0 +1,177 @@
+//! 警报历史查询与统计
+//! 过滤条件用于历史查询和批量确认，统计包括每条规则的触发次数、平均确认时间（MTTA）和平均恢复时间（MTTR）
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AlertSeverity;
+use crate::database::AlertHistory;
+
+// 警报历史过滤条件
+// 各字段之间为“与”关系，同一字段内为“或”关系；全部为空时匹配所有记录
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AlertHistoryFilter {
+    #[serde(default)]
+    pub rule_ids: Vec<String>,
+    /// 按记录当前的严重程度匹配，升级后的记录按升级后的级别
+    #[serde(default)]
+    pub severities: Vec<AlertSeverity>,
+    /// 按规则的目标标签匹配
+    #[serde(default)]
+    pub targets: Vec<String>,
+    #[serde(default)]
+    pub start_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end_time: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub acknowledged: Option<bool>,
+    #[serde(default)]
+    pub suppressed: Option<bool>,
+    /// 按所属事件匹配
+    #[serde(default)]
+    pub incident_ids: Vec<String>,
+}
+
+impl AlertHistoryFilter {
+    /// 校验过滤条件，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if let (Some(start), Some(end)) = (self.start_time, self.end_time) {
+            if end < start {
+                return Err("Filter end time must not be before its start time".to_string());
+            }
+        }
+        if self.rule_ids.iter().any(|id| id.trim().is_empty())
+            || self.targets.iter().any(|target| target.trim().is_empty())
+            || self.incident_ids.iter().any(|id| id.trim().is_empty())
+        {
+            return Err("Filter must not contain empty entries".to_string());
+        }
+        Ok(())
+    }
+
+    /// 没有任何过滤条件，匹配所有记录
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+// 单条规则的警报统计
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertRuleStatistics {
+    pub rule_id: String,
+    pub firing_count: u32,
+    pub acknowledged_count: u32,
+    pub suppressed_count: u32,
+    pub mean_time_to_acknowledge_secs: Option<f64>,
+    pub mean_time_to_resolve_secs: Option<f64>,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+}
+
+// 警报统计
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct AlertStatistics {
+    pub total: u32,
+    pub acknowledged: u32,
+    pub unacknowledged: u32,
+    pub suppressed: u32,
+    pub resolved: u32,
+    pub mean_time_to_acknowledge_secs: Option<f64>,
+    pub mean_time_to_resolve_secs: Option<f64>,
+    /// 按触发次数从多到少排列
+    pub rules: Vec<AlertRuleStatistics>,
+    /// 触发次数最多的若干条规则
+    pub noisiest_rules: Vec<AlertRuleStatistics>,
+}
+
+// 累计平均耗时
+#[derive(Default)]
+struct MeanDuration {
+    total_secs: f64,
+    count: u32,
+}
+
+impl MeanDuration {
+    fn push(&mut self, from: DateTime<Utc>, to: Option<DateTime<Utc>>) {
+        // 时间早于触发时间的记录来自旧版本的错误数据，不计入
+        if let Some(to) = to.filter(|to| *to >= from) {
+            self.total_secs += (to - from).num_milliseconds() as f64 / 1000.0;
+            self.count += 1;
+        }
+    }
+
+    fn mean(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total_secs / self.count as f64)
+    }
+}
+
+#[derive(Default)]
+struct RuleAccumulator {
+    firing_count: u32,
+    acknowledged_count: u32,
+    suppressed_count: u32,
+    acknowledge: MeanDuration,
+    resolve: MeanDuration,
+    last_triggered_at: Option<DateTime<Utc>>,
+}
+
+impl AlertStatistics {
+    /// 根据警报历史记录计算统计，`noisiest_limit` 为最吵规则列表的长度
+    pub fn from_history(history: &[AlertHistory], noisiest_limit: usize) -> Self {
+        let mut statistics = Self::default();
+        let mut acknowledge = MeanDuration::default();
+        let mut resolve = MeanDuration::default();
+        let mut rules: HashMap<&str, RuleAccumulator> = HashMap::new();
+
+        for alert in history {
+            statistics.total += 1;
+            if alert.acknowledged {
+                statistics.acknowledged += 1;
+            }
+            if alert.suppressed {
+                statistics.suppressed += 1;
+            }
+            if alert.resolved_at.is_some() {
+                statistics.resolved += 1;
+            }
+            acknowledge.push(alert.triggered_at, alert.acknowledged_at);
+            resolve.push(alert.triggered_at, alert.resolved_at);
+
+            let rule = rules.entry(alert.alert_id.as_str()).or_default();
+            rule.firing_count += 1;
+            if alert.acknowledged {
+                rule.acknowledged_count += 1;
+            }
+            if alert.suppressed {
+                rule.suppressed_count += 1;
+            }
+            rule.acknowledge.push(alert.triggered_at, alert.acknowledged_at);
+            rule.resolve.push(alert.triggered_at, alert.resolved_at);
+            rule.last_triggered_at = rule.last_triggered_at.max(Some(alert.triggered_at));
+        }
+
+        statistics.unacknowledged = statistics.total - statistics.acknowledged;
+        statistics.mean_time_to_acknowledge_secs = acknowledge.mean();
+        statistics.mean_time_to_resolve_secs = resolve.mean();
+
+        statistics.rules = rules
+            .into_iter()
+            .map(|(rule_id, rule)| AlertRuleStatistics {
+                rule_id: rule_id.to_string(),
+                firing_count: rule.firing_count,
+                acknowledged_count: rule.acknowledged_count,
+                suppressed_count: rule.suppressed_count,
+                mean_time_to_acknowledge_secs: rule.acknowledge.mean(),
+                mean_time_to_resolve_secs: rule.resolve.mean(),
+                last_triggered_at: rule.last_triggered_at,
+            })
+            .collect();
+        statistics
+            .rules
+            .sort_by(|a, b| b.firing_count.cmp(&a.firing_count).then_with(|| a.rule_id.cmp(&b.rule_id)));
+        statistics.noisiest_rules = statistics.rules.iter().take(noisiest_limit).cloned().collect();
+
+        statistics
+    }
+}
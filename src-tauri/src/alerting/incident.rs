@@ -0,0 +1,228 @@
+//! 警报事件（incident）
+//! 短时间内相继触发或目标相同的警报归入同一事件，事件有状态、时间线和备注；
+//! 确认事件会同时确认其中的所有警报
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::AlertSeverity;
+use crate::database::AlertHistory;
+
+// 分组窗口的上限
+const MAX_GROUPING_WINDOW_SECS: u64 = 24 * 60 * 60;
+
+fn default_window_secs() -> u64 {
+    300
+}
+
+fn default_group_by_target() -> bool {
+    true
+}
+
+// 警报分组方式
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IncidentGrouping {
+    /// 事件打开后该时长内触发的新警报归入该事件；窗口从事件打开时起算，
+    /// 持续触发的警报不会使事件无限延续
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// 目标相同的警报归入同一事件，不受时间窗口限制
+    #[serde(default = "default_group_by_target")]
+    pub group_by_target: bool,
+}
+
+impl Default for IncidentGrouping {
+    fn default() -> Self {
+        Self {
+            window_secs: default_window_secs(),
+            group_by_target: default_group_by_target(),
+        }
+    }
+}
+
+impl IncidentGrouping {
+    /// 校验分组方式，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.window_secs == 0 || self.window_secs > MAX_GROUPING_WINDOW_SECS {
+            return Err(format!(
+                "Incident grouping window must be between 1 and {} seconds",
+                MAX_GROUPING_WINDOW_SECS
+            ));
+        }
+        Ok(())
+    }
+
+    /// 为新触发的警报选择要加入的事件，已解决的事件不参与分组；
+    /// 优先选择目标相同的事件，其次选择窗口内最近打开的事件
+    pub fn select<'a>(&self, incidents: &'a [Incident], target: Option<&str>, now: DateTime<Utc>) -> Option<&'a Incident> {
+        let active = incidents.iter().filter(|incident| incident.status != IncidentStatus::Resolved);
+
+        if self.group_by_target {
+            if let Some(target) = target {
+                let shared = active
+                    .clone()
+                    .filter(|incident| incident.targets.iter().any(|other| other == target))
+                    .max_by_key(|incident| incident.last_alert_at);
+                if shared.is_some() {
+                    return shared;
+                }
+            }
+        }
+
+        let window = Duration::seconds(self.window_secs as i64);
+        active
+            .filter(|incident| now - incident.opened_at <= window)
+            .max_by_key(|incident| incident.opened_at)
+    }
+}
+
+// 事件状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentStatus {
+    Open,
+    Acknowledged,
+    /// 所有警报已恢复或手动解决
+    Resolved,
+}
+
+impl IncidentStatus {
+    /// 状态的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Acknowledged => "acknowledged",
+            Self::Resolved => "resolved",
+        }
+    }
+
+    /// 按存储名称解析状态
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "open" => Some(Self::Open),
+            "acknowledged" => Some(Self::Acknowledged),
+            "resolved" => Some(Self::Resolved),
+            _ => None,
+        }
+    }
+}
+
+// 事件时间线条目的类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IncidentEventKind {
+    Opened,
+    /// 新的规则触发并加入事件，同一规则的重复触发不再记录
+    AlertAdded,
+    Acknowledged,
+    Resolved,
+    Note,
+}
+
+impl IncidentEventKind {
+    /// 类型的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Opened => "opened",
+            Self::AlertAdded => "alert_added",
+            Self::Acknowledged => "acknowledged",
+            Self::Resolved => "resolved",
+            Self::Note => "note",
+        }
+    }
+
+    /// 按存储名称解析类型
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "opened" => Some(Self::Opened),
+            "alert_added" => Some(Self::AlertAdded),
+            "acknowledged" => Some(Self::Acknowledged),
+            "resolved" => Some(Self::Resolved),
+            "note" => Some(Self::Note),
+            _ => None,
+        }
+    }
+}
+
+// 事件时间线条目
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IncidentEvent {
+    pub id: String,
+    pub incident_id: String,
+    pub kind: IncidentEventKind,
+    pub message: String,
+    /// 自动记录的条目为空
+    pub author: Option<String>,
+    pub alert_history_id: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+// 事件
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Incident {
+    pub id: String,
+    pub title: String,
+    pub status: IncidentStatus,
+    /// 成员警报中最高的严重程度
+    pub severity: AlertSeverity,
+    pub opened_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub last_alert_at: DateTime<Utc>,
+    pub acknowledged_at: Option<DateTime<Utc>>,
+    pub acknowledged_by: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub alert_count: u32,
+    pub rule_ids: Vec<String>,
+    pub targets: Vec<String>,
+}
+
+impl Incident {
+    /// 由第一条警报创建事件
+    pub fn open(title: String, alert: &AlertHistory, target: Option<&str>) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            title,
+            status: IncidentStatus::Open,
+            severity: alert.severity.unwrap_or(AlertSeverity::Low),
+            opened_at: alert.triggered_at,
+            updated_at: alert.triggered_at,
+            last_alert_at: alert.triggered_at,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            resolved_at: None,
+            alert_count: 1,
+            rule_ids: vec![alert.alert_id.clone()],
+            targets: target.map(|target| vec![target.to_string()]).unwrap_or_default(),
+        }
+    }
+
+    /// 将警报加入事件，返回该警报的规则是否首次加入
+    pub fn add_alert(&mut self, alert: &AlertHistory, target: Option<&str>) -> bool {
+        self.alert_count += 1;
+        self.last_alert_at = self.last_alert_at.max(alert.triggered_at);
+        self.updated_at = self.updated_at.max(alert.triggered_at);
+        if let Some(severity) = alert.severity {
+            self.severity = self.severity.max(severity);
+        }
+        if let Some(target) = target {
+            if !self.targets.iter().any(|other| other == target) {
+                self.targets.push(target.to_string());
+            }
+        }
+        if self.rule_ids.contains(&alert.alert_id) {
+            return false;
+        }
+        self.rule_ids.push(alert.alert_id.clone());
+        true
+    }
+}
+
+// 事件详情
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IncidentDetail {
+    pub incident: Incident,
+    /// 按触发时间排列
+    pub alerts: Vec<AlertHistory>,
+    /// 按时间排列
+    pub timeline: Vec<IncidentEvent>,
+}
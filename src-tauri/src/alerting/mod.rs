@@ -0,0 +1,421 @@
+//! 警报引擎
+//! 定义警报规则的指标、比较方式和严重程度，以及规则求值时使用的指标快照
+
+pub mod anomaly;
+pub mod escalation;
+pub mod expression;
+pub mod forecast;
+pub mod history;
+pub mod incident;
+pub mod notification;
+pub mod prometheus;
+pub mod remediation;
+pub mod silence;
+pub mod simulation;
+pub mod template;
+pub mod transfer;
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::database::{AlertConfiguration, HistoricalSystemData};
+use crate::process::fds::FdUsage;
+use expression::AlertExpression;
+
+// 警报可引用的指标
+// 序列化使用前端的长名称（如 `cpu_usage`），反序列化同时接受短名称
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AlertMetric {
+    Cpu,
+    Memory,
+    Disk,
+    Network,
+    Load,
+    Swap,
+    Cores,
+    /// 单个进程持有的最大文件描述符数量
+    OpenFds,
+}
+
+impl AlertMetric {
+    /// 按名称解析指标，同时接受前端使用的长名称（如 `cpu_usage`）
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "cpu" | "cpu_usage" => Some(Self::Cpu),
+            "memory" | "memory_usage" | "mem" => Some(Self::Memory),
+            "disk" | "disk_usage" => Some(Self::Disk),
+            "network" | "network_traffic" | "net" => Some(Self::Network),
+            "load" | "system_load" => Some(Self::Load),
+            "swap" | "swap_usage" => Some(Self::Swap),
+            "cores" | "cpu_cores" => Some(Self::Cores),
+            "fds" | "open_fds" | "file_descriptors" => Some(Self::OpenFds),
+            _ => None,
+        }
+    }
+
+    /// 指标的存储及序列化名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu_usage",
+            Self::Memory => "memory_usage",
+            Self::Disk => "disk_usage",
+            Self::Network => "network_traffic",
+            Self::Load => "system_load",
+            Self::Swap => "swap_usage",
+            Self::Cores => "cpu_cores",
+            Self::OpenFds => "open_fds",
+        }
+    }
+
+    /// 指标在表达式中使用的短名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Memory => "memory",
+            Self::Disk => "disk",
+            Self::Network => "network",
+            Self::Load => "load",
+            Self::Swap => "swap",
+            Self::Cores => "cores",
+            Self::OpenFds => "fds",
+        }
+    }
+
+    /// 指标值是否为百分比
+    pub fn is_percentage(&self) -> bool {
+        matches!(self, Self::Cpu | Self::Memory | Self::Disk | Self::Swap)
+    }
+
+    /// 指标是否在历史数据表中有记录
+    pub fn has_history(&self) -> bool {
+        matches!(self, Self::Cpu | Self::Memory | Self::Disk | Self::Network | Self::Load | Self::OpenFds)
+    }
+
+    /// 从一条历史记录中取出该指标的值
+    pub fn historical_value(&self, record: &HistoricalSystemData) -> Option<f64> {
+        match self {
+            Self::Cpu => Some(record.cpu_usage as f64),
+            Self::Memory => Some(record.memory_usage as f64),
+            Self::Load => Some(record.system_load as f64),
+            // 多个挂载点时取使用率最高的一个
+            Self::Disk => record
+                .disk_usage
+                .iter()
+                .map(|disk| disk.usage_percent as f64)
+                .reduce(f64::max),
+            Self::Network => Some(
+                (record.network_traffic.bytes_received + record.network_traffic.bytes_sent) as f64,
+            ),
+            Self::OpenFds => record.open_fds.map(f64::from),
+            Self::Swap | Self::Cores => None,
+        }
+    }
+}
+
+impl FromStr for AlertMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_name(s.trim()).ok_or_else(|| {
+            format!(
+                "unknown metric `{}` (expected cpu_usage, memory_usage, disk_usage, network_traffic, system_load, swap_usage or open_fds)",
+                s
+            )
+        })
+    }
+}
+
+impl TryFrom<String> for AlertMetric {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AlertMetric> for String {
+    fn from(metric: AlertMetric) -> Self {
+        metric.as_str().to_string()
+    }
+}
+
+impl fmt::Display for AlertMetric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// 阈值比较方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AlertComparison {
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    Equals,
+    NotEquals,
+}
+
+impl AlertComparison {
+    /// 比较方式的存储及序列化名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GreaterThan => "greater_than",
+            Self::GreaterOrEqual => "greater_or_equal",
+            Self::LessThan => "less_than",
+            Self::LessOrEqual => "less_or_equal",
+            Self::Equals => "equals",
+            Self::NotEquals => "not_equals",
+        }
+    }
+
+    /// 比较运算符
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::GreaterThan => ">",
+            Self::GreaterOrEqual => ">=",
+            Self::LessThan => "<",
+            Self::LessOrEqual => "<=",
+            Self::Equals => "==",
+            Self::NotEquals => "!=",
+        }
+    }
+
+    pub fn apply(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::GreaterThan => value > threshold,
+            Self::GreaterOrEqual => value >= threshold,
+            Self::LessThan => value < threshold,
+            Self::LessOrEqual => value <= threshold,
+            Self::Equals => (value - threshold).abs() < f64::EPSILON,
+            Self::NotEquals => (value - threshold).abs() >= f64::EPSILON,
+        }
+    }
+}
+
+impl FromStr for AlertComparison {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            ">" | "gt" | "greater_than" => Ok(Self::GreaterThan),
+            ">=" | "ge" | "gte" | "greater_or_equal" | "greater_than_or_equal" => Ok(Self::GreaterOrEqual),
+            "<" | "lt" | "less_than" => Ok(Self::LessThan),
+            "<=" | "le" | "lte" | "less_or_equal" | "less_than_or_equal" => Ok(Self::LessOrEqual),
+            "==" | "=" | "eq" | "equals" => Ok(Self::Equals),
+            "!=" | "<>" | "ne" | "not_equals" => Ok(Self::NotEquals),
+            _ => Err(format!(
+                "unknown condition `{}` (expected greater_than, greater_or_equal, less_than, less_or_equal, equals or not_equals)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for AlertComparison {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AlertComparison> for String {
+    fn from(comparison: AlertComparison) -> Self {
+        comparison.as_str().to_string()
+    }
+}
+
+impl fmt::Display for AlertComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+// 警报严重程度
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum AlertSeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl AlertSeverity {
+    /// 严重程度的存储及序列化名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+            Self::Critical => "critical",
+        }
+    }
+}
+
+impl FromStr for AlertSeverity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "low" | "info" => Ok(Self::Low),
+            "medium" | "warning" | "warn" => Ok(Self::Medium),
+            "high" | "error" => Ok(Self::High),
+            "critical" | "crit" | "fatal" => Ok(Self::Critical),
+            _ => Err(format!(
+                "unknown severity `{}` (expected low, medium, high or critical)",
+                s
+            )),
+        }
+    }
+}
+
+impl TryFrom<String> for AlertSeverity {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<AlertSeverity> for String {
+    fn from(severity: AlertSeverity) -> Self {
+        severity.as_str().to_string()
+    }
+}
+
+impl fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// 检查警报时的指标快照
+#[derive(Debug, Clone, Default)]
+pub struct MetricSnapshot {
+    pub cpu: f64,
+    pub memory: f64,
+    pub disk: f64,
+    pub network: f64,
+    pub load: f64,
+    pub swap: f64,
+    pub cores: f64,
+    /// 平台不支持或没有规则使用时不采集，使用它的规则无法求值
+    pub open_fds: Option<f64>,
+    /// 持有文件描述符最多的进程，`open_fds` 即其数量
+    pub open_fds_process: Option<FdUsage>,
+}
+
+impl MetricSnapshot {
+    /// 获取指定指标的当前值，未采集的指标返回 None
+    pub fn value(&self, metric: AlertMetric) -> Option<f64> {
+        match metric {
+            AlertMetric::Cpu => Some(self.cpu),
+            AlertMetric::Memory => Some(self.memory),
+            AlertMetric::Disk => Some(self.disk),
+            AlertMetric::Network => Some(self.network),
+            AlertMetric::Load => Some(self.load),
+            AlertMetric::Swap => Some(self.swap),
+            AlertMetric::Cores => Some(self.cores),
+            AlertMetric::OpenFds => self.open_fds,
+        }
+    }
+}
+
+/// 启用的规则中是否有规则使用该指标，包括表达式中引用的指标
+pub fn rules_use_metric(configurations: &[AlertConfiguration], metric: AlertMetric) -> bool {
+    configurations.iter().filter(|config| config.enabled).any(|config| match &config.expression {
+        Some(source) => AlertExpression::parse(source).is_ok_and(|expression| expression.metrics().contains(&metric)),
+        None => config.metric == metric,
+    })
+}
+
+// 单条规则的求值结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleEvaluation {
+    /// 条件成立，附带记录的值和与规则类型相关的描述，描述会代入消息模板的 `{details}`
+    Firing { value: f64, details: String },
+    /// 条件不成立，此前触发的警报视为已恢复
+    Clear,
+    /// 数据不足等原因无法求值，保持原有状态
+    Unknown,
+}
+
+/// 校验警报配置，返回可直接展示给用户的错误信息
+pub fn validate_configuration(config: &AlertConfiguration) -> Result<(), String> {
+    if config.notification_methods.iter().any(|method| method.trim().is_empty()) {
+        return Err("Notification methods must not contain empty entries".to_string());
+    }
+
+    if let Some(template) = &config.message_template {
+        template::validate_template(template)?;
+    }
+
+    if let Some(policy) = &config.remediation {
+        policy.validate()?;
+    }
+
+    let rule_types = [config.expression.is_some(), config.anomaly.is_some(), config.forecast.is_some()];
+    if rule_types.iter().filter(|set| **set).count() > 1 {
+        return Err("An alert rule can use only one of expression, anomaly detection or forecast".to_string());
+    }
+
+    if let Some(condition) = &config.forecast {
+        if !forecast::supports_forecast(config.metric) {
+            return Err(format!(
+                "Forecasting is only available for disk_usage and memory_usage, not {}",
+                config.metric.as_str()
+            ));
+        }
+        return condition.validate();
+    }
+
+    if let Some(detection) = &config.anomaly {
+        if !config.metric.has_history() {
+            return Err(format!(
+                "Anomaly detection needs stored history, which {} does not have",
+                config.metric.as_str()
+            ));
+        }
+        return detection.validate();
+    }
+
+    if let Some(source) = &config.expression {
+        if source.trim().is_empty() {
+            return Err("Alert expression must not be empty".to_string());
+        }
+        AlertExpression::parse(source).map_err(|e| format!("Invalid alert expression: {}", e))?;
+        return Ok(());
+    }
+
+    if config.metric == AlertMetric::Cores {
+        return Err("Metric `cpu_cores` is a constant and can only be used inside an expression".to_string());
+    }
+
+    if !config.threshold.is_finite() {
+        return Err(format!("Threshold must be a finite number, got {}", config.threshold));
+    }
+
+    if config.metric.is_percentage() && !(0.0..=100.0).contains(&config.threshold) {
+        return Err(format!(
+            "Threshold for {} is a percentage and must be between 0 and 100, got {}",
+            config.metric.as_str(),
+            config.threshold
+        ));
+    }
+
+    if !config.metric.is_percentage() && config.threshold < 0.0 {
+        return Err(format!(
+            "Threshold for {} must not be negative, got {}",
+            config.metric.as_str(),
+            config.threshold
+        ));
+    }
+
+    Ok(())
+}
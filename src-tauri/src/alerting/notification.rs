@@ -0,0 +1,382 @@
+//! 通知限流与摘要
+//! 每条警报按通知方式逐一决定立即发送、放入摘要，或因超出频率限制转入溢出摘要；
+//! 放入摘要和溢出的通知都会在到期后汇总为一条摘要发送，不会被静默丢弃
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::template::MessageLocale;
+use super::{AlertComparison, AlertSeverity};
+use crate::database::{AlertConfiguration, AlertHistory};
+
+fn default_digest_interval_secs() -> u64 {
+    900
+}
+
+// 频率限制：`period_secs` 秒内最多发送 `max_notifications` 条
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub max_notifications: u32,
+    pub period_secs: u64,
+}
+
+impl RateLimit {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        if self.max_notifications == 0 {
+            return Err(format!("{} must allow at least one notification", name));
+        }
+        if self.period_secs == 0 {
+            return Err(format!("{} period must be at least one second", name));
+        }
+        Ok(())
+    }
+
+    fn period(&self) -> Duration {
+        Duration::seconds(self.period_secs as i64)
+    }
+}
+
+// 摘要模式
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DigestSettings {
+    /// 摘要的发送间隔
+    #[serde(default = "default_digest_interval_secs")]
+    pub interval_secs: u64,
+    /// 使用摘要的通知方式，为空时所有通知方式都使用摘要
+    #[serde(default)]
+    pub channels: Vec<String>,
+}
+
+// 通知策略，全部为空时所有通知立即发送
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct NotificationPolicy {
+    /// 所有通知方式合计的频率限制
+    #[serde(default)]
+    pub global_limit: Option<RateLimit>,
+    /// 按通知方式的频率限制
+    #[serde(default)]
+    pub channel_limits: BTreeMap<String, RateLimit>,
+    #[serde(default)]
+    pub digest: Option<DigestSettings>,
+}
+
+impl NotificationPolicy {
+    /// 校验通知策略，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(limit) = &self.global_limit {
+            limit.validate("Global rate limit")?;
+        }
+        for (channel, limit) in &self.channel_limits {
+            if channel.trim().is_empty() {
+                return Err("Rate limits must not contain an empty notification method".to_string());
+            }
+            limit.validate(&format!("Rate limit for {}", channel))?;
+        }
+        if let Some(digest) = &self.digest {
+            if digest.interval_secs == 0 {
+                return Err("Digest interval must be at least one second".to_string());
+            }
+            if digest.channels.iter().any(|channel| channel.trim().is_empty()) {
+                return Err("Digest must not contain an empty notification method".to_string());
+            }
+        }
+        Ok(())
+    }
+
+    /// 该通知方式是否使用摘要
+    pub fn digests_channel(&self, channel: &str) -> bool {
+        self.digest.as_ref().is_some_and(|digest| {
+            digest.channels.is_empty() || digest.channels.iter().any(|other| other == channel)
+        })
+    }
+
+    /// 该通知方式的待发送通知汇总为摘要的间隔：使用摘要时为摘要间隔，
+    /// 否则为限制该通知方式的最长限流周期，限流周期结束后发送溢出摘要
+    pub fn flush_interval(&self, channel: &str) -> Duration {
+        if let Some(digest) = self.digest.as_ref().filter(|_| self.digests_channel(channel)) {
+            return Duration::seconds(digest.interval_secs as i64);
+        }
+        self.global_limit
+            .iter()
+            .chain(self.channel_limits.get(channel))
+            .map(RateLimit::period)
+            .max()
+            .unwrap_or_else(Duration::zero)
+    }
+
+    /// 需要加载的最近发送记录的时长
+    pub fn lookback(&self) -> Duration {
+        self.global_limit
+            .iter()
+            .chain(self.channel_limits.values())
+            .map(RateLimit::period)
+            .max()
+            .unwrap_or_else(Duration::zero)
+    }
+}
+
+// 单条通知的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationDisposition {
+    /// 立即发送
+    Sent,
+    /// 放入摘要
+    Batched,
+    /// 超出频率限制，放入溢出摘要
+    Overflow,
+}
+
+impl NotificationDisposition {
+    /// 处理方式的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sent => "sent",
+            Self::Batched => "batched",
+            Self::Overflow => "overflow",
+        }
+    }
+
+    /// 按存储名称解析处理方式
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sent" => Some(Self::Sent),
+            "batched" => Some(Self::Batched),
+            "overflow" => Some(Self::Overflow),
+            _ => None,
+        }
+    }
+}
+
+// 根据最近的发送记录决定每条通知的处理方式
+pub struct NotificationLimiter<'a> {
+    policy: &'a NotificationPolicy,
+    /// 限流周期内已发送的通知
+    sent: Vec<(DateTime<Utc>, String)>,
+}
+
+impl<'a> NotificationLimiter<'a> {
+    pub fn new(policy: &'a NotificationPolicy, sent: Vec<(DateTime<Utc>, String)>) -> Self {
+        Self { policy, sent }
+    }
+
+    /// 决定一条通知的处理方式，立即发送的通知计入之后的限流
+    pub fn decide(&mut self, channel: &str, now: DateTime<Utc>) -> NotificationDisposition {
+        if self.policy.digests_channel(channel) {
+            return NotificationDisposition::Batched;
+        }
+
+        let count_since = |sent: &[(DateTime<Utc>, String)], limit: &RateLimit, channel: Option<&str>| {
+            let since = now - limit.period();
+            sent.iter()
+                .filter(|(sent_at, _)| *sent_at > since)
+                .filter(|(_, other)| channel.is_none() || channel == Some(other.as_str()))
+                .count()
+        };
+        let over_global = self
+            .policy
+            .global_limit
+            .as_ref()
+            .is_some_and(|limit| count_since(&self.sent, limit, None) >= limit.max_notifications as usize);
+        let over_channel = self
+            .policy
+            .channel_limits
+            .get(channel)
+            .is_some_and(|limit| count_since(&self.sent, limit, Some(channel)) >= limit.max_notifications as usize);
+        if over_global || over_channel {
+            return NotificationDisposition::Overflow;
+        }
+
+        self.sent.push((now, channel.to_string()));
+        NotificationDisposition::Sent
+    }
+}
+
+/// 该规则的值是否越低越严重：低于阈值触发的规则和耗尽预测（剩余小时数）越低越严重
+pub fn lower_is_worse(config: &AlertConfiguration) -> bool {
+    if config.forecast.is_some() {
+        return true;
+    }
+    config.expression.is_none()
+        && config.anomaly.is_none()
+        && matches!(config.condition, AlertComparison::LessThan | AlertComparison::LessOrEqual)
+}
+
+// 未发送的通知，等待汇总为摘要
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PendingNotification {
+    pub alert_history_id: String,
+    pub rule_id: String,
+    pub channel: String,
+    pub disposition: NotificationDisposition,
+    pub value: f64,
+    pub severity: Option<AlertSeverity>,
+    pub created_at: DateTime<Utc>,
+}
+
+// 摘要中单条规则的汇总
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DigestRuleSummary {
+    pub rule_id: String,
+    pub count: u32,
+    pub worst_value: f64,
+    /// 取得最严重值的警报
+    pub worst_alert_id: String,
+    pub severity: Option<AlertSeverity>,
+}
+
+// 通知摘要
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationDigest {
+    pub id: String,
+    pub channel: String,
+    pub created_at: DateTime<Utc>,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    pub alert_count: u32,
+    /// 因超出频率限制而未立即发送的数量
+    pub overflow_count: u32,
+    /// 按触发次数从多到少排列
+    pub rules: Vec<DigestRuleSummary>,
+    /// 摘要包含的警报，按时间排列
+    pub alert_ids: Vec<String>,
+    pub message: String,
+}
+
+impl NotificationDigest {
+    /// 汇总一个通知方式的待发送通知；`lower_is_worse` 为值越低越严重的规则
+    pub fn build(
+        channel: &str,
+        pending: &[PendingNotification],
+        lower_is_worse: &HashMap<String, bool>,
+        locale: MessageLocale,
+        now: DateTime<Utc>,
+    ) -> Option<Self> {
+        let period_start = pending.iter().map(|entry| entry.created_at).min()?;
+        let period_end = pending.iter().map(|entry| entry.created_at).max()?;
+
+        let mut rules: Vec<DigestRuleSummary> = Vec::new();
+        for entry in pending {
+            let lower = lower_is_worse.get(&entry.rule_id).copied().unwrap_or(false);
+            match rules.iter_mut().find(|rule| rule.rule_id == entry.rule_id) {
+                Some(rule) => {
+                    rule.count += 1;
+                    let worse = if lower { entry.value < rule.worst_value } else { entry.value > rule.worst_value };
+                    if worse {
+                        rule.worst_value = entry.value;
+                        rule.worst_alert_id = entry.alert_history_id.clone();
+                    }
+                    rule.severity = rule.severity.max(entry.severity);
+                }
+                None => rules.push(DigestRuleSummary {
+                    rule_id: entry.rule_id.clone(),
+                    count: 1,
+                    worst_value: entry.value,
+                    worst_alert_id: entry.alert_history_id.clone(),
+                    severity: entry.severity,
+                }),
+            }
+        }
+        rules.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.rule_id.cmp(&b.rule_id)));
+
+        let mut entries: Vec<&PendingNotification> = pending.iter().collect();
+        entries.sort_by_key(|entry| entry.created_at);
+        let alert_count = pending.len() as u32;
+        let overflow_count = pending
+            .iter()
+            .filter(|entry| entry.disposition == NotificationDisposition::Overflow)
+            .count() as u32;
+
+        let message = digest_message(alert_count, overflow_count, &rules, period_start, period_end, locale);
+        Some(Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            channel: channel.to_string(),
+            created_at: now,
+            period_start,
+            period_end,
+            alert_count,
+            overflow_count,
+            rules,
+            alert_ids: entries.iter().map(|entry| entry.alert_history_id.clone()).collect(),
+            message,
+        })
+    }
+}
+
+fn digest_message(
+    alert_count: u32,
+    overflow_count: u32,
+    rules: &[DigestRuleSummary],
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+    locale: MessageLocale,
+) -> String {
+    let from = period_start.format("%H:%M");
+    let to = period_end.format("%H:%M");
+    let rule_list = rules
+        .iter()
+        .map(|rule| match locale {
+            MessageLocale::En => format!("{} ×{} (worst {:.2})", rule.rule_id, rule.count, rule.worst_value),
+            MessageLocale::Zh => format!("{} ×{}（最严重 {:.2}）", rule.rule_id, rule.count, rule.worst_value),
+        })
+        .collect::<Vec<_>>();
+
+    match locale {
+        MessageLocale::En => {
+            let overflow = if overflow_count > 0 {
+                format!(", {} held back by rate limits", overflow_count)
+            } else {
+                String::new()
+            };
+            format!(
+                "{} alert(s) from {} rule(s) between {} and {} UTC{}: {}",
+                alert_count,
+                rules.len(),
+                from,
+                to,
+                overflow,
+                rule_list.join(", ")
+            )
+        }
+        MessageLocale::Zh => {
+            let overflow = if overflow_count > 0 {
+                format!("，其中 {} 条因频率限制未立即发送", overflow_count)
+            } else {
+                String::new()
+            };
+            format!(
+                "{} 至 {}（UTC）共 {} 条警报，来自 {} 条规则{}：{}",
+                from,
+                to,
+                alert_count,
+                rules.len(),
+                overflow,
+                rule_list.join("，")
+            )
+        }
+    }
+}
+
+// 立即发送的通知
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct NotificationDelivery {
+    pub alert: AlertHistory,
+    /// 可以立即发送的通知方式
+    pub channels: Vec<String>,
+    /// 测试警报，发送时应标明为测试
+    #[serde(default)]
+    pub test: bool,
+}
+
+// 本次需要发送的通知和摘要
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct NotificationPlan {
+    pub deliveries: Vec<NotificationDelivery>,
+    pub digests: Vec<NotificationDigest>,
+    /// 本次放入摘要的通知数量
+    pub batched: u32,
+    /// 本次因超出频率限制而转入溢出摘要的通知数量
+    pub overflowed: u32,
+}
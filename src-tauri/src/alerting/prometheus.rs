@@ -0,0 +1,393 @@
+//! Prometheus 告警规则导入
+//! 支持常见 node_exporter 表达式的子集：CPU、内存、交换分区、文件系统使用率和系统负载，
+//! 形如 `<指标表达式> <比较> <数字>`；其余规则连同原因一起报告，不会导入
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::{AlertComparison, AlertMetric, AlertSeverity};
+use crate::database::AlertConfiguration;
+
+#[derive(Deserialize)]
+struct PrometheusRuleFile {
+    #[serde(default)]
+    groups: Vec<PrometheusRuleGroup>,
+}
+
+#[derive(Deserialize)]
+struct PrometheusRuleGroup {
+    name: String,
+    #[serde(default)]
+    rules: Vec<PrometheusRule>,
+}
+
+#[derive(Deserialize)]
+struct PrometheusRule {
+    alert: Option<String>,
+    record: Option<String>,
+    expr: String,
+    #[serde(rename = "for")]
+    for_duration: Option<String>,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+// 无法映射的 Prometheus 规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UnmappedPrometheusRule {
+    pub group: String,
+    pub name: String,
+    pub expr: String,
+    pub reason: String,
+}
+
+// 转换结果
+#[derive(Debug, Clone, Default)]
+pub struct PrometheusConversion {
+    pub rules: Vec<AlertConfiguration>,
+    pub unmapped: Vec<UnmappedPrometheusRule>,
+    /// 已转换但有部分语义被忽略的规则，键为规则 ID
+    pub warnings: Vec<(String, String)>,
+}
+
+// 表达式映射结果
+#[derive(Debug, Clone, PartialEq)]
+struct MappedCondition {
+    metric: AlertMetric,
+    condition: AlertComparison,
+    threshold: f64,
+    target: Option<String>,
+    warnings: Vec<String>,
+}
+
+/// 将 Prometheus 告警规则文件转换为警报配置
+pub fn convert_prometheus_rules(content: &str) -> Result<PrometheusConversion, String> {
+    let file: PrometheusRuleFile =
+        serde_yaml::from_str(content).map_err(|e| format!("Invalid Prometheus rule file: {}", e))?;
+
+    let mut conversion = PrometheusConversion::default();
+    // 同名告警（例如不同分组中的同名规则）按出现顺序加数字后缀
+    let mut used_ids: HashMap<String, usize> = HashMap::new();
+    for group in file.groups {
+        for rule in group.rules {
+            let Some(name) = rule.alert.clone() else {
+                conversion.unmapped.push(UnmappedPrometheusRule {
+                    group: group.name.clone(),
+                    name: rule.record.clone().unwrap_or_default(),
+                    expr: rule.expr.clone(),
+                    reason: "recording rules are not alerts".to_string(),
+                });
+                continue;
+            };
+
+            let mapped = match map_expression(&rule.expr) {
+                Ok(mapped) => mapped,
+                Err(reason) => {
+                    conversion.unmapped.push(UnmappedPrometheusRule {
+                        group: group.name.clone(),
+                        name,
+                        expr: rule.expr.clone(),
+                        reason,
+                    });
+                    continue;
+                }
+            };
+
+            let base_id = format!("prometheus-{}", slug(&name));
+            let id = match used_ids.get(&base_id).copied() {
+                None => base_id.clone(),
+                Some(count) => (count + 1..)
+                    .map(|n| format!("{}-{}", base_id, n))
+                    .find(|candidate| !used_ids.contains_key(candidate))
+                    .unwrap_or_default(),
+            };
+            *used_ids.entry(base_id).or_insert(0) += 1;
+            used_ids.entry(id.clone()).or_insert(1);
+            let mut warnings = mapped.warnings;
+            if let Some(duration) = &rule.for_duration {
+                warnings.push(format!("`for: {}` is not supported and was ignored", duration));
+            }
+            let severity = match rule.labels.get("severity") {
+                Some(label) => label.parse().unwrap_or_else(|_| {
+                    warnings.push(format!("unknown severity `{}`, using medium", label));
+                    AlertSeverity::Medium
+                }),
+                None => AlertSeverity::Medium,
+            };
+            conversion
+                .warnings
+                .extend(warnings.into_iter().map(|warning| (id.clone(), warning)));
+
+            conversion.rules.push(AlertConfiguration {
+                id,
+                metric: mapped.metric,
+                condition: mapped.condition,
+                threshold: mapped.threshold,
+                severity,
+                enabled: true,
+                notification_methods: vec!["visual".to_string()],
+                expression: None,
+                target: mapped.target,
+                escalation_policy_id: None,
+                anomaly: None,
+                forecast: None,
+                message_template: None,
+                remediation: None,
+            });
+        }
+    }
+
+    Ok(conversion)
+}
+
+/// 将告警名称转换为规则 ID 中使用的小写短横线形式
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        // 驼峰写法在小写字母后的大写字母处断开，例如 HighCPU -> high-cpu
+        if c.is_ascii_uppercase() && previous_lowercase {
+            slug.push('-');
+        }
+        previous_lowercase = c.is_ascii_lowercase() || c.is_ascii_digit();
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+/// 将 `<左侧> <比较> <右侧>` 形式的 PromQL 映射为单一指标的阈值比较
+fn map_expression(expr: &str) -> Result<MappedCondition, String> {
+    let (lhs, comparison, rhs) = split_comparison(expr)?;
+
+    // 允许数字写在左侧，例如 `90 < cpu_usage`
+    let (query, comparison, threshold) = match (lhs.trim().parse::<f64>(), rhs.trim().parse::<f64>()) {
+        (_, Ok(threshold)) => (lhs, comparison, threshold),
+        (Ok(threshold), Err(_)) => (rhs, flip(comparison), threshold),
+        _ if rhs.trim_start().starts_with("bool") => {
+            return Err("the `bool` modifier is not supported".to_string())
+        }
+        _ => return Err("the comparison must be against a number".to_string()),
+    };
+
+    let query = query.trim();
+    let compact: String = query.chars().filter(|c| !c.is_whitespace()).collect();
+    let mut warnings = Vec::new();
+
+    // 本应用自己的指标名直接映射
+    if let Some(metric) = AlertMetric::from_name(query) {
+        return Ok(MappedCondition {
+            metric,
+            condition: comparison,
+            threshold,
+            target: None,
+            warnings,
+        });
+    }
+
+    let (metric, remaining_form, target) = if compact.contains("node_cpu_seconds_total") {
+        (AlertMetric::Cpu, compact.contains("mode=\"idle\""), None)
+    } else if compact.contains("node_memory_SwapFree_bytes") {
+        (AlertMetric::Swap, true, None)
+    } else if compact.contains("node_memory_MemAvailable_bytes") || compact.contains("node_memory_MemFree_bytes") {
+        (AlertMetric::Memory, true, None)
+    } else if compact.contains("node_filesystem_avail_bytes") || compact.contains("node_filesystem_free_bytes") {
+        (AlertMetric::Disk, true, label_value(query, "mountpoint"))
+    } else if let Some(load) = ["node_load1", "node_load5", "node_load15"]
+        .into_iter()
+        .rev()
+        .find(|name| compact.contains(name))
+    {
+        if load != "node_load1" {
+            warnings.push(format!("`{}` was mapped to the 1-minute load average", load));
+        }
+        return Ok(MappedCondition {
+            metric: AlertMetric::Load,
+            condition: comparison,
+            threshold,
+            target: None,
+            warnings,
+        });
+    } else {
+        return Err("no supported node_exporter metric found".to_string());
+    };
+
+    // 百分比写法乘以 100，否则按 0–1 的比例处理
+    let threshold = if scaled_to_percent(query) { threshold } else { threshold * 100.0 };
+    // `1 - 可用/总量` 表示已用，否则表达式描述的是剩余量，需要换算为使用率
+    let used_form = compact.starts_with("1-")
+        || compact.starts_with("100-")
+        || compact.contains("(1-")
+        || compact.contains("(100-");
+    // `剩余 < x` 等价于 `已用 > 100 - x`
+    let (condition, threshold) = if remaining_form && !used_form {
+        (flip(comparison), 100.0 - threshold)
+    } else {
+        (comparison, threshold)
+    };
+
+    if !(0.0..=100.0).contains(&threshold) {
+        return Err(format!("threshold {} is not a valid percentage", threshold));
+    }
+
+    Ok(MappedCondition {
+        metric,
+        condition,
+        threshold,
+        target,
+        warnings,
+    })
+}
+
+/// 找到唯一的顶层比较运算符，括号和标签选择器内部的运算符不计入
+fn split_comparison(expr: &str) -> Result<(&str, AlertComparison, &str), String> {
+    let bytes = expr.as_bytes();
+    let mut depth = 0i32;
+    let mut in_string = false;
+    let mut found = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        if in_string {
+            if c == b'\\' {
+                i += 1;
+            } else if c == b'"' {
+                in_string = false;
+            }
+            i += 1;
+            continue;
+        }
+        match c {
+            b'"' => in_string = true,
+            b'(' | b'[' | b'{' => depth += 1,
+            b')' | b']' | b'}' => depth -= 1,
+            b'>' | b'<' | b'=' | b'!' if depth == 0 => {
+                let two = expr.get(i..i + 2).unwrap_or("");
+                let (symbol, width) = match two {
+                    ">=" | "<=" | "==" | "!=" => (two, 2),
+                    _ if c == b'>' || c == b'<' => (&expr[i..i + 1], 1),
+                    _ => return Err(format!("unsupported operator at position {}", i)),
+                };
+                if found.is_some() {
+                    return Err("only a single comparison is supported".to_string());
+                }
+                let comparison: AlertComparison = symbol.parse()?;
+                found = Some((i, width, comparison));
+                i += width;
+                continue;
+            }
+            _ if depth == 0 && is_set_operator(expr, i) => {
+                return Err("combining conditions with and/or/unless is not supported".to_string());
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    let (position, width, comparison) = found.ok_or_else(|| "the expression has no comparison".to_string())?;
+    Ok((&expr[..position], comparison, &expr[position + width..]))
+}
+
+/// 判断位置 `i` 处是否为独立的 and/or/unless 关键字
+fn is_set_operator(expr: &str, i: usize) -> bool {
+    if !expr.is_char_boundary(i) {
+        return false;
+    }
+    let boundary = |c: Option<char>| !c.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !boundary(expr[..i].chars().next_back()) {
+        return false;
+    }
+    ["and", "or", "unless"].iter().any(|keyword| {
+        expr[i..].starts_with(keyword) && boundary(expr[i + keyword.len()..].chars().next())
+    })
+}
+
+/// 表达式中是否有乘以 100 的运算（`* 100` 或 `100 *`），标签值和区间选择器中的数字不计入
+fn scaled_to_percent(query: &str) -> bool {
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == ':';
+    let mut tokens: Vec<String> = Vec::new();
+    let mut depth = 0i32;
+    let mut chars = query.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                while let Some(inner) = chars.next() {
+                    match inner {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            _ if depth > 0 || c.is_whitespace() => {}
+            c if is_word(c) => {
+                let mut word = c.to_string();
+                while let Some(next) = chars.next_if(|next| is_word(*next)) {
+                    word.push(next);
+                }
+                tokens.push(word);
+            }
+            c => tokens.push(c.to_string()),
+        }
+    }
+
+    let is_hundred = |token: &str| token.parse::<f64>().is_ok_and(|value| value == 100.0);
+    tokens.windows(2).any(|pair| {
+        (pair[0] == "*" && is_hundred(&pair[1])) || (pair[1] == "*" && is_hundred(&pair[0]))
+    })
+}
+
+/// 从标签选择器中取出指定标签的值，例如 `mountpoint="/home"`；允许等号两侧有空白，
+/// 值按原文读取，`=~` 和 `!=` 匹配不视为取值
+fn label_value(query: &str, label: &str) -> Option<String> {
+    let mut search = 0;
+    while let Some(offset) = query[search..].find(label) {
+        let start = search + offset;
+        search = start + label.len();
+        let before = query[..start].chars().next_back();
+        if before.is_some_and(|c| c.is_ascii_alphanumeric() || c == '_') {
+            continue;
+        }
+        let rest = query[search..].trim_start();
+        let Some(rest) = rest.strip_prefix('=') else {
+            continue;
+        };
+        if rest.starts_with('~') {
+            continue;
+        }
+        let Some(rest) = rest.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        let mut value = String::new();
+        let mut chars = rest.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => value.extend(chars.next()),
+                '"' => return Some(value),
+                c => value.push(c),
+            }
+        }
+        return None;
+    }
+    None
+}
+
+/// 交换比较两侧时的运算符
+fn flip(comparison: AlertComparison) -> AlertComparison {
+    match comparison {
+        AlertComparison::GreaterThan => AlertComparison::LessThan,
+        AlertComparison::GreaterOrEqual => AlertComparison::LessOrEqual,
+        AlertComparison::LessThan => AlertComparison::GreaterThan,
+        AlertComparison::LessOrEqual => AlertComparison::GreaterOrEqual,
+        other => other,
+    }
+}
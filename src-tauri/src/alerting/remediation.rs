@@ -0,0 +1,438 @@
+//! 警报自动修复
+//! 规则触发时依次执行配置的动作：终止匹配的进程、运行脚本（警报上下文通过环境变量传入）或调用 Webhook；
+//! 每条规则有执行频率限制，试运行模式下只记录将要执行的操作
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Signal, System};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+use super::template::AlertMessageContext;
+use crate::database::AlertHistory;
+
+// 记录的输出最多保留的字节数
+const MAX_OUTPUT_BYTES: usize = 4096;
+const TRUNCATED_MARKER: &str = "… (truncated)";
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+fn default_max_runs_per_hour() -> u32 {
+    3
+}
+
+fn default_cooldown_secs() -> u64 {
+    300
+}
+
+// 修复动作
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemediationAction {
+    /// 终止名称匹配（不区分大小写）的所有进程
+    TerminateProcess {
+        process_name: String,
+        /// 为 true 时直接强制终止，否则发送 SIGTERM
+        #[serde(default)]
+        force: bool,
+    },
+    /// 运行脚本，不经过 shell；警报上下文通过 `ALERT_*` 环境变量传入
+    RunScript {
+        path: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+    /// 以 POST 方式发送警报记录和上下文的 JSON
+    Webhook {
+        url: String,
+        #[serde(default = "default_timeout_secs")]
+        timeout_secs: u64,
+    },
+}
+
+impl RemediationAction {
+    /// 动作类型的存储名称
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::TerminateProcess { .. } => "terminate_process",
+            Self::RunScript { .. } => "run_script",
+            Self::Webhook { .. } => "webhook",
+        }
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        match self {
+            Self::TerminateProcess { process_name, .. } => {
+                if process_name.trim().is_empty() {
+                    return Err("Process name must not be empty".to_string());
+                }
+            }
+            Self::RunScript { path, timeout_secs, .. } => {
+                if !Path::new(path).is_absolute() {
+                    return Err(format!("Script path must be absolute, got `{}`", path));
+                }
+                if *timeout_secs == 0 {
+                    return Err("Script timeout must be at least one second".to_string());
+                }
+            }
+            Self::Webhook { url, timeout_secs } => {
+                if !(url.starts_with("http://") || url.starts_with("https://")) {
+                    return Err(format!("Webhook URL must start with http:// or https://, got `{}`", url));
+                }
+                if *timeout_secs == 0 {
+                    return Err("Webhook timeout must be at least one second".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// 规则的修复策略
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RemediationPolicy {
+    pub actions: Vec<RemediationAction>,
+    /// 只记录将要执行的操作，不实际执行
+    #[serde(default)]
+    pub dry_run: bool,
+    /// 一小时内最多执行的次数，每次触发执行全部动作算一次
+    #[serde(default = "default_max_runs_per_hour")]
+    pub max_runs_per_hour: u32,
+    /// 两次执行之间至少间隔的秒数
+    #[serde(default = "default_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl RemediationPolicy {
+    /// 校验修复策略，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.actions.is_empty() {
+            return Err("Remediation needs at least one action".to_string());
+        }
+        if self.max_runs_per_hour == 0 {
+            return Err("Remediation must be allowed to run at least once per hour".to_string());
+        }
+        for (index, action) in self.actions.iter().enumerate() {
+            action
+                .validate()
+                .map_err(|e| format!("Remediation action {}: {}", index + 1, e))?;
+        }
+        Ok(())
+    }
+
+    /// 根据最近一小时内的执行时间判断是否允许再次执行，不允许时返回原因
+    pub fn check_rate_limit(&self, recent_runs: &[DateTime<Utc>], now: DateTime<Utc>) -> Result<(), String> {
+        let window_start = now - chrono::Duration::hours(1);
+        let runs: Vec<&DateTime<Utc>> = recent_runs.iter().filter(|run| **run > window_start).collect();
+
+        if runs.len() >= self.max_runs_per_hour as usize {
+            return Err(format!(
+                "rate limited: already ran {} times in the last hour (limit {})",
+                runs.len(),
+                self.max_runs_per_hour
+            ));
+        }
+        if let Some(last) = runs.into_iter().max() {
+            let elapsed = (now - *last).num_seconds();
+            if elapsed < self.cooldown_secs as i64 {
+                return Err(format!(
+                    "rate limited: last run was {}s ago, cooldown is {}s",
+                    elapsed.max(0),
+                    self.cooldown_secs
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 修复动作的执行状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RemediationStatus {
+    Running,
+    Succeeded,
+    Failed,
+    /// 无需执行，例如没有匹配的进程
+    Skipped,
+    DryRun,
+    RateLimited,
+}
+
+impl RemediationStatus {
+    /// 状态的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Running => "running",
+            Self::Succeeded => "succeeded",
+            Self::Failed => "failed",
+            Self::Skipped => "skipped",
+            Self::DryRun => "dry_run",
+            Self::RateLimited => "rate_limited",
+        }
+    }
+
+    /// 按存储名称解析状态
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "running" => Some(Self::Running),
+            "succeeded" => Some(Self::Succeeded),
+            "failed" => Some(Self::Failed),
+            "skipped" => Some(Self::Skipped),
+            "dry_run" => Some(Self::DryRun),
+            "rate_limited" => Some(Self::RateLimited),
+            _ => None,
+        }
+    }
+}
+
+// 修复动作的执行记录
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RemediationResult {
+    pub id: String,
+    pub alert_history_id: String,
+    pub rule_id: String,
+    /// 动作在策略中的序号（0 起始），被频率限制时为空
+    pub action_index: Option<u32>,
+    pub action: Option<String>,
+    pub status: RemediationStatus,
+    pub output: String,
+    pub executed_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// 脚本可用的环境变量
+pub fn script_environment(alert: &AlertHistory, context: &AlertMessageContext) -> Vec<(String, String)> {
+    let mut variables = vec![
+        ("ALERT_ID", alert.id.clone()),
+        ("ALERT_RULE_ID", context.rule_id.clone()),
+        ("ALERT_SEVERITY", context.severity.as_str().to_string()),
+        ("ALERT_METRIC", context.metric.as_str().to_string()),
+        ("ALERT_VALUE", context.value.to_string()),
+        ("ALERT_HOSTNAME", context.hostname.clone()),
+        ("ALERT_MESSAGE", alert.message.clone()),
+        ("ALERT_DETAILS", context.details.clone()),
+        ("ALERT_TRIGGERED_AT", alert.triggered_at.to_rfc3339()),
+        ("ALERT_DURATION_SECS", context.duration_secs.to_string()),
+    ];
+    if let Some(threshold) = context.threshold {
+        variables.push(("ALERT_THRESHOLD", threshold.to_string()));
+    }
+    if let Some(target) = &context.target {
+        variables.push(("ALERT_TARGET", target.clone()));
+    }
+    variables
+        .into_iter()
+        .map(|(name, value)| (name.to_string(), value))
+        .collect()
+}
+
+/// 执行单个修复动作，返回状态和输出
+pub async fn execute_action(
+    action: &RemediationAction,
+    alert: &AlertHistory,
+    context: &AlertMessageContext,
+    dry_run: bool,
+) -> (RemediationStatus, String) {
+    match action {
+        RemediationAction::TerminateProcess { process_name, force } => {
+            let process_name = process_name.clone();
+            let force = *force;
+            tokio::task::spawn_blocking(move || terminate_processes(&process_name, force, dry_run))
+                .await
+                .unwrap_or_else(|e| (RemediationStatus::Failed, format!("Failed to terminate processes: {}", e)))
+        }
+        RemediationAction::RunScript { path, args, timeout_secs } => {
+            if dry_run {
+                let command = std::iter::once(path.as_str())
+                    .chain(args.iter().map(String::as_str))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                return (
+                    RemediationStatus::DryRun,
+                    format!("would run {} with ALERT_* environment variables", command),
+                );
+            }
+            run_script(path, args, *timeout_secs, &script_environment(alert, context)).await
+        }
+        RemediationAction::Webhook { url, timeout_secs } => {
+            if dry_run {
+                return (RemediationStatus::DryRun, format!("would POST the alert to {}", url));
+            }
+            call_webhook(url, *timeout_secs, alert, context).await
+        }
+    }
+}
+
+fn terminate_processes(process_name: &str, force: bool, dry_run: bool) -> (RemediationStatus, String) {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let own_pid = std::process::id();
+
+    // 不终止自身和 init 进程
+    let matching: Vec<_> = sys
+        .processes()
+        .iter()
+        .filter(|(pid, process)| {
+            pid.as_u32() > 1 && pid.as_u32() != own_pid && process.name().eq_ignore_ascii_case(process_name)
+        })
+        .collect();
+    if matching.is_empty() {
+        return (RemediationStatus::Skipped, format!("no process named {} is running", process_name));
+    }
+
+    let signal = if force { "SIGKILL" } else { "SIGTERM" };
+    if dry_run {
+        let pids: Vec<String> = matching.iter().map(|(pid, _)| pid.to_string()).collect();
+        return (
+            RemediationStatus::DryRun,
+            format!("would send {} to {} (pid {})", signal, process_name, pids.join(", ")),
+        );
+    }
+
+    let mut terminated = Vec::new();
+    let mut failed = Vec::new();
+    for (pid, process) in matching {
+        // 不支持 SIGTERM 的平台（Windows）退回强制终止
+        let sent = if force {
+            process.kill()
+        } else {
+            process.kill_with(Signal::Term).unwrap_or_else(|| process.kill())
+        };
+        if sent {
+            terminated.push(pid.to_string());
+        } else {
+            failed.push(pid.to_string());
+        }
+    }
+
+    let mut output = format!("sent {} to {} (pid {})", signal, process_name, terminated.join(", "));
+    if !failed.is_empty() {
+        output.push_str(&format!("; failed for pid {}", failed.join(", ")));
+    }
+    let status = if terminated.is_empty() {
+        RemediationStatus::Failed
+    } else {
+        RemediationStatus::Succeeded
+    };
+    (status, output)
+}
+
+async fn run_script(
+    path: &str,
+    args: &[String],
+    timeout_secs: u64,
+    environment: &[(String, String)],
+) -> (RemediationStatus, String) {
+    let child = tokio::process::Command::new(path)
+        .args(args)
+        .envs(environment.iter().map(|(name, value)| (name, value)))
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => return (RemediationStatus::Failed, format!("Failed to start {}: {}", path, e)),
+    };
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let run = async {
+        tokio::try_join!(read_capped(stdout), read_capped(stderr), child.wait())
+    };
+    // 超时后丢弃 run 时 child 随之释放，kill_on_drop 会终止脚本
+    match tokio::time::timeout(Duration::from_secs(timeout_secs), run).await {
+        Ok(Ok(((stdout, stdout_truncated), (stderr, stderr_truncated), exit_status))) => {
+            let mut text = String::from_utf8_lossy(&stdout).to_string();
+            let stderr = String::from_utf8_lossy(&stderr);
+            if !stderr.trim().is_empty() {
+                text.push_str("\nstderr: ");
+                text.push_str(&stderr);
+            }
+            let status = if exit_status.success() {
+                RemediationStatus::Succeeded
+            } else {
+                RemediationStatus::Failed
+            };
+            let mut output = truncate_output(format!("{}\n{}", exit_status, text.trim()));
+            if (stdout_truncated || stderr_truncated) && !output.ends_with(TRUNCATED_MARKER) {
+                output.push_str(TRUNCATED_MARKER);
+            }
+            (status, output)
+        }
+        Ok(Err(e)) => (RemediationStatus::Failed, format!("Failed to wait for {}: {}", path, e)),
+        Err(_) => (RemediationStatus::Failed, format!("{} timed out after {}s and was killed", path, timeout_secs)),
+    }
+}
+
+/// 最多保留 MAX_OUTPUT_BYTES 字节的输出，其余读出后丢弃，避免脚本因管道写满而阻塞；
+/// 返回保留的内容以及是否有内容被丢弃
+async fn read_capped<R: AsyncRead + Unpin>(reader: Option<R>) -> std::io::Result<(Vec<u8>, bool)> {
+    let Some(mut reader) = reader else {
+        return Ok((Vec::new(), false));
+    };
+    let mut captured = Vec::new();
+    (&mut reader).take(MAX_OUTPUT_BYTES as u64).read_to_end(&mut captured).await?;
+    let discarded = tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+    Ok((captured, discarded > 0))
+}
+
+async fn call_webhook(
+    url: &str,
+    timeout_secs: u64,
+    alert: &AlertHistory,
+    context: &AlertMessageContext,
+) -> (RemediationStatus, String) {
+    let client = match reqwest::Client::builder().timeout(Duration::from_secs(timeout_secs)).build() {
+        Ok(client) => client,
+        Err(e) => return (RemediationStatus::Failed, format!("Failed to create HTTP client: {}", e)),
+    };
+    let body = serde_json::json!({ "alert": alert, "context": context });
+
+    match client.post(url).json(&body).send().await {
+        Ok(response) => {
+            let status = response.status();
+            let text = read_response_capped(response).await;
+            let result = if status.is_success() {
+                RemediationStatus::Succeeded
+            } else {
+                RemediationStatus::Failed
+            };
+            (result, truncate_output(format!("HTTP {}\n{}", status, text.trim())))
+        }
+        Err(e) => (RemediationStatus::Failed, format!("Webhook request failed: {}", e)),
+    }
+}
+
+// 响应体最多读取 MAX_OUTPUT_BYTES 字节
+async fn read_response_capped(mut response: reqwest::Response) -> String {
+    let mut body = Vec::new();
+    while body.len() <= MAX_OUTPUT_BYTES {
+        match response.chunk().await {
+            Ok(Some(chunk)) => body.extend_from_slice(&chunk),
+            _ => break,
+        }
+    }
+    String::from_utf8_lossy(&body).to_string()
+}
+
+fn truncate_output(mut output: String) -> String {
+    if output.len() > MAX_OUTPUT_BYTES {
+        let mut end = MAX_OUTPUT_BYTES;
+        while !output.is_char_boundary(end) {
+            end -= 1;
+        }
+        output.truncate(end);
+        output.push_str(TRUNCATED_MARKER);
+    }
+    output
+}
@@ -0,0 +1,166 @@
+//! 警报静默与维护窗口
+//! 静默按规则 ID、指标、严重程度或目标标签匹配规则，可以是固定时间段或按本地时间循环的窗口
+
+use chrono::{DateTime, Datelike, Duration, Local, NaiveTime, TimeZone, Utc, Weekday};
+use serde::{Deserialize, Serialize};
+
+use super::{AlertMetric, AlertSeverity};
+use crate::database::AlertConfiguration;
+
+// 静默匹配条件
+// 各字段之间为“与”关系，同一字段内为“或”关系；全部为空时匹配所有规则，需设置 all 确认
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SilenceMatcher {
+    #[serde(default)]
+    pub rule_ids: Vec<String>,
+    #[serde(default)]
+    pub metrics: Vec<AlertMetric>,
+    #[serde(default)]
+    pub severities: Vec<AlertSeverity>,
+    #[serde(default)]
+    pub targets: Vec<String>,
+    /// 确认静默所有规则，其余条件全部为空时必须设置
+    #[serde(default)]
+    pub all: bool,
+}
+
+impl SilenceMatcher {
+    /// 是否没有任何匹配条件
+    pub fn is_empty(&self) -> bool {
+        self.rule_ids.is_empty() && self.metrics.is_empty() && self.severities.is_empty() && self.targets.is_empty()
+    }
+
+    pub fn matches(&self, config: &AlertConfiguration) -> bool {
+        (self.rule_ids.is_empty() || self.rule_ids.iter().any(|id| id == &config.id))
+            && (self.metrics.is_empty() || self.metrics.contains(&config.metric))
+            && (self.severities.is_empty() || self.severities.contains(&config.severity))
+            && (self.targets.is_empty()
+                || config
+                    .target
+                    .as_ref()
+                    .is_some_and(|target| self.targets.iter().any(|t| t == target)))
+    }
+}
+
+// 静默时间安排
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SilenceSchedule {
+    /// 固定时间段
+    Fixed {
+        starts_at: DateTime<Utc>,
+        ends_at: DateTime<Utc>,
+    },
+    /// 每天（或指定星期几）本地时间的循环窗口，例如 02:00–03:00；
+    /// 结束时间早于开始时间时窗口跨越午夜
+    Recurring {
+        start_time: String,
+        end_time: String,
+        #[serde(default)]
+        weekdays: Vec<Weekday>,
+    },
+}
+
+impl SilenceSchedule {
+    /// 校验时间安排
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SilenceSchedule::Fixed { starts_at, ends_at } => {
+                if ends_at <= starts_at {
+                    return Err("Silence must end after it starts".to_string());
+                }
+            }
+            SilenceSchedule::Recurring {
+                start_time,
+                end_time,
+                ..
+            } => {
+                let start = parse_time_of_day(start_time)?;
+                let end = parse_time_of_day(end_time)?;
+                if start == end {
+                    return Err("Recurring silence start and end times must differ".to_string());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 判断给定时刻是否处于静默窗口内，循环窗口按本地时区计算
+    pub fn is_active_at(&self, now: DateTime<Utc>) -> bool {
+        self.is_active_in(now, &Local)
+    }
+
+    /// 按指定时区判断是否处于静默窗口内
+    pub fn is_active_in<Tz: TimeZone>(&self, now: DateTime<Utc>, tz: &Tz) -> bool {
+        match self {
+            SilenceSchedule::Fixed { starts_at, ends_at } => *starts_at <= now && now < *ends_at,
+            SilenceSchedule::Recurring {
+                start_time,
+                end_time,
+                weekdays,
+            } => {
+                let (Ok(start), Ok(end)) = (parse_time_of_day(start_time), parse_time_of_day(end_time)) else {
+                    return false;
+                };
+                let local = now.with_timezone(tz);
+                let time = local.time();
+                let day_allowed = |day: Weekday| weekdays.is_empty() || weekdays.contains(&day);
+
+                if start < end {
+                    start <= time && time < end && day_allowed(local.weekday())
+                } else if time >= start {
+                    day_allowed(local.weekday())
+                } else if time < end {
+                    // 跨午夜窗口的后半段属于前一天开始的窗口
+                    day_allowed((local.clone() - Duration::days(1)).weekday())
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// 固定时间段是否已经结束
+    pub fn is_expired_at(&self, now: DateTime<Utc>) -> bool {
+        matches!(self, SilenceSchedule::Fixed { ends_at, .. } if *ends_at <= now)
+    }
+}
+
+/// 解析 `HH:MM` 或 `HH:MM:SS` 格式的时刻
+fn parse_time_of_day(value: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(value.trim(), "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(value.trim(), "%H:%M:%S"))
+        .map_err(|_| format!("Invalid time of day `{}` (expected HH:MM)", value))
+}
+
+// 警报静默
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertSilence {
+    pub id: String,
+    pub comment: String,
+    pub matcher: SilenceMatcher,
+    pub schedule: SilenceSchedule,
+    pub enabled: bool,
+    pub created_by: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl AlertSilence {
+    /// 校验静默配置，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.matcher.rule_ids.iter().any(|id| id.trim().is_empty())
+            || self.matcher.targets.iter().any(|target| target.trim().is_empty())
+        {
+            return Err("Silence matchers must not contain empty entries".to_string());
+        }
+        if self.matcher.is_empty() && !self.matcher.all {
+            return Err("Silence matcher has no criteria; set all to silence every rule".to_string());
+        }
+        self.schedule.validate()
+    }
+
+    /// 静默当前是否对指定规则生效
+    pub fn silences(&self, config: &AlertConfiguration, now: DateTime<Utc>) -> bool {
+        self.enabled && self.schedule.is_active_at(now) && self.matcher.matches(config)
+    }
+}
@@ -0,0 +1,352 @@
+//! 警报规则模拟
+//! 回测用历史数据按时间顺序重放规则求值，得到规则会在何时触发、何时恢复；
+//! 测试触发生成一条不写入历史的警报，由前端按规则的通知方式发送
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Duration, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::expression::{AlertExpression, EvaluationContext};
+use super::forecast::ResourceForecast;
+use super::template::{self, AlertMessageContext, MessageEnvironment, MessageLocale};
+use super::{AlertMetric, MetricSnapshot, RuleEvaluation};
+use crate::database::{AlertConfiguration, AlertHistory, HistoricalSystemData};
+
+// 预测规则的求值间隔，两次求值之间沿用上一次的结果
+const FORECAST_STEP_MINUTES: i64 = 5;
+
+// 回测中的一次触发过程
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertFiringEpisode {
+    pub fired_at: DateTime<Utc>,
+    /// 在回测范围内未恢复时为空
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// 持续时间，未恢复时计算到回测范围结束
+    pub duration_secs: i64,
+    pub firing_samples: u32,
+    pub min_value: f64,
+    pub max_value: f64,
+    /// 首次触发时的描述
+    pub details: String,
+}
+
+// 回测结果
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertBacktestReport {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub evaluated_samples: u32,
+    pub firing_samples: u32,
+    /// 数据不足等原因无法求值的采样点，这些点保持之前的状态
+    pub unknown_samples: u32,
+    pub total_firing_secs: i64,
+    pub episodes: Vec<AlertFiringEpisode>,
+}
+
+impl AlertBacktestReport {
+    /// 将按时间排列的求值结果合并为触发过程
+    pub fn from_evaluations(
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+        evaluations: Vec<(DateTime<Utc>, RuleEvaluation)>,
+    ) -> Self {
+        let mut report = Self {
+            start_time,
+            end_time,
+            evaluated_samples: 0,
+            firing_samples: 0,
+            unknown_samples: 0,
+            total_firing_secs: 0,
+            episodes: Vec::new(),
+        };
+        let mut open: Option<AlertFiringEpisode> = None;
+
+        for (timestamp, evaluation) in evaluations {
+            report.evaluated_samples += 1;
+            match evaluation {
+                RuleEvaluation::Firing { value, details } => {
+                    report.firing_samples += 1;
+                    let episode = open.get_or_insert(AlertFiringEpisode {
+                        fired_at: timestamp,
+                        resolved_at: None,
+                        duration_secs: 0,
+                        firing_samples: 0,
+                        min_value: value,
+                        max_value: value,
+                        details,
+                    });
+                    episode.firing_samples += 1;
+                    episode.min_value = episode.min_value.min(value);
+                    episode.max_value = episode.max_value.max(value);
+                }
+                RuleEvaluation::Clear => {
+                    if let Some(mut episode) = open.take() {
+                        episode.resolved_at = Some(timestamp);
+                        episode.duration_secs = (timestamp - episode.fired_at).num_seconds();
+                        report.episodes.push(episode);
+                    }
+                }
+                RuleEvaluation::Unknown => report.unknown_samples += 1,
+            }
+        }
+        if let Some(mut episode) = open {
+            episode.duration_secs = (end_time - episode.fired_at).num_seconds().max(0);
+            report.episodes.push(episode);
+        }
+
+        report.total_firing_secs = report.episodes.iter().map(|episode| episode.duration_secs).sum();
+        report
+    }
+}
+
+/// 回测需要在范围开始前额外加载的历史时长，使范围开头的采样点也能求值
+pub fn backtest_warmup(config: &AlertConfiguration) -> Duration {
+    if let Some(source) = &config.expression {
+        let window_secs = AlertExpression::parse(source)
+            .map(|expression| expression.history_window_secs())
+            .unwrap_or_default();
+        return Duration::seconds(window_secs.ceil() as i64);
+    }
+    if let Some(condition) = &config.forecast {
+        return Duration::hours(condition.lookback_hours as i64);
+    }
+    if let Some(detection) = &config.anomaly {
+        return Duration::hours(detection.lookback_hours() as i64);
+    }
+    Duration::zero()
+}
+
+/// 用历史数据回测规则，`history` 需按时间升序排列并包含预热数据；`cores` 为表达式中 `cores` 的取值
+pub fn backtest_rule(
+    config: &AlertConfiguration,
+    history: &[HistoricalSystemData],
+    start_time: DateTime<Utc>,
+    end_time: DateTime<Utc>,
+    cores: f64,
+    locale: MessageLocale,
+) -> Result<AlertBacktestReport, String> {
+    if end_time <= start_time {
+        return Err("Backtest end time must be after its start time".to_string());
+    }
+    let in_range: Vec<usize> = (0..history.len())
+        .filter(|index| (start_time..=end_time).contains(&history[*index].timestamp))
+        .collect();
+
+    let evaluations = if let Some(source) = &config.expression {
+        backtest_expression(source, history, &in_range, cores, locale)?
+    } else if let Some(condition) = &config.forecast {
+        let mut series: BTreeMap<Option<String>, Vec<(DateTime<Utc>, f64)>> = BTreeMap::new();
+        for record in history {
+            if config.metric == AlertMetric::Disk {
+                for disk in &record.disk_usage {
+                    series
+                        .entry(Some(disk.mount_point.clone()))
+                        .or_default()
+                        .push((record.timestamp, disk.usage_percent as f64));
+                }
+            } else if let Some(value) = config.metric.historical_value(record) {
+                series.entry(None).or_default().push((record.timestamp, value));
+            }
+        }
+        if let Some(target) = &config.target {
+            series.retain(|mount_point, _| mount_point.as_deref() == Some(target.as_str()));
+        }
+
+        let mut evaluations = Vec::new();
+        let mut last: Option<(DateTime<Utc>, RuleEvaluation)> = None;
+        for index in &in_range {
+            let now = history[*index].timestamp;
+            let evaluation = match &last {
+                Some((evaluated_at, evaluation)) if now - *evaluated_at < Duration::minutes(FORECAST_STEP_MINUTES) => {
+                    evaluation.clone()
+                }
+                _ => {
+                    let window_start = now - Duration::hours(condition.lookback_hours as i64);
+                    let forecasts: Vec<ResourceForecast> = series
+                        .iter()
+                        .filter_map(|(target, samples)| {
+                            let from = samples.partition_point(|(timestamp, _)| *timestamp < window_start);
+                            let to = samples.partition_point(|(timestamp, _)| *timestamp <= now);
+                            ResourceForecast::compute(
+                                config.metric,
+                                target.clone(),
+                                &samples[from..to],
+                                condition.threshold,
+                                condition.lookback_hours,
+                                now,
+                            )
+                        })
+                        .collect();
+                    let evaluation = if forecasts.is_empty() {
+                        RuleEvaluation::Unknown
+                    } else {
+                        match ResourceForecast::soonest_firing(forecasts, condition) {
+                            Some(forecast) => RuleEvaluation::Firing {
+                                value: forecast.hours_to_threshold.unwrap_or_default(),
+                                details: template::describe_forecast(&forecast, condition, locale),
+                            },
+                            None => RuleEvaluation::Clear,
+                        }
+                    };
+                    last = Some((now, evaluation.clone()));
+                    evaluation
+                }
+            };
+            evaluations.push((now, evaluation));
+        }
+        evaluations
+    } else if let Some(detection) = &config.anomaly {
+        let series: Vec<(DateTime<Utc>, f64)> = history
+            .iter()
+            .filter_map(|record| config.metric.historical_value(record).map(|value| (record.timestamp, value)))
+            .collect();
+        detection
+            .verdicts_in(&series, &Local)
+            .into_iter()
+            .filter(|(timestamp, _)| (start_time..=end_time).contains(timestamp))
+            .map(|(timestamp, verdict)| {
+                let evaluation = match verdict {
+                    Some(verdict) if verdict.is_anomaly => RuleEvaluation::Firing {
+                        value: verdict.value,
+                        details: template::describe_anomaly(config, detection, &verdict, locale),
+                    },
+                    Some(_) => RuleEvaluation::Clear,
+                    None => RuleEvaluation::Unknown,
+                };
+                (timestamp, evaluation)
+            })
+            .collect()
+    } else {
+        if !config.metric.has_history() {
+            return Err(format!("{} has no stored history to backtest against", config.metric.as_str()));
+        }
+        in_range
+            .iter()
+            .map(|index| {
+                let record = &history[*index];
+                let evaluation = match config.metric.historical_value(record) {
+                    Some(value) if config.condition.apply(value, config.threshold) => RuleEvaluation::Firing {
+                        value,
+                        details: template::describe_threshold(config, value, locale),
+                    },
+                    Some(_) => RuleEvaluation::Clear,
+                    None => RuleEvaluation::Unknown,
+                };
+                (record.timestamp, evaluation)
+            })
+            .collect()
+    };
+
+    Ok(AlertBacktestReport::from_evaluations(start_time, end_time, evaluations))
+}
+
+fn backtest_expression(
+    source: &str,
+    history: &[HistoricalSystemData],
+    in_range: &[usize],
+    cores: f64,
+    locale: MessageLocale,
+) -> Result<Vec<(DateTime<Utc>, RuleEvaluation)>, String> {
+    let expression = AlertExpression::parse(source).map_err(|e| format!("Invalid alert expression: {}", e))?;
+    let metrics = expression.metrics();
+    if let Some(metric) = metrics.iter().find(|metric| **metric != AlertMetric::Cores && !metric.has_history()) {
+        return Err(format!(
+            "The expression uses {}, which has no stored history to backtest against",
+            metric.as_str()
+        ));
+    }
+    let window = Duration::milliseconds((expression.history_window_secs() * 1000.0) as i64);
+
+    let evaluations = in_range
+        .iter()
+        .map(|index| {
+            let record = &history[*index];
+            let value = |metric: AlertMetric| metric.historical_value(record);
+            // 表达式用到的指标在这条记录中缺失时无法求值
+            if metrics
+                .iter()
+                .any(|metric| *metric != AlertMetric::Cores && value(*metric).is_none())
+            {
+                return (record.timestamp, RuleEvaluation::Unknown);
+            }
+            let snapshot = MetricSnapshot {
+                cpu: value(AlertMetric::Cpu).unwrap_or_default(),
+                memory: value(AlertMetric::Memory).unwrap_or_default(),
+                disk: value(AlertMetric::Disk).unwrap_or_default(),
+                network: value(AlertMetric::Network).unwrap_or_default(),
+                load: value(AlertMetric::Load).unwrap_or_default(),
+                swap: 0.0,
+                cores,
+                open_fds: value(AlertMetric::OpenFds),
+                open_fds_process: None,
+            };
+            // 只把窗口内的记录交给表达式，避免每个采样点都遍历全部历史
+            let from = history.partition_point(|other| other.timestamp < record.timestamp - window);
+            let ctx = EvaluationContext {
+                snapshot: &snapshot,
+                history: &history[from..=*index],
+                now: record.timestamp,
+            };
+            let evaluation = match expression.evaluate(&ctx) {
+                Some(outcome) if outcome.fired => RuleEvaluation::Firing {
+                    value: outcome.value,
+                    details: template::describe_expression(&expression.to_string(), outcome.value, locale),
+                },
+                Some(_) => RuleEvaluation::Clear,
+                None => RuleEvaluation::Unknown,
+            };
+            (record.timestamp, evaluation)
+        })
+        .collect();
+
+    Ok(evaluations)
+}
+
+// 测试触发的警报
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TestAlert {
+    /// 不写入警报历史，ID 以 `test-` 开头
+    pub alert: AlertHistory,
+    /// 前端应通过这些通知方式发送
+    pub notification_methods: Vec<String>,
+}
+
+/// 为规则生成一条测试警报，消息使用规则自己的模板
+pub fn test_fire(config: &AlertConfiguration, environment: &MessageEnvironment, now: DateTime<Utc>) -> TestAlert {
+    let value = if config.expression.is_none() && config.anomaly.is_none() && config.forecast.is_none() {
+        config.threshold
+    } else {
+        config.forecast.as_ref().map_or(0.0, |condition| condition.within_hours)
+    };
+    let details = template::describe_test_fire(config, environment.locale);
+    let context = AlertMessageContext::for_rule(config, value, details, environment, None, now);
+    let message = config
+        .message_template
+        .as_deref()
+        .and_then(|custom| template::render_template(custom, &context, environment.locale).ok())
+        .unwrap_or_else(|| {
+            template::render_template(environment.locale.default_template(), &context, environment.locale)
+                .unwrap_or_else(|_| context.details.clone())
+        });
+
+    TestAlert {
+        alert: AlertHistory {
+            id: format!("test-{}", uuid::Uuid::new_v4()),
+            alert_id: config.id.clone(),
+            triggered_at: now,
+            value,
+            message,
+            acknowledged: false,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            suppressed: false,
+            silence_id: None,
+            severity: Some(config.severity),
+            escalation_level: 0,
+            resolved_at: None,
+            incident_id: None,
+        },
+        notification_methods: config.notification_methods.clone(),
+    }
+}
@@ -0,0 +1,472 @@
+//! 警报消息模板
+//! 模板中的 `{占位符}` 在触发时替换为规则和主机的上下文，`{{`、`}}` 表示字面量花括号；
+//! 规则未设置模板时使用当前语言的默认模板
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::anomaly::{AnomalyDetection, AnomalyVerdict};
+use super::forecast::{ForecastCondition, ResourceForecast};
+use super::{AlertComparison, AlertMetric, AlertSeverity};
+use crate::database::AlertConfiguration;
+use crate::process::fds::FdUsage;
+
+// 模板可用的占位符
+pub const TEMPLATE_PLACEHOLDERS: &[&str] = &[
+    "rule_id",
+    "severity",
+    "metric",
+    "condition",
+    "value",
+    "threshold",
+    "target",
+    "hostname",
+    "duration",
+    "top_processes",
+    "details",
+    "time",
+];
+
+// 消息使用的语言
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageLocale {
+    #[default]
+    #[serde(alias = "en-US", alias = "en_US")]
+    En,
+    #[serde(alias = "zh-CN", alias = "zh_CN", alias = "zh-cn")]
+    Zh,
+}
+
+impl MessageLocale {
+    /// 该语言的默认模板
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            Self::En => "[{hostname}] {severity} alert: {details}",
+            Self::Zh => "[{hostname}] {severity}警报：{details}",
+        }
+    }
+
+    fn severity(&self, severity: AlertSeverity) -> &'static str {
+        match (self, severity) {
+            (Self::En, severity) => severity.as_str(),
+            (Self::Zh, AlertSeverity::Low) => "低级",
+            (Self::Zh, AlertSeverity::Medium) => "中级",
+            (Self::Zh, AlertSeverity::High) => "高级",
+            (Self::Zh, AlertSeverity::Critical) => "严重",
+        }
+    }
+}
+
+// 触发时占用资源最多的进程
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessSample {
+    pub pid: u32,
+    pub name: String,
+    pub cpu_usage: f32,
+    pub memory_bytes: u64,
+}
+
+// 与具体规则无关的消息上下文，每次检查警报时采集一次
+#[derive(Debug, Clone, Default)]
+pub struct MessageEnvironment {
+    pub hostname: String,
+    pub locale: MessageLocale,
+    /// 按 CPU 使用率从高到低排列；只有模板引用 `{top_processes}` 时才采集
+    pub top_processes: Vec<ProcessSample>,
+}
+
+// 渲染模板使用的数据
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertMessageContext {
+    pub rule_id: String,
+    pub severity: AlertSeverity,
+    pub metric: AlertMetric,
+    /// 仅固定阈值规则有比较方式
+    #[serde(default)]
+    pub condition: Option<AlertComparison>,
+    pub value: f64,
+    #[serde(default)]
+    pub threshold: Option<f64>,
+    #[serde(default)]
+    pub target: Option<String>,
+    #[serde(default)]
+    pub hostname: String,
+    /// 本次触发前规则已持续触发的秒数，首次触发为 0
+    #[serde(default)]
+    pub duration_secs: i64,
+    #[serde(default)]
+    pub top_processes: Vec<ProcessSample>,
+    /// 与规则类型相关的描述，例如阈值比较或预测结果
+    #[serde(default)]
+    pub details: String,
+    pub triggered_at: DateTime<Utc>,
+}
+
+impl AlertMessageContext {
+    /// 根据规则和求值结果构建上下文
+    pub fn for_rule(
+        config: &AlertConfiguration,
+        value: f64,
+        details: String,
+        environment: &MessageEnvironment,
+        firing_since: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) -> Self {
+        let plain_threshold = config.expression.is_none() && config.anomaly.is_none() && config.forecast.is_none();
+        let threshold = match &config.forecast {
+            Some(condition) => Some(condition.threshold),
+            None if plain_threshold => Some(config.threshold),
+            None => None,
+        };
+        Self {
+            rule_id: config.id.clone(),
+            severity: config.severity,
+            metric: config.metric,
+            condition: plain_threshold.then_some(config.condition),
+            value,
+            threshold,
+            target: config.target.clone(),
+            hostname: environment.hostname.clone(),
+            duration_secs: firing_since.map_or(0, |since| (now - since).num_seconds().max(0)),
+            top_processes: environment.top_processes.clone(),
+            details,
+            triggered_at: now,
+        }
+    }
+
+    /// 预览模板时使用的示例数据
+    pub fn sample(hostname: &str, locale: MessageLocale) -> Self {
+        let config = AlertConfiguration {
+            id: "sample-rule".to_string(),
+            metric: AlertMetric::Cpu,
+            condition: AlertComparison::GreaterThan,
+            threshold: 90.0,
+            severity: AlertSeverity::High,
+            enabled: true,
+            notification_methods: vec!["visual".to_string()],
+            expression: None,
+            target: None,
+            escalation_policy_id: None,
+            anomaly: None,
+            forecast: None,
+            message_template: None,
+            remediation: None,
+        };
+        let now = Utc::now();
+        let environment = MessageEnvironment {
+            hostname: hostname.to_string(),
+            locale,
+            top_processes: vec![
+                ProcessSample {
+                    pid: 4242,
+                    name: "chrome".to_string(),
+                    cpu_usage: 61.5,
+                    memory_bytes: 1_288_490_189,
+                },
+                ProcessSample {
+                    pid: 1337,
+                    name: "cargo".to_string(),
+                    cpu_usage: 24.0,
+                    memory_bytes: 536_870_912,
+                },
+            ],
+        };
+        let details = describe_threshold(&config, 95.3, locale);
+        Self::for_rule(&config, 95.3, details, &environment, Some(now - chrono::Duration::seconds(312)), now)
+    }
+}
+
+// 模板片段
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+fn parse(template: &str) -> Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+
+    while let Some(position) = rest.find(['{', '}']) {
+        if position > 0 {
+            segments.push(Segment::Text(&rest[..position]));
+        }
+        let tail = &rest[position..];
+        if let Some(after) = tail.strip_prefix("{{") {
+            segments.push(Segment::Text("{"));
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("}}") {
+            segments.push(Segment::Text("}"));
+            rest = after;
+        } else if tail.starts_with('}') {
+            return Err(format!("unmatched `}}` at position {}", template.len() - tail.len()));
+        } else {
+            let end = tail
+                .find('}')
+                .ok_or_else(|| format!("unclosed placeholder at position {}", template.len() - tail.len()))?;
+            let name = tail[1..end].trim();
+            if !TEMPLATE_PLACEHOLDERS.contains(&name) {
+                return Err(format!(
+                    "unknown placeholder `{{{}}}`, available: {}",
+                    name,
+                    TEMPLATE_PLACEHOLDERS.join(", ")
+                ));
+            }
+            segments.push(Segment::Placeholder(name));
+            rest = &tail[end + 1..];
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+
+    Ok(segments)
+}
+
+/// 校验模板语法和占位符名称
+pub fn validate_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Message template must not be empty".to_string());
+    }
+    parse(template).map(|_| ()).map_err(|e| format!("Invalid message template: {}", e))
+}
+
+/// 判断模板是否引用了指定占位符，无效模板视为未引用
+pub fn references_placeholder(template: &str, placeholder: &str) -> bool {
+    parse(template).is_ok_and(|segments| {
+        segments
+            .iter()
+            .any(|segment| matches!(segment, Segment::Placeholder(name) if *name == placeholder))
+    })
+}
+
+/// 按上下文渲染模板，缺失的值显示为 `-`
+pub fn render_template(template: &str, context: &AlertMessageContext, locale: MessageLocale) -> Result<String, String> {
+    let segments = parse(template).map_err(|e| format!("Invalid message template: {}", e))?;
+    let mut message = String::new();
+
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => message.push_str(text),
+            Segment::Placeholder(name) => {
+                let value = match name {
+                    "rule_id" => context.rule_id.clone(),
+                    "severity" => locale.severity(context.severity).to_string(),
+                    "metric" => context.metric.as_str().to_string(),
+                    "condition" => context.condition.map_or_else(missing, |condition| condition.to_string()),
+                    "value" => format_number(context.value),
+                    "threshold" => context.threshold.map_or_else(missing, format_number),
+                    "target" => context.target.clone().unwrap_or_else(missing),
+                    "hostname" => context.hostname.clone(),
+                    "duration" => format_duration(context.duration_secs),
+                    "top_processes" => format_processes(&context.top_processes, locale),
+                    "details" => context.details.clone(),
+                    "time" => context.triggered_at.to_rfc3339(),
+                    _ => unreachable!("placeholder names are checked while parsing"),
+                };
+                message.push_str(&value);
+            }
+        }
+    }
+
+    Ok(message)
+}
+
+fn missing() -> String {
+    "-".to_string()
+}
+
+/// 整数不带小数，其余保留两位小数
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 && value.abs() < 1e15 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+/// 以相邻的两个最大单位显示时长，例如 `5m 12s`、`2h 3m`；为零的低位单位不显示
+fn format_duration(secs: i64) -> String {
+    const UNITS: [(i64, &str); 4] = [(86_400, "d"), (3_600, "h"), (60, "m"), (1, "s")];
+    let secs = secs.max(0);
+    let index = UNITS.iter().position(|(size, _)| secs >= *size).unwrap_or(UNITS.len() - 1);
+    let (size, unit) = UNITS[index];
+    let mut formatted = format!("{}{}", secs / size, unit);
+    if let Some((smaller, smaller_unit)) = UNITS.get(index + 1) {
+        let remainder = secs % size / smaller;
+        if remainder > 0 {
+            formatted.push_str(&format!(" {}{}", remainder, smaller_unit));
+        }
+    }
+    formatted
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+fn format_processes(processes: &[ProcessSample], locale: MessageLocale) -> String {
+    if processes.is_empty() {
+        return missing();
+    }
+    let formatted: Vec<String> = processes
+        .iter()
+        .map(|process| match locale {
+            MessageLocale::En => format!(
+                "{} (pid {}, {:.1}% CPU, {})",
+                process.name,
+                process.pid,
+                process.cpu_usage,
+                format_bytes(process.memory_bytes)
+            ),
+            MessageLocale::Zh => format!(
+                "{}（pid {}，CPU {:.1}%，内存 {}）",
+                process.name,
+                process.pid,
+                process.cpu_usage,
+                format_bytes(process.memory_bytes)
+            ),
+        })
+        .collect();
+    let separator = match locale {
+        MessageLocale::En => ", ",
+        MessageLocale::Zh => "；",
+    };
+    formatted.join(separator)
+}
+
+/// 规则的对象，例如 `disk_usage (/home)`
+pub(crate) fn subject(config: &AlertConfiguration) -> String {
+    match &config.target {
+        Some(target) => format!("{} ({})", config.metric.as_str(), target),
+        None => config.metric.as_str().to_string(),
+    }
+}
+
+/// 固定阈值规则的描述
+pub fn describe_threshold(config: &AlertConfiguration, value: f64, locale: MessageLocale) -> String {
+    let comparison = format!("{} {} {}", subject(config), config.condition, format_number(config.threshold));
+    match locale {
+        MessageLocale::En => format!("{} (current: {})", comparison, format_number(value)),
+        MessageLocale::Zh => format!("{}（当前值：{}）", comparison, format_number(value)),
+    }
+}
+
+/// 在文件描述符规则的描述后注明持有最多文件描述符的进程
+pub fn describe_fd_process(details: String, process: &FdUsage, locale: MessageLocale) -> String {
+    match locale {
+        MessageLocale::En => format!("{}, held by {} (PID {})", details, process.process_name, process.pid),
+        MessageLocale::Zh => format!("{}，持有进程：{}（PID {}）", details, process.process_name, process.pid),
+    }
+}
+
+/// 表达式规则的描述
+pub fn describe_expression(expression: &str, value: f64, locale: MessageLocale) -> String {
+    match locale {
+        MessageLocale::En => format!("{} (current: {})", expression, format_number(value)),
+        MessageLocale::Zh => format!("{}（当前值：{}）", expression, format_number(value)),
+    }
+}
+
+/// 异常检测规则的描述
+pub fn describe_anomaly(
+    config: &AlertConfiguration,
+    detection: &AnomalyDetection,
+    verdict: &AnomalyVerdict,
+    locale: MessageLocale,
+) -> String {
+    match locale {
+        MessageLocale::En => format!(
+            "{} = {:.2} outside expected range {:.2} – {:.2} (baseline {:.2}, z-score {:.1}, sensitivity {}σ)",
+            subject(config),
+            verdict.value,
+            verdict.lower,
+            verdict.upper,
+            verdict.expected,
+            verdict.z_score,
+            detection.sensitivity
+        ),
+        MessageLocale::Zh => format!(
+            "{} = {:.2}，超出预期范围 {:.2} – {:.2}（基线 {:.2}，z 分数 {:.1}，灵敏度 {}σ）",
+            subject(config),
+            verdict.value,
+            verdict.lower,
+            verdict.upper,
+            verdict.expected,
+            verdict.z_score,
+            detection.sensitivity
+        ),
+    }
+}
+
+/// 耗尽预测规则的描述
+pub fn describe_forecast(forecast: &ResourceForecast, condition: &ForecastCondition, locale: MessageLocale) -> String {
+    let hours = forecast.hours_to_threshold.unwrap_or_default();
+    let earliest = forecast.earliest_hours.unwrap_or(hours);
+    match locale {
+        MessageLocale::En => format!(
+            "{} expected to reach {}% in {:.1}h (95% range {:.1}h – {}, current: {:.1}%, trend: {:+.2}%/h)",
+            forecast.subject(),
+            condition.threshold,
+            hours,
+            earliest,
+            forecast
+                .latest_hours
+                .map_or_else(|| "no upper bound".to_string(), |latest| format!("{:.1}h", latest)),
+            forecast.current,
+            forecast.trend.slope_per_hour
+        ),
+        MessageLocale::Zh => format!(
+            "{} 预计 {:.1} 小时后达到 {}%（95% 区间 {:.1} 小时 – {}，当前：{:.1}%，趋势：{:+.2}%/小时）",
+            forecast.subject(),
+            hours,
+            condition.threshold,
+            earliest,
+            forecast
+                .latest_hours
+                .map_or_else(|| "无上限".to_string(), |latest| format!("{:.1} 小时", latest)),
+            forecast.current,
+            forecast.trend.slope_per_hour
+        ),
+    }
+}
+
+/// 测试触发时的描述，说明规则的触发条件
+pub fn describe_test_fire(config: &AlertConfiguration, locale: MessageLocale) -> String {
+    let condition = if let Some(expression) = &config.expression {
+        expression.clone()
+    } else if let Some(condition) = &config.forecast {
+        match locale {
+            MessageLocale::En => format!(
+                "{} forecast to reach {}% within {}h",
+                subject(config),
+                condition.threshold,
+                condition.within_hours
+            ),
+            MessageLocale::Zh => format!(
+                "{} 预计 {} 小时内达到 {}%",
+                subject(config),
+                condition.within_hours,
+                condition.threshold
+            ),
+        }
+    } else if let Some(detection) = &config.anomaly {
+        match locale {
+            MessageLocale::En => format!("{} deviates from its baseline by more than {}σ", subject(config), detection.sensitivity),
+            MessageLocale::Zh => format!("{} 偏离基线超过 {}σ", subject(config), detection.sensitivity),
+        }
+    } else {
+        format!("{} {} {}", subject(config), config.condition, format_number(config.threshold))
+    };
+    match locale {
+        MessageLocale::En => format!("test notification, rule condition: {}", condition),
+        MessageLocale::Zh => format!("测试通知，规则条件：{}", condition),
+    }
+}
@@ -0,0 +1,123 @@
+//! 警报规则导入导出
+//! 规则文件为 JSON 或 YAML，导入时按规则 ID 检测冲突，可选择跳过、覆盖或重命名
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::prometheus::UnmappedPrometheusRule;
+use crate::database::AlertConfiguration;
+
+// 当前规则文件版本
+const RULE_FILE_VERSION: u32 = 1;
+
+// 规则文件格式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleFileFormat {
+    #[default]
+    Json,
+    #[serde(alias = "yml")]
+    Yaml,
+}
+
+// 规则文件
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertRuleFile {
+    pub version: u32,
+    #[serde(default)]
+    pub exported_at: Option<DateTime<Utc>>,
+    pub rules: Vec<AlertConfiguration>,
+}
+
+// 导入时规则 ID 已存在的处理方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportConflictMode {
+    /// 保留已有规则，跳过导入的规则
+    #[default]
+    Skip,
+    /// 用导入的规则覆盖已有规则
+    Overwrite,
+    /// 以新 ID 导入，与已有规则并存
+    Rename,
+}
+
+// 以新 ID 导入的规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RenamedAlertRule {
+    pub original_id: String,
+    pub new_id: String,
+}
+
+// 导入过程中针对单条规则的问题
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AlertImportIssue {
+    pub id: String,
+    pub message: String,
+}
+
+// 导入报告
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AlertImportReport {
+    pub imported: Vec<String>,
+    pub overwritten: Vec<String>,
+    pub renamed: Vec<RenamedAlertRule>,
+    pub skipped: Vec<String>,
+    /// 校验失败未导入的规则
+    pub failed: Vec<AlertImportIssue>,
+    /// 已导入但做了调整的规则，例如移除了不存在的升级策略
+    pub warnings: Vec<AlertImportIssue>,
+    /// 无法映射的 Prometheus 规则
+    pub unmapped: Vec<UnmappedPrometheusRule>,
+}
+
+/// 将规则导出为指定格式的规则文件
+pub fn export_rule_file(rules: Vec<AlertConfiguration>, format: RuleFileFormat) -> Result<String, String> {
+    let file = AlertRuleFile {
+        version: RULE_FILE_VERSION,
+        exported_at: Some(Utc::now()),
+        rules,
+    };
+    match format {
+        RuleFileFormat::Json => serde_json::to_string_pretty(&file).map_err(|e| e.to_string()),
+        RuleFileFormat::Yaml => serde_yaml::to_string(&file).map_err(|e| e.to_string()),
+    }
+}
+
+/// 解析规则文件，同时接受带版本信息的文件和直接的规则列表；未指定格式时按内容判断
+pub fn parse_rule_file(content: &str, format: Option<RuleFileFormat>) -> Result<Vec<AlertConfiguration>, String> {
+    let format = format.unwrap_or_else(|| {
+        if content.trim_start().starts_with(['{', '[']) {
+            RuleFileFormat::Json
+        } else {
+            RuleFileFormat::Yaml
+        }
+    });
+
+    let file: AlertRuleFile = match format {
+        RuleFileFormat::Json => {
+            let value: serde_json::Value =
+                serde_json::from_str(content).map_err(|e| format!("Invalid JSON rule file: {}", e))?;
+            if value.is_array() {
+                return serde_json::from_value(value).map_err(|e| format!("Invalid alert rule: {}", e));
+            }
+            serde_json::from_value(value).map_err(|e| format!("Invalid rule file: {}", e))?
+        }
+        RuleFileFormat::Yaml => {
+            let value: serde_yaml::Value =
+                serde_yaml::from_str(content).map_err(|e| format!("Invalid YAML rule file: {}", e))?;
+            if value.is_sequence() {
+                return serde_yaml::from_value(value).map_err(|e| format!("Invalid alert rule: {}", e));
+            }
+            serde_yaml::from_value(value).map_err(|e| format!("Invalid rule file: {}", e))?
+        }
+    };
+
+    if file.version > RULE_FILE_VERSION {
+        return Err(format!(
+            "Rule file version {} is newer than the supported version {}",
+            file.version, RULE_FILE_VERSION
+        ));
+    }
+    Ok(file.rules)
+}
@@ -1,20 +1,53 @@
-use sqlx::{SqlitePool, Row};
+use sqlx::{QueryBuilder, Row, Sqlite, SqliteConnection, SqlitePool};
 use serde::{Deserialize, Serialize};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDateTime, Utc};
 use uuid::Uuid;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 use tauri::{AppHandle, Manager};
+use crate::alerting::anomaly::AnomalyDetection;
+use crate::alerting::history::{AlertHistoryFilter, AlertStatistics};
+use crate::alerting::notification::{
+    self, NotificationDelivery, NotificationDigest, NotificationDisposition, NotificationLimiter, NotificationPlan,
+    NotificationPolicy, PendingNotification,
+};
+use crate::alerting::incident::{Incident, IncidentDetail, IncidentEvent, IncidentEventKind, IncidentGrouping, IncidentStatus};
+use crate::alerting::forecast::{self, ForecastCondition, ResourceForecast};
+use crate::alerting::escalation::{AlertEscalation, EscalationPolicy, EscalationStep};
+use crate::alerting::expression::{AlertExpression, EvaluationContext};
+use crate::alerting::transfer::{AlertImportIssue, AlertImportReport, ImportConflictMode, RenamedAlertRule};
+use crate::alerting::remediation::{self, RemediationAction, RemediationPolicy, RemediationResult, RemediationStatus};
+use crate::alerting::silence::{AlertSilence, SilenceMatcher, SilenceSchedule};
+use crate::alerting::template::{self, AlertMessageContext, MessageEnvironment, MessageLocale};
+use crate::process::audit::{ProcessAuditAction, ProcessAuditEntry};
+use crate::process::cgroupfs::CgroupSample;
+use crate::process::supervisor::{InvalidSupervisorRule, SupervisorEvent, SupervisorEventKind, SupervisorRule, SupervisorTarget};
+use crate::alerting::{self, AlertComparison, AlertMetric, AlertSeverity, MetricSnapshot, RuleEvaluation};
 
 // 警报配置结构体
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct AlertConfiguration {
     pub id: String,
-    pub metric: String,
-    pub condition: String,
+    pub metric: AlertMetric,
+    pub condition: AlertComparison,
     pub threshold: f64,
-    pub severity: String,
+    pub severity: AlertSeverity,
     pub enabled: bool,
     pub notification_methods: Vec<String>,
+    /// 组合条件表达式，设置后取代 metric/condition/threshold 的单一比较
+    pub expression: Option<String>,
+    /// 目标标签，例如挂载点、进程名或服务名
+    pub target: Option<String>,
+    /// 未确认时使用的升级策略
+    pub escalation_policy_id: Option<String>,
+    /// 异常检测规则，设置后按偏离基线的程度触发，取代固定阈值
+    pub anomaly: Option<AnomalyDetection>,
+    /// 耗尽预测条件，设置后在预计剩余时间少于指定小时数时触发
+    pub forecast: Option<ForecastCondition>,
+    /// 自定义消息模板，未设置时使用当前语言的默认模板
+    pub message_template: Option<String>,
+    /// 触发时自动执行的修复动作
+    pub remediation: Option<RemediationPolicy>,
 }
 
 // 警报历史结构体
@@ -28,6 +61,48 @@ pub struct AlertHistory {
     pub acknowledged: bool,
     pub acknowledged_at: Option<DateTime<Utc>>,
     pub acknowledged_by: Option<String>,
+    /// 触发时处于静默期，未发送通知
+    pub suppressed: bool,
+    pub silence_id: Option<String>,
+    /// 当前严重程度，升级后可能高于规则配置
+    pub severity: Option<AlertSeverity>,
+    pub escalation_level: u32,
+    /// 规则恢复正常的时间，仍在触发时为空
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// 所属事件，处于静默期的警报不归入事件
+    #[serde(default)]
+    pub incident_id: Option<String>,
+}
+
+// 已存储警报配置中的问题
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AlertConfigurationIssue {
+    pub id: String,
+    pub field: String,
+    pub value: String,
+    pub message: String,
+    pub action: AlertIssueAction,
+}
+
+// 对已存储警报配置中问题的处理
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertIssueAction {
+    /// 已改写为规范写法
+    Repaired,
+    /// 无法修复，规则已禁用
+    Disabled,
+    /// 仅报告，未做修改
+    Reported,
+}
+
+// 警报配置迁移报告
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AlertMigrationReport {
+    pub checked: u32,
+    pub repaired: u32,
+    pub disabled: u32,
+    pub issues: Vec<AlertConfigurationIssue>,
 }
 
 // 历史系统数据结构体
@@ -41,6 +116,9 @@ pub struct HistoricalSystemData {
     pub disk_usage: Vec<DiskUsageData>,
     pub network_traffic: NetworkTrafficData,
     pub system_load: f32,
+    /// 单个进程持有的最大文件描述符数量，旧记录及不支持的平台为空
+    #[serde(default)]
+    pub open_fds: Option<u32>,
 }
 
 // 磁盘使用数据结构体
@@ -62,8 +140,11 @@ pub struct NetworkTrafficData {
 }
 
 // 数据库管理器
+#[derive(Clone)]
 pub struct DatabaseManager {
     pool: SqlitePool,
+    /// 启动时检查警报配置的结果，供界面提示需要迁移的规则
+    startup_migration: std::sync::Arc<AlertMigrationReport>,
 }
 
 impl DatabaseManager {
@@ -84,14 +165,34 @@ impl DatabaseManager {
         // 创建连接池
         let pool = SqlitePool::connect(&db_url).await?;
         
-        let manager = Self { pool };
+        Self::with_pool(pool).await
+    }
+    
+    /// 使用已有的连接池创建数据库管理器，初始化表并检查警报配置
+    pub async fn with_pool(pool: SqlitePool) -> Result<Self, sqlx::Error> {
+        let mut manager = Self {
+            pool,
+            startup_migration: Default::default(),
+        };
         
         // 初始化数据库表
         manager.init_tables().await?;
         
+        // 启动时只检查旧版本写入的警报配置，修复和禁用由用户通过迁移命令确认后执行
+        let report = manager.migrate_alert_configurations(false).await?;
+        for issue in &report.issues {
+            eprintln!("Invalid alert configuration {}: {} {}", issue.id, issue.field, issue.message);
+        }
+        manager.startup_migration = std::sync::Arc::new(report);
+        
         Ok(manager)
     }
     
+    /// 启动时检查警报配置的结果，不包含修复和禁用
+    pub fn startup_migration_report(&self) -> &AlertMigrationReport {
+        &self.startup_migration
+    }
+    
     /// 初始化数据库表
     async fn init_tables(&self) -> Result<(), sqlx::Error> {
         // 创建历史系统数据表
@@ -110,6 +211,7 @@ impl DatabaseManager {
         )
         .execute(&self.pool)
         .await?;
+        self.add_column_if_missing("historical_system_data", "open_fds", "INTEGER").await?;
         
         // 创建磁盘使用数据表
         sqlx::query(
@@ -164,6 +266,14 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await?;
         
+        self.add_column_if_missing("alert_configurations", "expression", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "target", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "escalation_policy_id", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "anomaly", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "forecast", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "message_template", "TEXT").await?;
+        self.add_column_if_missing("alert_configurations", "remediation", "TEXT").await?;
+        
         // 创建警报历史表
         sqlx::query(
             r#"
@@ -183,6 +293,245 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await?;
         
+        self.add_column_if_missing("alert_history", "suppressed", "INTEGER NOT NULL DEFAULT 0").await?;
+        self.add_column_if_missing("alert_history", "silence_id", "TEXT").await?;
+        self.add_column_if_missing("alert_history", "severity", "TEXT").await?;
+        self.add_column_if_missing("alert_history", "escalation_level", "INTEGER NOT NULL DEFAULT 0").await?;
+        self.add_column_if_missing("alert_history", "resolved_at", "TEXT").await?;
+        self.add_column_if_missing("alert_history", "incident_id", "TEXT").await?;
+        
+        // 创建警报静默表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS alert_silences (
+                id TEXT PRIMARY KEY,
+                comment TEXT NOT NULL,
+                matcher TEXT NOT NULL,
+                schedule TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                created_by TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建升级策略表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS escalation_policies (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                steps TEXT NOT NULL,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建警报升级记录表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS alert_escalations (
+                id TEXT PRIMARY KEY,
+                alert_history_id TEXT NOT NULL,
+                rule_id TEXT NOT NULL,
+                policy_id TEXT NOT NULL,
+                step INTEGER NOT NULL,
+                total_steps INTEGER NOT NULL,
+                notification_methods TEXT NOT NULL,
+                severity TEXT,
+                escalated_at TEXT NOT NULL,
+                message TEXT NOT NULL,
+                FOREIGN KEY (alert_history_id) REFERENCES alert_history (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建修复动作执行记录表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS remediation_results (
+                id TEXT PRIMARY KEY,
+                alert_history_id TEXT NOT NULL,
+                rule_id TEXT NOT NULL,
+                action_index INTEGER,
+                action TEXT,
+                status TEXT NOT NULL,
+                output TEXT NOT NULL,
+                executed_at TEXT NOT NULL,
+                finished_at TEXT,
+                FOREIGN KEY (alert_history_id) REFERENCES alert_history (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建事件表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS incidents (
+                id TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                status TEXT NOT NULL,
+                severity TEXT NOT NULL,
+                opened_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                last_alert_at TEXT NOT NULL,
+                acknowledged_at TEXT,
+                acknowledged_by TEXT,
+                resolved_at TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建事件时间线表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS incident_events (
+                id TEXT PRIMARY KEY,
+                incident_id TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                message TEXT NOT NULL,
+                author TEXT,
+                alert_history_id TEXT,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (incident_id) REFERENCES incidents (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建通知记录表，记录每条警报在每个通知方式上的处理方式
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS notification_log (
+                id TEXT PRIMARY KEY,
+                alert_history_id TEXT NOT NULL,
+                rule_id TEXT NOT NULL,
+                channel TEXT NOT NULL,
+                disposition TEXT NOT NULL,
+                value REAL NOT NULL,
+                severity TEXT,
+                created_at TEXT NOT NULL,
+                digest_id TEXT
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建通知摘要表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS notification_digests (
+                id TEXT PRIMARY KEY,
+                channel TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                period_start TEXT NOT NULL,
+                period_end TEXT NOT NULL,
+                alert_count INTEGER NOT NULL,
+                overflow_count INTEGER NOT NULL,
+                rules TEXT NOT NULL,
+                alert_ids TEXT NOT NULL,
+                message TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建进程操作审计表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS process_audit_log (
+                id TEXT PRIMARY KEY,
+                pid INTEGER NOT NULL,
+                process_name TEXT,
+                action TEXT NOT NULL,
+                previous_value TEXT,
+                requested_value TEXT NOT NULL,
+                success BOOLEAN NOT NULL,
+                error TEXT,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建 cgroup 用量历史表，随历史系统数据一同清理
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS cgroup_stats_data (
+                id TEXT PRIMARY KEY,
+                historical_data_id TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                path TEXT NOT NULL,
+                memory_current INTEGER,
+                memory_max INTEGER,
+                cpu_usage_usec INTEGER,
+                cpu_nr_periods INTEGER,
+                cpu_nr_throttled INTEGER,
+                cpu_throttled_usec INTEGER,
+                io_read_bytes INTEGER NOT NULL,
+                io_written_bytes INTEGER NOT NULL,
+                pids_current INTEGER,
+                FOREIGN KEY (historical_data_id) REFERENCES historical_system_data (id) ON DELETE CASCADE
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建进程守护规则表
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS supervisor_rules (
+                id TEXT PRIMARY KEY,
+                name TEXT NOT NULL,
+                target TEXT NOT NULL,
+                enabled BOOLEAN NOT NULL DEFAULT 1,
+                initial_backoff_secs INTEGER NOT NULL,
+                max_backoff_secs INTEGER NOT NULL,
+                max_restarts INTEGER,
+                created_at TEXT DEFAULT CURRENT_TIMESTAMP,
+                updated_at TEXT DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        // 创建进程守护事件表，删除规则后保留其事件
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS supervisor_events (
+                id TEXT PRIMARY KEY,
+                rule_id TEXT NOT NULL,
+                rule_name TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                pid INTEGER,
+                exit_code INTEGER,
+                signal INTEGER,
+                message TEXT,
+                restart_count INTEGER NOT NULL,
+                created_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        
         // 创建索引以提高查询性能
         sqlx::query(
             "CREATE INDEX IF NOT EXISTS idx_historical_data_timestamp ON historical_system_data (timestamp)",
@@ -202,6 +551,70 @@ impl DatabaseManager {
         .execute(&self.pool)
         .await?;
         
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_remediation_results_rule ON remediation_results (rule_id, executed_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_alert_history_incident ON alert_history (incident_id)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_incident_events_incident ON incident_events (incident_id, created_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_notification_log_created_at ON notification_log (created_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_notification_log_pending ON notification_log (digest_id, channel)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_process_audit_log_created_at ON process_audit_log (created_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_cgroup_stats_data_path ON cgroup_stats_data (path, timestamp)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_supervisor_events_rule ON supervisor_events (rule_id, created_at)",
+        )
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 为已存在的表补充新增列
+    async fn add_column_if_missing(&self, table: &str, column: &str, definition: &str) -> Result<(), sqlx::Error> {
+        let columns = sqlx::query(&format!("PRAGMA table_info({})", table))
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let exists = columns.iter().any(|row| row.get::<String, _>("name") == column);
+        if !exists {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))
+                .execute(&self.pool)
+                .await?;
+        }
+        
         Ok(())
     }
     
@@ -212,8 +625,8 @@ impl DatabaseManager {
         // 插入主要历史数据
         sqlx::query(
             r#"
-            INSERT INTO historical_system_data (id, timestamp, cpu_usage, memory_usage, memory_total, system_load)
-            VALUES (?, ?, ?, ?, ?, ?)
+            INSERT INTO historical_system_data (id, timestamp, cpu_usage, memory_usage, memory_total, system_load, open_fds)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&data.id)
@@ -222,6 +635,7 @@ impl DatabaseManager {
         .bind(data.memory_usage)
         .bind(data.memory_total)
         .bind(data.system_load)
+        .bind(data.open_fds.map(i64::from))
         .execute(&mut *tx)
         .await?;
         
@@ -273,7 +687,7 @@ impl DatabaseManager {
     ) -> Result<Vec<HistoricalSystemData>, sqlx::Error> {
         let rows = sqlx::query(
             r#"
-            SELECT id, timestamp, cpu_usage, memory_usage, memory_total, system_load
+            SELECT id, timestamp, cpu_usage, memory_usage, memory_total, system_load, open_fds
             FROM historical_system_data
             WHERE timestamp BETWEEN ? AND ?
             ORDER BY timestamp ASC
@@ -343,12 +757,135 @@ impl DatabaseManager {
                 disk_usage,
                 network_traffic,
                 system_load: row.get("system_load"),
+                open_fds: row.get::<Option<i64>, _>("open_fds").map(|count| count as u32),
             });
         }
         
         Ok(historical_data)
     }
     
+    /// 获取单个指标的历史序列，按时间升序排列
+    /// 磁盘取各挂载点中最高的使用率，网络取收发字节数之和；没有历史记录的指标返回空序列
+    pub async fn fetch_metric_series(
+        &self,
+        metric: AlertMetric,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<(DateTime<Utc>, f64)>, sqlx::Error> {
+        let query = match metric {
+            AlertMetric::Cpu => "SELECT h.timestamp, CAST(h.cpu_usage AS REAL) AS value FROM historical_system_data h",
+            AlertMetric::Memory => "SELECT h.timestamp, CAST(h.memory_usage AS REAL) AS value FROM historical_system_data h",
+            AlertMetric::Load => "SELECT h.timestamp, CAST(h.system_load AS REAL) AS value FROM historical_system_data h",
+            AlertMetric::Disk => {
+                "SELECT h.timestamp, CAST(MAX(d.usage_percent) AS REAL) AS value FROM historical_system_data h JOIN disk_usage_data d ON d.historical_data_id = h.id"
+            }
+            AlertMetric::Network => {
+                "SELECT h.timestamp, CAST(n.bytes_received + n.bytes_sent AS REAL) AS value FROM historical_system_data h JOIN network_traffic_data n ON n.historical_data_id = h.id"
+            }
+            AlertMetric::OpenFds => "SELECT h.timestamp, CAST(h.open_fds AS REAL) AS value FROM historical_system_data h",
+            AlertMetric::Swap | AlertMetric::Cores => return Ok(Vec::new()),
+        };
+        
+        let rows = sqlx::query(&format!(
+            "{} WHERE h.timestamp BETWEEN ? AND ? GROUP BY h.id ORDER BY h.timestamp ASC",
+            query
+        ))
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let series = rows
+            .iter()
+            .filter_map(|row| {
+                let timestamp: String = row.get("timestamp");
+                let timestamp = DateTime::parse_from_rfc3339(&timestamp).ok()?.with_timezone(&Utc);
+                // 新增指标在旧记录中没有值
+                Some((timestamp, row.get::<Option<f64>, _>("value")?))
+            })
+            .collect();
+        
+        Ok(series)
+    }
+    
+    /// 获取各挂载点磁盘使用率的历史序列，按时间升序排列
+    pub async fn fetch_disk_usage_series(
+        &self,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<BTreeMap<String, Vec<(DateTime<Utc>, f64)>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT h.timestamp, d.mount_point, CAST(d.usage_percent AS REAL) AS value
+            FROM historical_system_data h
+            JOIN disk_usage_data d ON d.historical_data_id = h.id
+            WHERE h.timestamp BETWEEN ? AND ?
+            ORDER BY h.timestamp ASC
+            "#,
+        )
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut series: BTreeMap<String, Vec<(DateTime<Utc>, f64)>> = BTreeMap::new();
+        for row in rows {
+            let timestamp: String = row.get("timestamp");
+            let Ok(timestamp) = DateTime::parse_from_rfc3339(&timestamp) else {
+                continue;
+            };
+            series
+                .entry(row.get("mount_point"))
+                .or_default()
+                .push((timestamp.with_timezone(&Utc), row.get("value")));
+        }
+        
+        Ok(series)
+    }
+    
+    /// 预测磁盘或内存使用率到达阈值的时间
+    /// 磁盘未指定挂载点时对每个挂载点分别预测，历史样本不足的对象不会出现在结果中
+    pub async fn forecast_resource(
+        &self,
+        metric: AlertMetric,
+        target: Option<&str>,
+        lookback_hours: u32,
+        threshold: f64,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<ResourceForecast>, sqlx::Error> {
+        if !forecast::supports_forecast(metric) {
+            return Ok(Vec::new());
+        }
+        
+        // 回看时长超出时间范围时没有可拟合的数据
+        let Some(start_time) = now.checked_sub_signed(chrono::Duration::hours(lookback_hours as i64)) else {
+            return Ok(Vec::new());
+        };
+        let mut forecasts = Vec::new();
+        
+        if metric == AlertMetric::Disk {
+            let series = self.fetch_disk_usage_series(start_time, now).await?;
+            for (mount_point, samples) in series {
+                if target.is_some_and(|target| target != mount_point) {
+                    continue;
+                }
+                forecasts.extend(ResourceForecast::compute(
+                    metric,
+                    Some(mount_point),
+                    &samples,
+                    threshold,
+                    lookback_hours,
+                    now,
+                ));
+            }
+        } else {
+            let samples = self.fetch_metric_series(metric, start_time, now).await?;
+            forecasts.extend(ResourceForecast::compute(metric, None, &samples, threshold, lookback_hours, now));
+        }
+        
+        Ok(forecasts)
+    }
+    
     /// 清理历史数据
     pub async fn prune_historical_data(&self, retention_days: i32) -> Result<u64, sqlx::Error> {
         let cutoff_time = Utc::now() - chrono::Duration::days(retention_days as i64);
@@ -430,9 +967,10 @@ impl DatabaseManager {
     }
     
     /// 获取所有警报配置
+    /// 无法解析的旧记录会被跳过并记录日志，详情可通过 `migrate_alert_configurations` 查看
     pub async fn get_alert_configurations(&self) -> Result<Vec<AlertConfiguration>, sqlx::Error> {
         let rows = sqlx::query(
-            "SELECT id, metric, condition, threshold, severity, enabled, notification_methods FROM alert_configurations"
+            "SELECT id, metric, condition, threshold, severity, enabled, notification_methods, expression, target, escalation_policy_id, anomaly, forecast, message_template, remediation FROM alert_configurations"
         )
         .fetch_all(&self.pool)
         .await?;
@@ -444,107 +982,408 @@ impl DatabaseManager {
             let notification_methods: Vec<String> = serde_json::from_str(&notification_methods_str)
                 .unwrap_or_default();
             
+            let metric: String = row.get("metric");
+            let condition: String = row.get("condition");
+            let severity: String = row.get("severity");
+            let (Ok(metric), Ok(condition), Ok(severity), Ok(anomaly), Ok(forecast), Ok(remediation)) = (
+                metric.parse::<AlertMetric>(),
+                condition.parse::<AlertComparison>(),
+                severity.parse::<AlertSeverity>(),
+                parse_json_column::<AnomalyDetection>(row.get("anomaly")),
+                parse_json_column::<ForecastCondition>(row.get("forecast")),
+                parse_json_column::<RemediationPolicy>(row.get("remediation")),
+            ) else {
+                eprintln!("Skipped unreadable alert configuration {}", row.get::<String, _>("id"));
+                continue;
+            };
+            
             configurations.push(AlertConfiguration {
                 id: row.get("id"),
-                metric: row.get("metric"),
-                condition: row.get("condition"),
+                metric,
+                condition,
                 threshold: row.get("threshold"),
-                severity: row.get("severity"),
+                severity,
                 enabled: row.get("enabled"),
                 notification_methods,
+                expression: row.get("expression"),
+                target: row.get("target"),
+                escalation_policy_id: row.get("escalation_policy_id"),
+                anomaly,
+                forecast,
+                message_template: row.get("message_template"),
+                remediation,
             });
         }
         
         Ok(configurations)
     }
     
-    /// 添加警报配置
-    pub async fn add_alert_configuration(&self, config: &AlertConfiguration) -> Result<String, sqlx::Error> {
-        let id = Uuid::new_v4().to_string();
-        let notification_methods_json = serde_json::to_string(&config.notification_methods)
-            .unwrap_or_else(|_| "[]".to_string());
-        
-        sqlx::query(
-            r#"
-            INSERT INTO alert_configurations (id, metric, condition, threshold, severity, enabled, notification_methods)
-            VALUES (?, ?, ?, ?, ?, ?, ?)
-            "#,
+    /// 校验已存储的警报配置
+    /// `repair` 为 true 时将可识别的旧写法改写为规范值，并禁用无法修复的规则
+    pub async fn migrate_alert_configurations(&self, repair: bool) -> Result<AlertMigrationReport, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, metric, condition, threshold, severity, enabled, notification_methods, expression, target, escalation_policy_id, anomaly, forecast, message_template, remediation FROM alert_configurations"
         )
-        .bind(&id)
-        .bind(&config.metric)
-        .bind(&config.condition)
-        .bind(config.threshold)
-        .bind(&config.severity)
-        .bind(config.enabled)
-        .bind(&notification_methods_json)
-        .execute(&self.pool)
+        .fetch_all(&self.pool)
         .await?;
         
-        Ok(id)
-    }
+        let mut report = AlertMigrationReport::default();
+        
+        for row in rows {
+            report.checked += 1;
+            
+            let id: String = row.get("id");
+            let enabled: bool = row.get("enabled");
+            let raw_metric: String = row.get("metric");
+            let raw_condition: String = row.get("condition");
+            let raw_severity: String = row.get("severity");
+            let mut issues = Vec::new();
+            let mut broken = false;
+            
+            let metric = raw_metric.parse::<AlertMetric>();
+            let condition = raw_condition.parse::<AlertComparison>();
+            let severity = raw_severity.parse::<AlertSeverity>();
+            
+            // 可识别但不是规范写法的值可以直接改写
+            let canonical = [
+                ("metric", &raw_metric, metric.as_ref().map(|m| m.as_str())),
+                ("condition", &raw_condition, condition.as_ref().map(|c| c.as_str())),
+                ("severity", &raw_severity, severity.as_ref().map(|s| s.as_str())),
+            ];
+            let mut rewrites = Vec::new();
+            for (field, raw, parsed) in canonical {
+                match parsed {
+                    Ok(value) if value != raw.as_str() => {
+                        rewrites.push((field, value));
+                        issues.push((field, raw.clone(), format!("non-canonical value, canonical form is `{}`", value), true));
+                    }
+                    Ok(_) => {}
+                    Err(message) => {
+                        broken = true;
+                        issues.push((field, raw.clone(), message.clone(), false));
+                    }
+                }
+            }
+            
+            let raw_anomaly: Option<String> = row.get("anomaly");
+            let anomaly = parse_json_column::<AnomalyDetection>(raw_anomaly.clone());
+            if let Err(e) = &anomaly {
+                broken = true;
+                issues.push(("anomaly", raw_anomaly.unwrap_or_default(), e.to_string(), false));
+            }
+            let raw_forecast: Option<String> = row.get("forecast");
+            let forecast = parse_json_column::<ForecastCondition>(raw_forecast.clone());
+            if let Err(e) = &forecast {
+                broken = true;
+                issues.push(("forecast", raw_forecast.unwrap_or_default(), e.to_string(), false));
+            }
+            let raw_remediation: Option<String> = row.get("remediation");
+            let remediation = parse_json_column::<RemediationPolicy>(raw_remediation.clone());
+            if let Err(e) = &remediation {
+                broken = true;
+                issues.push(("remediation", raw_remediation.unwrap_or_default(), e.to_string(), false));
+            }
+            
+            if let (Ok(metric), Ok(condition), Ok(severity), Ok(anomaly), Ok(forecast), Ok(remediation)) =
+                (metric, condition, severity, anomaly, forecast, remediation)
+            {
+                let notification_methods_str: String = row.get("notification_methods");
+                let config = AlertConfiguration {
+                    id: id.clone(),
+                    metric,
+                    condition,
+                    threshold: row.get("threshold"),
+                    severity,
+                    enabled,
+                    notification_methods: serde_json::from_str(&notification_methods_str).unwrap_or_default(),
+                    expression: row.get("expression"),
+                    target: row.get("target"),
+                    escalation_policy_id: row.get("escalation_policy_id"),
+                    anomaly,
+                    forecast,
+                    message_template: row.get("message_template"),
+                    remediation,
+                };
+                if let Err(message) = alerting::validate_configuration(&config) {
+                    broken = true;
+                    issues.push(("rule", String::new(), message, false));
+                }
+            }
+            
+            let repaired = repair && !rewrites.is_empty();
+            if repaired {
+                for (field, value) in &rewrites {
+                    sqlx::query(&format!(
+                        "UPDATE alert_configurations SET {} = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?",
+                        field
+                    ))
+                    .bind(value)
+                    .bind(&id)
+                    .execute(&self.pool)
+                    .await?;
+                }
+                report.repaired += 1;
+            }
+            
+            let disabled = repair && broken && enabled;
+            if disabled {
+                sqlx::query("UPDATE alert_configurations SET enabled = 0, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+                    .bind(&id)
+                    .execute(&self.pool)
+                    .await?;
+                report.disabled += 1;
+            }
+            
+            for (field, value, message, repairable) in issues {
+                let action = if repairable && repaired {
+                    AlertIssueAction::Repaired
+                } else if !repairable && disabled {
+                    AlertIssueAction::Disabled
+                } else {
+                    AlertIssueAction::Reported
+                };
+                report.issues.push(AlertConfigurationIssue {
+                    id: id.clone(),
+                    field: field.to_string(),
+                    value,
+                    message,
+                    action,
+                });
+            }
+        }
+        
+        Ok(report)
+    }
+    
+    /// 添加警报配置
+    pub async fn add_alert_configuration(&self, config: &AlertConfiguration) -> Result<String, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_alert_configuration(&mut conn, &id, config).await?;
+        Ok(id)
+    }
+    
+    /// 以指定 ID 写入警报配置
+    async fn insert_alert_configuration(conn: &mut SqliteConnection, id: &str, config: &AlertConfiguration) -> Result<(), sqlx::Error> {
+        let notification_methods_json = serde_json::to_string(&config.notification_methods)
+            .unwrap_or_else(|_| "[]".to_string());
+        let anomaly_json = config.anomaly.as_ref().and_then(|anomaly| serde_json::to_string(anomaly).ok());
+        let forecast_json = config.forecast.as_ref().and_then(|forecast| serde_json::to_string(forecast).ok());
+        let remediation_json = config.remediation.as_ref().and_then(|remediation| serde_json::to_string(remediation).ok());
+        
+        sqlx::query(
+            r#"
+            INSERT INTO alert_configurations (id, metric, condition, threshold, severity, enabled, notification_methods, expression, target, escalation_policy_id, anomaly, forecast, message_template, remediation)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(id)
+        .bind(config.metric.as_str())
+        .bind(config.condition.as_str())
+        .bind(config.threshold)
+        .bind(config.severity.as_str())
+        .bind(config.enabled)
+        .bind(&notification_methods_json)
+        .bind(&config.expression)
+        .bind(&config.target)
+        .bind(&config.escalation_policy_id)
+        .bind(&anomaly_json)
+        .bind(&forecast_json)
+        .bind(&config.message_template)
+        .bind(&remediation_json)
+        .execute(conn)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 导入警报配置，规则 ID 已存在时按 `mode` 处理
+    /// 校验失败的规则不会导入；引用了不存在的升级策略时移除该引用并给出警告，修复动作一律移除。
+    /// 所有规则在同一事务中写入，写入失败时不导入任何规则
+    pub async fn import_alert_configurations(
+        &self,
+        rules: Vec<AlertConfiguration>,
+        mode: ImportConflictMode,
+    ) -> Result<AlertImportReport, sqlx::Error> {
+        let mut existing: HashSet<String> = sqlx::query("SELECT id FROM alert_configurations")
+            .fetch_all(&self.pool)
+            .await?
+            .iter()
+            .map(|row| row.get("id"))
+            .collect();
+        let policies: HashSet<String> = self
+            .get_escalation_policies()
+            .await?
+            .into_iter()
+            .map(|policy| policy.id)
+            .collect();
+        
+        let mut report = AlertImportReport::default();
+        let mut disabled = Vec::new();
+        let mut tx = self.pool.begin().await?;
+        
+        for mut rule in rules {
+            if rule.id.trim().is_empty() {
+                rule.id = Uuid::new_v4().to_string();
+            }
+            
+            if let Err(message) = alerting::validate_configuration(&rule) {
+                report.failed.push(AlertImportIssue { id: rule.id, message });
+                continue;
+            }
+            
+            // 导入的规则不能直接安装会自动执行的修复动作，需要在导入后由用户重新配置
+            if rule.remediation.take().is_some() {
+                report.warnings.push(AlertImportIssue {
+                    id: rule.id.clone(),
+                    message: "remediation actions are not imported and were removed; review and add them again".to_string(),
+                });
+            }
+            
+            if let Some(policy_id) = rule.escalation_policy_id.clone().filter(|policy_id| !policies.contains(policy_id)) {
+                rule.escalation_policy_id = None;
+                report.warnings.push(AlertImportIssue {
+                    id: rule.id.clone(),
+                    message: format!("escalation policy {} does not exist and was removed", policy_id),
+                });
+            }
+            
+            if existing.contains(&rule.id) {
+                match mode {
+                    ImportConflictMode::Skip => {
+                        report.skipped.push(rule.id);
+                        continue;
+                    }
+                    ImportConflictMode::Overwrite => {
+                        Self::write_alert_configuration(&mut tx, &rule.id, &rule).await?;
+                        if !rule.enabled {
+                            disabled.push(rule.id.clone());
+                        }
+                        report.overwritten.push(rule.id);
+                        continue;
+                    }
+                    ImportConflictMode::Rename => {
+                        let new_id = Uuid::new_v4().to_string();
+                        report.renamed.push(RenamedAlertRule {
+                            original_id: std::mem::replace(&mut rule.id, new_id.clone()),
+                            new_id,
+                        });
+                    }
+                }
+            } else {
+                report.imported.push(rule.id.clone());
+            }
+            
+            Self::insert_alert_configuration(&mut tx, &rule.id, &rule).await?;
+            existing.insert(rule.id);
+        }
+        
+        tx.commit().await?;
+        
+        // 被覆盖为停用的规则与手动停用时一样恢复其警报
+        for id in disabled {
+            self.resolve_rule_alerts(&id).await?;
+        }
+        
+        Ok(report)
+    }
     
     /// 更新警报配置
     pub async fn update_alert_configuration(&self, id: &str, config: &AlertConfiguration) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        if Self::write_alert_configuration(&mut conn, id, config).await? == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        drop(conn);
+        
+        // 停用的规则不再求值，其未恢复的警报随之恢复
+        if !config.enabled {
+            self.resolve_rule_alerts(id).await?;
+        }
+        
+        Ok(())
+    }
+    
+    /// 写入已有警报配置的新内容，返回更新的行数
+    async fn write_alert_configuration(conn: &mut SqliteConnection, id: &str, config: &AlertConfiguration) -> Result<u64, sqlx::Error> {
         let notification_methods_json = serde_json::to_string(&config.notification_methods)
             .unwrap_or_else(|_| "[]".to_string());
+        let anomaly_json = config.anomaly.as_ref().and_then(|anomaly| serde_json::to_string(anomaly).ok());
+        let forecast_json = config.forecast.as_ref().and_then(|forecast| serde_json::to_string(forecast).ok());
+        let remediation_json = config.remediation.as_ref().and_then(|remediation| serde_json::to_string(remediation).ok());
         
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE alert_configurations
-            SET metric = ?, condition = ?, threshold = ?, severity = ?, enabled = ?, notification_methods = ?, updated_at = CURRENT_TIMESTAMP
+            SET metric = ?, condition = ?, threshold = ?, severity = ?, enabled = ?, notification_methods = ?, expression = ?, target = ?, escalation_policy_id = ?, anomaly = ?, forecast = ?, message_template = ?, remediation = ?, updated_at = CURRENT_TIMESTAMP
             WHERE id = ?
             "#,
         )
-        .bind(&config.metric)
-        .bind(&config.condition)
+        .bind(config.metric.as_str())
+        .bind(config.condition.as_str())
         .bind(config.threshold)
-        .bind(&config.severity)
+        .bind(config.severity.as_str())
         .bind(config.enabled)
         .bind(&notification_methods_json)
+        .bind(&config.expression)
+        .bind(&config.target)
+        .bind(&config.escalation_policy_id)
+        .bind(&anomaly_json)
+        .bind(&forecast_json)
+        .bind(&config.message_template)
+        .bind(&remediation_json)
         .bind(id)
-        .execute(&self.pool)
+        .execute(conn)
         .await?;
         
-        Ok(())
+        Ok(result.rows_affected())
     }
     
-    /// 删除警报配置
+    /// 删除警报配置，其未恢复的警报随之恢复
     pub async fn delete_alert_configuration(&self, id: &str) -> Result<(), sqlx::Error> {
         sqlx::query("DELETE FROM alert_configurations WHERE id = ?")
             .bind(id)
             .execute(&self.pool)
             .await?;
         
-        Ok(())
+        self.resolve_rule_alerts(id).await
     }
     
-    /// 获取警报历史
-    pub async fn get_alert_history(&self, limit: u32, offset: u32) -> Result<Vec<AlertHistory>, sqlx::Error> {
-        let rows = sqlx::query(
+    /// 恢复规则所有未恢复的警报，并解决因此不再有未恢复警报的事件
+    async fn resolve_rule_alerts(&self, rule_id: &str) -> Result<(), sqlx::Error> {
+        let now = Utc::now();
+        self.resolve_alerts(rule_id, now).await?;
+        self.resolve_finished_incidents(now).await
+    }
+    
+    /// 获取警报历史，按触发时间倒序排列；`limit` 为空时返回全部匹配记录
+    pub async fn get_alert_history(
+        &self,
+        filter: &AlertHistoryFilter,
+        limit: Option<u32>,
+        offset: u32,
+    ) -> Result<Vec<AlertHistory>, sqlx::Error> {
+        let mut query = QueryBuilder::<Sqlite>::new(
             r#"
-            SELECT id, alert_id, triggered_at, value, message, acknowledged, acknowledged_at, acknowledged_by
-            FROM alert_history
-            ORDER BY triggered_at DESC
-            LIMIT ? OFFSET ?
+            SELECT h.id, h.alert_id, h.triggered_at, h.value, h.message, h.acknowledged, h.acknowledged_at, h.acknowledged_by,
+                   h.suppressed, h.silence_id, h.severity, h.escalation_level, h.resolved_at, h.incident_id
+            FROM alert_history h
+            LEFT JOIN alert_configurations c ON c.id = h.alert_id
             "#,
-        )
-        .bind(limit)
-        .bind(offset)
-        .fetch_all(&self.pool)
-        .await?;
+        );
+        push_history_filter(&mut query, filter);
+        query.push(" ORDER BY h.triggered_at DESC");
+        if let Some(limit) = limit {
+            query.push(" LIMIT ").push_bind(limit).push(" OFFSET ").push_bind(offset);
+        }
+        
+        let rows = query.build().fetch_all(&self.pool).await?;
         
         let mut history = Vec::new();
         
         for row in rows {
             let triggered_at_str: String = row.get("triggered_at");
-            let triggered_at = DateTime::parse_from_rfc3339(&triggered_at_str)
-                .map(|dt| dt.with_timezone(&Utc))
-                .unwrap_or_else(|_| Utc::now());
-            
-            let acknowledged_at_str: Option<String> = row.get("acknowledged_at");
-            let acknowledged_at = acknowledged_at_str
-                .and_then(|ts| DateTime::parse_from_rfc3339(&ts).ok())
-                .map(|dt| dt.with_timezone(&Utc));
+            let triggered_at = parse_timestamp(&triggered_at_str).unwrap_or_else(Utc::now);
             
             history.push(AlertHistory {
                 id: row.get("id"),
@@ -553,8 +1392,14 @@ impl DatabaseManager {
                 value: row.get("value"),
                 message: row.get("message"),
                 acknowledged: row.get("acknowledged"),
-                acknowledged_at,
+                acknowledged_at: row.get::<Option<String>, _>("acknowledged_at").and_then(|ts| parse_timestamp(&ts)),
                 acknowledged_by: row.get("acknowledged_by"),
+                suppressed: row.get("suppressed"),
+                silence_id: row.get("silence_id"),
+                severity: row.get::<Option<String>, _>("severity").and_then(|s| s.parse().ok()),
+                escalation_level: row.get::<i64, _>("escalation_level") as u32,
+                resolved_at: row.get::<Option<String>, _>("resolved_at").and_then(|ts| parse_timestamp(&ts)),
+                incident_id: row.get("incident_id"),
             });
         }
         
@@ -563,95 +1408,1801 @@ impl DatabaseManager {
     
     /// 确认警报
     pub async fn acknowledge_alert(&self, id: &str, acknowledged_by: &str) -> Result<(), sqlx::Error> {
-        sqlx::query(
+        let result = sqlx::query(
             r#"
             UPDATE alert_history
-            SET acknowledged = 1, acknowledged_at = CURRENT_TIMESTAMP, acknowledged_by = ?
+            SET acknowledged = 1, acknowledged_at = ?, acknowledged_by = ?
             WHERE id = ?
             "#,
         )
+        .bind(Utc::now().to_rfc3339())
         .bind(acknowledged_by)
         .bind(id)
         .execute(&self.pool)
         .await?;
         
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
         Ok(())
     }
     
-    /// 检查警报
-    pub async fn check_alerts(&self, cpu_usage: f32, memory_usage: f32, disk_usage: f32, network_traffic: f64) -> Result<Vec<AlertHistory>, sqlx::Error> {
-        let configurations = self.get_alert_configurations().await?;
+    /// 批量确认匹配过滤条件且尚未确认的警报，返回确认的数量
+    pub async fn acknowledge_alerts(&self, filter: &AlertHistoryFilter, acknowledged_by: &str) -> Result<u64, sqlx::Error> {
+        let mut query = QueryBuilder::<Sqlite>::new("UPDATE alert_history SET acknowledged = 1, acknowledged_at = ");
+        query
+            .push_bind(Utc::now().to_rfc3339())
+            .push(", acknowledged_by = ")
+            .push_bind(acknowledged_by.to_string())
+            .push(
+                r#"
+                WHERE acknowledged = 0 AND id IN (
+                    SELECT h.id FROM alert_history h
+                    LEFT JOIN alert_configurations c ON c.id = h.alert_id
+                "#,
+            );
+        push_history_filter(&mut query, filter);
+        query.push(")");
+        
+        let result = query.build().execute(&self.pool).await?;
+        Ok(result.rows_affected())
+    }
+    
+    /// 统计匹配过滤条件的警报
+    pub async fn get_alert_statistics(
+        &self,
+        filter: &AlertHistoryFilter,
+        noisiest_limit: usize,
+    ) -> Result<AlertStatistics, sqlx::Error> {
+        let history = self.get_alert_history(filter, None, 0).await?;
+        Ok(AlertStatistics::from_history(&history, noisiest_limit))
+    }
+    
+    /// 按调用方已加载的警报配置检查警报，未静默的警报按 `grouping` 归入事件
+    pub async fn check_alerts(
+        &self,
+        configurations: Vec<AlertConfiguration>,
+        snapshot: &MetricSnapshot,
+        environment: &MessageEnvironment,
+        grouping: &IncidentGrouping,
+    ) -> Result<Vec<AlertHistory>, sqlx::Error> {
         let mut triggered_alerts = Vec::new();
+        let now = Utc::now();
+        
+        // 表达式规则只在添加时校验，这里解析失败的规则直接跳过
+        let expressions: HashMap<String, AlertExpression> = configurations
+            .iter()
+            .filter(|config| config.enabled)
+            .filter_map(|config| {
+                let source = config.expression.as_deref()?;
+                AlertExpression::parse(source).ok().map(|expr| (config.id.clone(), expr))
+            })
+            .collect();
+        
+        // 一次性加载所有窗口函数需要的历史数据
+        let history_window_secs = expressions
+            .values()
+            .map(|expr| expr.history_window_secs())
+            .fold(0.0, f64::max);
+        // 窗口长度在解析时已有上限，超出时间范围时不加载历史，窗口函数无法求值
+        let start_time = chrono::Duration::try_seconds(history_window_secs.ceil() as i64)
+            .and_then(|window| now.checked_sub_signed(window));
+        let history = match start_time {
+            Some(start_time) if history_window_secs > 0.0 => self.fetch_historical_data(start_time, now).await?,
+            _ => Vec::new(),
+        };
+        let ctx = EvaluationContext {
+            snapshot,
+            history: &history,
+            now,
+        };
+        
+        let silences = self.get_alert_silences().await?;
+        let firing_since = self.get_firing_since().await?;
+        let mut incidents = self
+            .get_incidents(&[IncidentStatus::Open, IncidentStatus::Acknowledged], None)
+            .await?;
         
         for config in configurations {
             if !config.enabled {
                 continue;
             }
             
-            let value = match config.metric.as_str() {
-                "cpu" => cpu_usage as f64,
-                "memory" => memory_usage as f64,
-                "disk" => disk_usage as f64,
-                "network" => network_traffic,
-                _ => continue,
+            match self.evaluate_rule(&config, &expressions, &ctx, environment.locale).await? {
+                RuleEvaluation::Firing { value, details } => {
+                    let context = AlertMessageContext::for_rule(
+                        &config,
+                        value,
+                        details,
+                        environment,
+                        firing_since.get(&config.id).copied(),
+                        now,
+                    );
+                    let message = render_alert_message(&config, &context, environment.locale);
+                    let silence = silences.iter().find(|silence| silence.silences(&config, now));
+                    let mut alert = self.record_alert(&config, value, message, silence).await?;
+                    if !alert.suppressed {
+                        let incident_id = self.attach_to_incident(&config, &alert, grouping, &mut incidents).await?;
+                        alert.incident_id = Some(incident_id);
+                        // 静默期内不执行修复动作
+                        // 修复失败不影响其余规则的检查
+                        if let Some(policy) = &config.remediation {
+                            if let Err(e) = self.start_remediation(policy, &alert, context, now).await {
+                                eprintln!("Failed to start remediation for alert {}: {}", alert.id, e);
+                            }
+                        }
+                        triggered_alerts.push(alert);
+                    }
+                }
+                RuleEvaluation::Clear => self.resolve_alerts(&config.id, now).await?,
+                RuleEvaluation::Unknown => {}
+            }
+        }
+        
+        self.resolve_finished_incidents(now).await?;
+        
+        Ok(triggered_alerts)
+    }
+    
+    /// 对单条规则求值，需要历史数据的规则类型会在这里查询数据库
+    async fn evaluate_rule(
+        &self,
+        config: &AlertConfiguration,
+        expressions: &HashMap<String, AlertExpression>,
+        ctx: &EvaluationContext<'_>,
+        locale: MessageLocale,
+    ) -> Result<RuleEvaluation, sqlx::Error> {
+        let now = ctx.now;
+        
+        if config.expression.is_some() {
+            let Some(expr) = expressions.get(&config.id) else {
+                return Ok(RuleEvaluation::Unknown);
             };
-            
-            let should_trigger = match config.condition.as_str() {
-                ">" => value > config.threshold,
-                "<" => value < config.threshold,
-                ">=" => value >= config.threshold,
-                "<=" => value <= config.threshold,
-                "==" => value == config.threshold,
-                _ => false,
+            let Some(outcome) = expr.evaluate(ctx) else {
+                return Ok(RuleEvaluation::Unknown);
             };
-            
-            if should_trigger {
-                let alert_id = Uuid::new_v4().to_string();
-                let message = format!(
-                    "{} alert triggered: {} {} {} (current: {}, threshold: {})",
-                    config.severity,
-                    config.metric,
-                    config.condition,
-                    config.threshold,
-                    value,
-                    config.threshold
-                );
-                
-                sqlx::query(
-                    r#"
-                    INSERT INTO alert_history (id, alert_id, triggered_at, value, message)
-                    VALUES (?, ?, ?, ?, ?)
-                    "#,
-                )
-                .bind(&alert_id)
-                .bind(&config.id)
-                .bind(Utc::now().to_rfc3339())
-                .bind(value)
-                .bind(&message)
-                .execute(&self.pool)
+            if !outcome.fired {
+                return Ok(RuleEvaluation::Clear);
+            }
+            let details = template::describe_expression(&expr.to_string(), outcome.value, locale);
+            return Ok(RuleEvaluation::Firing { value: outcome.value, details });
+        }
+        
+        if let Some(condition) = &config.forecast {
+            let forecasts = self
+                .forecast_resource(config.metric, config.target.as_deref(), condition.lookback_hours, condition.threshold, now)
                 .await?;
-                
-                triggered_alerts.push(AlertHistory {
-                    id: alert_id,
-                    alert_id: config.id,
-                    triggered_at: Utc::now(),
-                    value,
-                    message,
-                    acknowledged: false,
-                    acknowledged_at: None,
-                    acknowledged_by: None,
-                });
+            if forecasts.is_empty() {
+                return Ok(RuleEvaluation::Unknown);
             }
+            // 多个挂载点时只针对最先耗尽的一个触发
+            let Some(forecast) = ResourceForecast::soonest_firing(forecasts, condition) else {
+                return Ok(RuleEvaluation::Clear);
+            };
+            let hours = forecast.hours_to_threshold.unwrap_or_default();
+            let details = template::describe_forecast(&forecast, condition, locale);
+            return Ok(RuleEvaluation::Firing { value: hours, details });
         }
         
-        Ok(triggered_alerts)
+        if let Some(detection) = &config.anomaly {
+            let Some(start_time) = detection.lookback_start(now) else {
+                return Ok(RuleEvaluation::Unknown);
+            };
+            let series = self.fetch_metric_series(config.metric, start_time, now).await?;
+            // 历史样本不足时无法建立基线
+            let Some(baseline) = detection.baseline(&series, now) else {
+                return Ok(RuleEvaluation::Unknown);
+            };
+            let Some(value) = ctx.snapshot.value(config.metric) else {
+                return Ok(RuleEvaluation::Unknown);
+            };
+            let verdict = detection.evaluate(&baseline, value);
+            if !verdict.is_anomaly {
+                return Ok(RuleEvaluation::Clear);
+            }
+            let details = template::describe_anomaly(config, detection, &verdict, locale);
+            let details = Self::with_fd_process(config, details, ctx.snapshot, locale);
+            return Ok(RuleEvaluation::Firing { value: verdict.value, details });
+        }
+        
+        // 未采集的指标（如不支持的平台上的文件描述符数量）无法求值
+        let Some(value) = ctx.snapshot.value(config.metric) else {
+            return Ok(RuleEvaluation::Unknown);
+        };
+        if !config.condition.apply(value, config.threshold) {
+            return Ok(RuleEvaluation::Clear);
+        }
+        let details = template::describe_threshold(config, value, locale);
+        let details = Self::with_fd_process(config, details, ctx.snapshot, locale);
+        Ok(RuleEvaluation::Firing { value, details })
     }
-}
-
-// 数据库统计信息
-#[derive(Debug, Serialize, Deserialize)]
-pub struct DatabaseStats {
-    pub total_records: i64,
-    pub oldest_timestamp: Option<DateTime<Utc>>,
-    pub newest_timestamp: Option<DateTime<Utc>>,
-}
\ No newline at end of file
+    
+    /// 文件描述符规则的描述注明是哪个进程持有最多的文件描述符
+    fn with_fd_process(config: &AlertConfiguration, details: String, snapshot: &MetricSnapshot, locale: MessageLocale) -> String {
+        match &snapshot.open_fds_process {
+            Some(process) if config.metric == AlertMetric::OpenFds => template::describe_fd_process(details, process, locale),
+            _ => details,
+        }
+    }
+    
+    /// 获取每条规则最早一条尚未恢复的警报的触发时间，用于计算持续时间
+    async fn get_firing_since(&self) -> Result<HashMap<String, DateTime<Utc>>, sqlx::Error> {
+        let rows = sqlx::query("SELECT alert_id, triggered_at FROM alert_history WHERE resolved_at IS NULL")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut firing_since: HashMap<String, DateTime<Utc>> = HashMap::new();
+        for row in rows {
+            let triggered_at: String = row.get("triggered_at");
+            let Some(triggered_at) = parse_timestamp(&triggered_at) else {
+                continue;
+            };
+            firing_since
+                .entry(row.get("alert_id"))
+                .and_modify(|since| *since = (*since).min(triggered_at))
+                .or_insert(triggered_at);
+        }
+        
+        Ok(firing_since)
+    }
+    
+    /// 规则恢复正常时，为该规则尚未恢复的警报记录恢复时间
+    async fn resolve_alerts(&self, rule_id: &str, now: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE alert_history SET resolved_at = ? WHERE alert_id = ? AND resolved_at IS NULL")
+            .bind(now.to_rfc3339())
+            .bind(rule_id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    /// 写入一条警报历史记录，处于静默期的记录会带上静默标记
+    async fn record_alert(
+        &self,
+        config: &AlertConfiguration,
+        value: f64,
+        message: String,
+        silence: Option<&AlertSilence>,
+    ) -> Result<AlertHistory, sqlx::Error> {
+        let alert_id = Uuid::new_v4().to_string();
+        let triggered_at = Utc::now();
+        let silence_id = silence.map(|silence| silence.id.clone());
+        
+        sqlx::query(
+            r#"
+            INSERT INTO alert_history (id, alert_id, triggered_at, value, message, suppressed, silence_id, severity)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&alert_id)
+        .bind(&config.id)
+        .bind(triggered_at.to_rfc3339())
+        .bind(value)
+        .bind(&message)
+        .bind(silence_id.is_some())
+        .bind(&silence_id)
+        .bind(config.severity.as_str())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(AlertHistory {
+            id: alert_id,
+            alert_id: config.id.clone(),
+            triggered_at,
+            value,
+            message,
+            acknowledged: false,
+            acknowledged_at: None,
+            acknowledged_by: None,
+            suppressed: silence_id.is_some(),
+            silence_id,
+            severity: Some(config.severity),
+            escalation_level: 0,
+            resolved_at: None,
+            incident_id: None,
+        })
+    }
+    
+    /// 获取所有警报静默
+    pub async fn get_alert_silences(&self) -> Result<Vec<AlertSilence>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, comment, matcher, schedule, enabled, created_by, created_at FROM alert_silences ORDER BY created_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut silences = Vec::new();
+        
+        for row in rows {
+            let matcher_str: String = row.get("matcher");
+            let schedule_str: String = row.get("schedule");
+            let Ok(schedule) = serde_json::from_str::<SilenceSchedule>(&schedule_str) else {
+                continue;
+            };
+            
+            let created_at_str: String = row.get("created_at");
+            let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                .ok()
+                .map(|dt| dt.with_timezone(&Utc));
+            
+            silences.push(AlertSilence {
+                id: row.get("id"),
+                comment: row.get("comment"),
+                matcher: serde_json::from_str::<SilenceMatcher>(&matcher_str).unwrap_or_default(),
+                schedule,
+                enabled: row.get("enabled"),
+                created_by: row.get("created_by"),
+                created_at,
+            });
+        }
+        
+        Ok(silences)
+    }
+    
+    /// 添加警报静默
+    pub async fn add_alert_silence(&self, silence: &AlertSilence) -> Result<String, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let matcher_json = serde_json::to_string(&silence.matcher)
+            .unwrap_or_else(|_| "{}".to_string());
+        let schedule_json = serde_json::to_string(&silence.schedule)
+            .unwrap_or_else(|_| "{}".to_string());
+        
+        sqlx::query(
+            r#"
+            INSERT INTO alert_silences (id, comment, matcher, schedule, enabled, created_by, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(&silence.comment)
+        .bind(&matcher_json)
+        .bind(&schedule_json)
+        .bind(silence.enabled)
+        .bind(&silence.created_by)
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(id)
+    }
+    
+    /// 更新警报静默
+    pub async fn update_alert_silence(&self, id: &str, silence: &AlertSilence) -> Result<(), sqlx::Error> {
+        let matcher_json = serde_json::to_string(&silence.matcher)
+            .unwrap_or_else(|_| "{}".to_string());
+        let schedule_json = serde_json::to_string(&silence.schedule)
+            .unwrap_or_else(|_| "{}".to_string());
+        
+        let result = sqlx::query(
+            r#"
+            UPDATE alert_silences
+            SET comment = ?, matcher = ?, schedule = ?, enabled = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(&silence.comment)
+        .bind(&matcher_json)
+        .bind(&schedule_json)
+        .bind(silence.enabled)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    /// 删除警报静默
+    pub async fn delete_alert_silence(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM alert_silences WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取所有升级策略
+    pub async fn get_escalation_policies(&self) -> Result<Vec<EscalationPolicy>, sqlx::Error> {
+        let rows = sqlx::query("SELECT id, name, steps FROM escalation_policies ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+        
+        let mut policies = Vec::new();
+        
+        for row in rows {
+            let steps_str: String = row.get("steps");
+            let steps: Vec<EscalationStep> = serde_json::from_str(&steps_str).unwrap_or_default();
+            
+            policies.push(EscalationPolicy {
+                id: row.get("id"),
+                name: row.get("name"),
+                steps,
+            });
+        }
+        
+        Ok(policies)
+    }
+    
+    /// 按 ID 获取升级策略
+    pub async fn get_escalation_policy(&self, id: &str) -> Result<Option<EscalationPolicy>, sqlx::Error> {
+        Ok(self
+            .get_escalation_policies()
+            .await?
+            .into_iter()
+            .find(|policy| policy.id == id))
+    }
+    
+    /// 添加升级策略
+    pub async fn add_escalation_policy(&self, policy: &EscalationPolicy) -> Result<String, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let steps_json = serde_json::to_string(&policy.steps)
+            .unwrap_or_else(|_| "[]".to_string());
+        
+        sqlx::query("INSERT INTO escalation_policies (id, name, steps) VALUES (?, ?, ?)")
+            .bind(&id)
+            .bind(&policy.name)
+            .bind(&steps_json)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(id)
+    }
+    
+    /// 更新升级策略
+    pub async fn update_escalation_policy(&self, id: &str, policy: &EscalationPolicy) -> Result<(), sqlx::Error> {
+        let steps_json = serde_json::to_string(&policy.steps)
+            .unwrap_or_else(|_| "[]".to_string());
+        
+        let result = sqlx::query(
+            "UPDATE escalation_policies SET name = ?, steps = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?"
+        )
+        .bind(&policy.name)
+        .bind(&steps_json)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    /// 删除升级策略，并解除引用它的规则
+    pub async fn delete_escalation_policy(&self, id: &str) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        
+        sqlx::query("UPDATE alert_configurations SET escalation_policy_id = NULL WHERE escalation_policy_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        
+        sqlx::query("DELETE FROM escalation_policies WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// 处理未确认警报的升级，返回本次执行的升级
+    pub async fn process_escalations(&self, now: DateTime<Utc>) -> Result<Vec<AlertEscalation>, sqlx::Error> {
+        let policies: HashMap<String, EscalationPolicy> = self
+            .get_escalation_policies()
+            .await?
+            .into_iter()
+            .map(|policy| (policy.id.clone(), policy))
+            .collect();
+        
+        if policies.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        // 规则持续触发时每次检查都会写入一条记录，每次触发过程只按最早一条未恢复的记录升级；
+        // 过程中任意一条记录被确认即停止升级
+        let rows = sqlx::query(
+            r#"
+            SELECT h.id, h.alert_id, h.triggered_at, h.severity, h.escalation_level, c.escalation_policy_id
+            FROM alert_history h
+            JOIN alert_configurations c ON c.id = h.alert_id
+            WHERE h.resolved_at IS NULL AND h.suppressed = 0 AND c.escalation_policy_id IS NOT NULL
+              AND h.rowid = (
+                  SELECT e.rowid FROM alert_history e
+                  WHERE e.alert_id = h.alert_id AND e.resolved_at IS NULL AND e.suppressed = 0
+                  ORDER BY e.triggered_at, e.rowid
+                  LIMIT 1
+              )
+              AND NOT EXISTS (
+                  SELECT 1 FROM alert_history a
+                  WHERE a.alert_id = h.alert_id AND a.resolved_at IS NULL AND a.acknowledged = 1
+              )
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut escalations = Vec::new();
+        
+        for row in rows {
+            let policy_id: String = row.get("escalation_policy_id");
+            let Some(policy) = policies.get(&policy_id) else {
+                continue;
+            };
+            
+            let triggered_at_str: String = row.get("triggered_at");
+            let Ok(triggered_at) = DateTime::parse_from_rfc3339(&triggered_at_str) else {
+                continue;
+            };
+            let elapsed_minutes = (now - triggered_at.with_timezone(&Utc)).num_seconds() as f64 / 60.0;
+            
+            let current_level = row.get::<i64, _>("escalation_level") as u32;
+            let target_level = policy.level_after(elapsed_minutes);
+            let current_severity = row
+                .get::<Option<String>, _>("severity")
+                .and_then(|s| s.parse::<AlertSeverity>().ok());
+            
+            let Some((notification_methods, severity)) =
+                policy.escalate(current_level, target_level, current_severity)
+            else {
+                continue;
+            };
+            
+            let alert_history_id: String = row.get("id");
+            let rule_id: String = row.get("alert_id");
+            let message = format!(
+                "Alert {} not acknowledged after {} minutes, escalated to step {}/{} of policy {}",
+                alert_history_id,
+                elapsed_minutes.floor(),
+                target_level,
+                policy.steps.len(),
+                policy.name
+            );
+            
+            let escalation = AlertEscalation {
+                id: Uuid::new_v4().to_string(),
+                alert_history_id,
+                rule_id,
+                policy_id: policy_id.clone(),
+                step: target_level,
+                total_steps: policy.steps.len() as u32,
+                notification_methods,
+                severity,
+                escalated_at: now,
+                message,
+            };
+            
+            let mut tx = self.pool.begin().await?;
+            
+            sqlx::query(
+                r#"
+                INSERT INTO alert_escalations (id, alert_history_id, rule_id, policy_id, step, total_steps, notification_methods, severity, escalated_at, message)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&escalation.id)
+            .bind(&escalation.alert_history_id)
+            .bind(&escalation.rule_id)
+            .bind(&escalation.policy_id)
+            .bind(escalation.step)
+            .bind(escalation.total_steps)
+            .bind(serde_json::to_string(&escalation.notification_methods).unwrap_or_else(|_| "[]".to_string()))
+            .bind(escalation.severity.map(|s| s.as_str()))
+            .bind(now.to_rfc3339())
+            .bind(&escalation.message)
+            .execute(&mut *tx)
+            .await?;
+            
+            // 只在仍未确认时推进级别，避免与并发的确认操作冲突
+            sqlx::query(
+                "UPDATE alert_history SET escalation_level = ?, severity = ? WHERE id = ? AND acknowledged = 0"
+            )
+            .bind(escalation.step)
+            .bind(escalation.severity.map(|s| s.as_str()))
+            .bind(&escalation.alert_history_id)
+            .execute(&mut *tx)
+            .await?;
+            
+            tx.commit().await?;
+            escalations.push(escalation);
+        }
+        
+        Ok(escalations)
+    }
+    
+    /// 获取某条警报的升级记录
+    pub async fn get_alert_escalations(&self, alert_history_id: &str) -> Result<Vec<AlertEscalation>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, alert_history_id, rule_id, policy_id, step, total_steps, notification_methods, severity, escalated_at, message
+            FROM alert_escalations
+            WHERE alert_history_id = ?
+            ORDER BY escalated_at ASC
+            "#,
+        )
+        .bind(alert_history_id)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut escalations = Vec::new();
+        
+        for row in rows {
+            let notification_methods_str: String = row.get("notification_methods");
+            let escalated_at_str: String = row.get("escalated_at");
+            let escalated_at = DateTime::parse_from_rfc3339(&escalated_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now());
+            
+            escalations.push(AlertEscalation {
+                id: row.get("id"),
+                alert_history_id: row.get("alert_history_id"),
+                rule_id: row.get("rule_id"),
+                policy_id: row.get("policy_id"),
+                step: row.get::<i64, _>("step") as u32,
+                total_steps: row.get::<i64, _>("total_steps") as u32,
+                notification_methods: serde_json::from_str(&notification_methods_str).unwrap_or_default(),
+                severity: row.get::<Option<String>, _>("severity").and_then(|s| s.parse().ok()),
+                escalated_at,
+                message: row.get("message"),
+            });
+        }
+        
+        Ok(escalations)
+    }
+    
+    /// 开始执行规则的修复动作
+    /// 执行记录先以 running 状态写入，使频率限制对仍在执行的动作同样生效；动作在后台依次执行，完成后更新记录
+    async fn start_remediation(
+        &self,
+        policy: &RemediationPolicy,
+        alert: &AlertHistory,
+        context: AlertMessageContext,
+        now: DateTime<Utc>,
+    ) -> Result<(), sqlx::Error> {
+        // 频率检查与写入记录在同一个写事务中完成，避免并发的检查同时通过限制
+        let mut conn = self.pool.acquire().await?;
+        sqlx::query("BEGIN IMMEDIATE").execute(&mut *conn).await?;
+        let pending = match Self::reserve_remediation(&mut conn, policy, alert, now).await {
+            Ok(pending) => {
+                sqlx::query("COMMIT").execute(&mut *conn).await?;
+                pending
+            }
+            Err(e) => {
+                let _ = sqlx::query("ROLLBACK").execute(&mut *conn).await;
+                return Err(e);
+            }
+        };
+        drop(conn);
+        let Some(pending) = pending else {
+            return Ok(());
+        };
+        
+        let manager = self.clone();
+        let alert = alert.clone();
+        let dry_run = policy.dry_run;
+        tokio::spawn(async move {
+            for (id, action) in pending {
+                let (status, output) = remediation::execute_action(&action, &alert, &context, dry_run).await;
+                if let Err(e) = manager.finish_remediation_result(&id, status, &output).await {
+                    eprintln!("Failed to record remediation result {}: {}", id, e);
+                }
+            }
+        });
+        
+        Ok(())
+    }
+    
+    /// 检查频率限制并写入执行记录，被限制时写入 rate_limited 记录并返回 None
+    async fn reserve_remediation(
+        conn: &mut SqliteConnection,
+        policy: &RemediationPolicy,
+        alert: &AlertHistory,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Vec<(String, RemediationAction)>>, sqlx::Error> {
+        let recent_runs = Self::get_recent_remediation_runs(&mut *conn, &alert.alert_id, now - chrono::Duration::hours(1)).await?;
+        if let Err(reason) = policy.check_rate_limit(&recent_runs, now) {
+            Self::insert_remediation_result(&mut *conn, &RemediationResult {
+                id: Uuid::new_v4().to_string(),
+                alert_history_id: alert.id.clone(),
+                rule_id: alert.alert_id.clone(),
+                action_index: None,
+                action: None,
+                status: RemediationStatus::RateLimited,
+                output: reason,
+                executed_at: now,
+                finished_at: Some(now),
+            })
+            .await?;
+            return Ok(None);
+        }
+        
+        let mut pending = Vec::new();
+        for (index, action) in policy.actions.iter().enumerate() {
+            let result = RemediationResult {
+                id: Uuid::new_v4().to_string(),
+                alert_history_id: alert.id.clone(),
+                rule_id: alert.alert_id.clone(),
+                action_index: Some(index as u32),
+                action: Some(action.kind().to_string()),
+                status: RemediationStatus::Running,
+                output: String::new(),
+                executed_at: now,
+                finished_at: None,
+            };
+            Self::insert_remediation_result(&mut *conn, &result).await?;
+            pending.push((result.id, action.clone()));
+        }
+        Ok(Some(pending))
+    }
+    
+    /// 获取规则在指定时间之后的修复执行时间，每次触发只计一次，被频率限制的记录不计入
+    async fn get_recent_remediation_runs(
+        conn: &mut SqliteConnection,
+        rule_id: &str,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<DateTime<Utc>>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT MIN(executed_at) AS executed_at
+            FROM remediation_results
+            WHERE rule_id = ? AND status != ? AND executed_at >= ?
+            GROUP BY alert_history_id
+            "#,
+        )
+        .bind(rule_id)
+        .bind(RemediationStatus::RateLimited.as_str())
+        .bind(since.to_rfc3339())
+        .fetch_all(&mut *conn)
+        .await?;
+        
+        Ok(rows
+            .iter()
+            .filter_map(|row| parse_timestamp(&row.get::<String, _>("executed_at")))
+            .collect())
+    }
+    
+    async fn insert_remediation_result(conn: &mut SqliteConnection, result: &RemediationResult) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO remediation_results (id, alert_history_id, rule_id, action_index, action, status, output, executed_at, finished_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&result.id)
+        .bind(&result.alert_history_id)
+        .bind(&result.rule_id)
+        .bind(result.action_index.map(|index| index as i64))
+        .bind(&result.action)
+        .bind(result.status.as_str())
+        .bind(&result.output)
+        .bind(result.executed_at.to_rfc3339())
+        .bind(result.finished_at.map(|finished_at| finished_at.to_rfc3339()))
+        .execute(&mut *conn)
+        .await?;
+        
+        Ok(())
+    }
+    
+    async fn finish_remediation_result(&self, id: &str, status: RemediationStatus, output: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE remediation_results SET status = ?, output = ?, finished_at = ? WHERE id = ?")
+            .bind(status.as_str())
+            .bind(output)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取某条警报记录的修复动作执行记录
+    pub async fn get_remediation_results(&self, alert_history_id: &str) -> Result<Vec<RemediationResult>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, alert_history_id, rule_id, action_index, action, status, output, executed_at, finished_at
+            FROM remediation_results
+            WHERE alert_history_id = ?
+            ORDER BY executed_at ASC, action_index ASC
+            "#,
+        )
+        .bind(alert_history_id)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut results = Vec::new();
+        
+        for row in rows {
+            let status: String = row.get("status");
+            let executed_at: String = row.get("executed_at");
+            results.push(RemediationResult {
+                id: row.get("id"),
+                alert_history_id: row.get("alert_history_id"),
+                rule_id: row.get("rule_id"),
+                action_index: row.get::<Option<i64>, _>("action_index").map(|index| index as u32),
+                action: row.get("action"),
+                // 未知状态来自更新的版本，按失败显示
+                status: RemediationStatus::from_name(&status).unwrap_or(RemediationStatus::Failed),
+                output: row.get("output"),
+                executed_at: parse_timestamp(&executed_at).unwrap_or_else(Utc::now),
+                finished_at: row
+                    .get::<Option<String>, _>("finished_at")
+                    .and_then(|finished_at| parse_timestamp(&finished_at)),
+            });
+        }
+        
+        Ok(results)
+    }
+    
+    /// 将警报归入已有事件或新建事件，返回事件 ID；`incidents` 为未解决的事件，会同步更新。
+    /// 事件、警报的事件 ID 和时间线在同一事务中写入
+    async fn attach_to_incident(
+        &self,
+        config: &AlertConfiguration,
+        alert: &AlertHistory,
+        grouping: &IncidentGrouping,
+        incidents: &mut Vec<Incident>,
+    ) -> Result<String, sqlx::Error> {
+        let target = config.target.as_deref();
+        let selected = grouping
+            .select(incidents, target, alert.triggered_at)
+            .map(|incident| incident.id.clone());
+        
+        let mut tx = self.pool.begin().await?;
+        let (incident, event) = match selected.and_then(|id| incidents.iter_mut().find(|incident| incident.id == id)) {
+            Some(incident) => {
+                let first_for_rule = incident.add_alert(alert, target);
+                sqlx::query("UPDATE incidents SET severity = ?, updated_at = ?, last_alert_at = ? WHERE id = ?")
+                    .bind(incident.severity.as_str())
+                    .bind(incident.updated_at.to_rfc3339())
+                    .bind(incident.last_alert_at.to_rfc3339())
+                    .bind(&incident.id)
+                    .execute(&mut *tx)
+                    .await?;
+                (incident.clone(), first_for_rule.then_some(IncidentEventKind::AlertAdded))
+            }
+            None => {
+                let incident = Incident::open(template::subject(config), alert, target);
+                sqlx::query(
+                    r#"
+                    INSERT INTO incidents (id, title, status, severity, opened_at, updated_at, last_alert_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(&incident.id)
+                .bind(&incident.title)
+                .bind(incident.status.as_str())
+                .bind(incident.severity.as_str())
+                .bind(incident.opened_at.to_rfc3339())
+                .bind(incident.updated_at.to_rfc3339())
+                .bind(incident.last_alert_at.to_rfc3339())
+                .execute(&mut *tx)
+                .await?;
+                incidents.push(incident.clone());
+                (incident, Some(IncidentEventKind::Opened))
+            }
+        };
+        
+        sqlx::query("UPDATE alert_history SET incident_id = ? WHERE id = ?")
+            .bind(&incident.id)
+            .bind(&alert.id)
+            .execute(&mut *tx)
+            .await?;
+        
+        // 同一规则的重复触发不写入时间线
+        if let Some(kind) = event {
+            let message = format!("{}: {}", alert.alert_id, alert.message);
+            Self::insert_incident_event(&mut tx, &incident.id, kind, &message, None, Some(&alert.id), alert.triggered_at)
+                .await?;
+        }
+        
+        tx.commit().await?;
+        Ok(incident.id)
+    }
+    
+    /// 所有警报都已恢复的事件标记为已解决
+    async fn resolve_finished_incidents(&self, now: DateTime<Utc>) -> Result<(), sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT i.id FROM incidents i
+            WHERE i.status != 'resolved' AND NOT EXISTS (
+                SELECT 1 FROM alert_history h WHERE h.incident_id = i.id AND h.resolved_at IS NULL
+            )
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        for row in rows {
+            let id: String = row.get("id");
+            self.set_incident_resolved(&id, now).await?;
+            let mut conn = self.pool.acquire().await?;
+            Self::insert_incident_event(&mut conn, &id, IncidentEventKind::Resolved, "All alerts resolved", None, None, now)
+                .await?;
+        }
+        
+        Ok(())
+    }
+    
+    async fn set_incident_resolved(&self, id: &str, now: DateTime<Utc>) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query("UPDATE incidents SET status = ?, resolved_at = ?, updated_at = ? WHERE id = ?")
+            .bind(IncidentStatus::Resolved.as_str())
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+    
+    async fn insert_incident_event(
+        conn: &mut SqliteConnection,
+        incident_id: &str,
+        kind: IncidentEventKind,
+        message: &str,
+        author: Option<&str>,
+        alert_history_id: Option<&str>,
+        created_at: DateTime<Utc>,
+    ) -> Result<IncidentEvent, sqlx::Error> {
+        let event = IncidentEvent {
+            id: Uuid::new_v4().to_string(),
+            incident_id: incident_id.to_string(),
+            kind,
+            message: message.to_string(),
+            author: author.map(str::to_string),
+            alert_history_id: alert_history_id.map(str::to_string),
+            created_at,
+        };
+        
+        sqlx::query(
+            r#"
+            INSERT INTO incident_events (id, incident_id, kind, message, author, alert_history_id, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&event.id)
+        .bind(&event.incident_id)
+        .bind(event.kind.as_str())
+        .bind(&event.message)
+        .bind(&event.author)
+        .bind(&event.alert_history_id)
+        .bind(event.created_at.to_rfc3339())
+        .execute(conn)
+        .await?;
+        
+        Ok(event)
+    }
+    
+    /// 获取事件，按最近一条警报的时间倒序排列；`statuses` 为空时返回所有状态
+    pub async fn get_incidents(
+        &self,
+        statuses: &[IncidentStatus],
+        limit: Option<u32>,
+    ) -> Result<Vec<Incident>, sqlx::Error> {
+        self.load_incidents(statuses, None, limit).await
+    }
+    
+    async fn load_incidents(
+        &self,
+        statuses: &[IncidentStatus],
+        id: Option<&str>,
+        limit: Option<u32>,
+    ) -> Result<Vec<Incident>, sqlx::Error> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT id, title, status, severity, opened_at, updated_at, last_alert_at, acknowledged_at, acknowledged_by, resolved_at
+            FROM incidents
+            WHERE 1 = 1
+            "#,
+        );
+        if let Some(id) = id {
+            query.push(" AND id = ").push_bind(id.to_string());
+        }
+        if !statuses.is_empty() {
+            query.push(" AND status IN (");
+            let mut names = query.separated(", ");
+            for status in statuses {
+                names.push_bind(status.as_str());
+            }
+            query.push(")");
+        }
+        query.push(" ORDER BY last_alert_at DESC");
+        if let Some(limit) = limit {
+            query.push(" LIMIT ").push_bind(limit);
+        }
+        
+        let rows = query.build().fetch_all(&self.pool).await?;
+        
+        let mut incidents = Vec::new();
+        let mut positions = HashMap::new();
+        for row in rows {
+            let status: String = row.get("status");
+            let severity: String = row.get("severity");
+            let timestamp = |column: &str| parse_timestamp(&row.get::<String, _>(column)).unwrap_or_else(Utc::now);
+            positions.insert(row.get::<String, _>("id"), incidents.len());
+            incidents.push(Incident {
+                id: row.get("id"),
+                title: row.get("title"),
+                // 未知状态来自更新的版本，按未解决显示
+                status: IncidentStatus::from_name(&status).unwrap_or(IncidentStatus::Open),
+                severity: severity.parse().unwrap_or(AlertSeverity::Low),
+                opened_at: timestamp("opened_at"),
+                updated_at: timestamp("updated_at"),
+                last_alert_at: timestamp("last_alert_at"),
+                acknowledged_at: row.get::<Option<String>, _>("acknowledged_at").and_then(|ts| parse_timestamp(&ts)),
+                acknowledged_by: row.get("acknowledged_by"),
+                resolved_at: row.get::<Option<String>, _>("resolved_at").and_then(|ts| parse_timestamp(&ts)),
+                alert_count: 0,
+                rule_ids: Vec::new(),
+                targets: Vec::new(),
+            });
+        }
+        if incidents.is_empty() {
+            return Ok(incidents);
+        }
+        
+        // 汇总成员警报的规则和目标
+        let mut members = QueryBuilder::<Sqlite>::new(
+            r#"
+            SELECT h.incident_id, h.alert_id, c.target, COUNT(*) AS alert_count, MIN(h.triggered_at) AS first_triggered_at
+            FROM alert_history h
+            LEFT JOIN alert_configurations c ON c.id = h.alert_id
+            WHERE h.incident_id IN (
+            "#,
+        );
+        let mut ids = members.separated(", ");
+        for incident in &incidents {
+            ids.push_bind(incident.id.clone());
+        }
+        members.push(") GROUP BY h.incident_id, h.alert_id ORDER BY first_triggered_at ASC");
+        
+        for row in members.build().fetch_all(&self.pool).await? {
+            let Some(index) = positions.get(&row.get::<String, _>("incident_id")) else {
+                continue;
+            };
+            let incident = &mut incidents[*index];
+            incident.alert_count += row.get::<i64, _>("alert_count") as u32;
+            incident.rule_ids.push(row.get("alert_id"));
+            if let Some(target) = row.get::<Option<String>, _>("target") {
+                if !incident.targets.contains(&target) {
+                    incident.targets.push(target);
+                }
+            }
+        }
+        
+        Ok(incidents)
+    }
+    
+    /// 获取事件详情，包括成员警报和时间线
+    pub async fn get_incident(&self, id: &str) -> Result<Option<IncidentDetail>, sqlx::Error> {
+        let incidents = self.load_incidents(&[], Some(id), None).await?;
+        let Some(incident) = incidents.into_iter().next() else {
+            return Ok(None);
+        };
+        
+        let filter = AlertHistoryFilter {
+            incident_ids: vec![id.to_string()],
+            ..Default::default()
+        };
+        let mut alerts = self.get_alert_history(&filter, None, 0).await?;
+        alerts.reverse();
+        
+        let rows = sqlx::query(
+            r#"
+            SELECT id, incident_id, kind, message, author, alert_history_id, created_at
+            FROM incident_events
+            WHERE incident_id = ?
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut timeline = Vec::new();
+        for row in rows {
+            let kind: String = row.get("kind");
+            let created_at: String = row.get("created_at");
+            timeline.push(IncidentEvent {
+                id: row.get("id"),
+                incident_id: row.get("incident_id"),
+                kind: IncidentEventKind::from_name(&kind).unwrap_or(IncidentEventKind::Note),
+                message: row.get("message"),
+                author: row.get("author"),
+                alert_history_id: row.get("alert_history_id"),
+                created_at: parse_timestamp(&created_at).unwrap_or_else(Utc::now),
+            });
+        }
+        
+        Ok(Some(IncidentDetail { incident, alerts, timeline }))
+    }
+    
+    /// 确认事件及其所有尚未确认的警报，返回确认的警报数量
+    pub async fn acknowledge_incident(&self, id: &str, acknowledged_by: &str) -> Result<u64, sqlx::Error> {
+        let now = Utc::now();
+        // 已解决的事件保持已解决状态，只确认其中的警报
+        let result = sqlx::query(
+            r#"
+            UPDATE incidents
+            SET status = CASE WHEN status = 'resolved' THEN status ELSE ? END,
+                acknowledged_at = ?, acknowledged_by = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(IncidentStatus::Acknowledged.as_str())
+        .bind(now.to_rfc3339())
+        .bind(acknowledged_by)
+        .bind(now.to_rfc3339())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        let filter = AlertHistoryFilter {
+            incident_ids: vec![id.to_string()],
+            ..Default::default()
+        };
+        let acknowledged = self.acknowledge_alerts(&filter, acknowledged_by).await?;
+        
+        let message = format!("Acknowledged {} alert(s)", acknowledged);
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_incident_event(&mut conn, id, IncidentEventKind::Acknowledged, &message, Some(acknowledged_by), None, now)
+            .await?;
+        
+        Ok(acknowledged)
+    }
+    
+    /// 手动解决事件，成员警报的恢复状态不变
+    pub async fn resolve_incident(&self, id: &str, resolved_by: &str) -> Result<IncidentEvent, sqlx::Error> {
+        let now = Utc::now();
+        if self.set_incident_resolved(id, now).await? == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_incident_event(&mut conn, id, IncidentEventKind::Resolved, "Resolved manually", Some(resolved_by), None, now)
+            .await
+    }
+    
+    /// 为事件添加备注
+    pub async fn add_incident_note(&self, id: &str, author: &str, note: &str) -> Result<IncidentEvent, sqlx::Error> {
+        let now = Utc::now();
+        let result = sqlx::query("UPDATE incidents SET updated_at = ? WHERE id = ?")
+            .bind(now.to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        let mut conn = self.pool.acquire().await?;
+        Self::insert_incident_event(&mut conn, id, IncidentEventKind::Note, note, Some(author), None, now).await
+    }
+    
+    /// 按通知策略决定警报在各通知方式上的处理方式，并汇总到期的摘要
+    pub async fn plan_notifications(
+        &self,
+        alerts: &[AlertHistory],
+        policy: &NotificationPolicy,
+        locale: MessageLocale,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationPlan, sqlx::Error> {
+        let configurations: HashMap<String, AlertConfiguration> = self
+            .get_alert_configurations()
+            .await?
+            .into_iter()
+            .map(|config| (config.id.clone(), config))
+            .collect();
+        
+        let mut plan = self.plan_deliveries(alerts, &configurations, policy, false, now).await?;
+        
+        let lower_is_worse: HashMap<String, bool> = configurations
+            .iter()
+            .map(|(id, config)| (id.clone(), notification::lower_is_worse(config)))
+            .collect();
+        plan.digests = self.flush_digests(policy, &lower_is_worse, locale, now).await?;
+        
+        Ok(plan)
+    }
+    
+    /// 按与真实警报相同的通知策略处理规则的测试警报，立即发送的通知标记为测试；
+    /// 测试警报不写入警报历史，放入摘要或超出频率限制时不记录，不会出现在摘要中
+    pub async fn plan_test_notification(
+        &self,
+        alert: &AlertHistory,
+        config: &AlertConfiguration,
+        policy: &NotificationPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationPlan, sqlx::Error> {
+        let configurations = HashMap::from([(config.id.clone(), config.clone())]);
+        self.plan_deliveries(std::slice::from_ref(alert), &configurations, policy, true, now).await
+    }
+    
+    async fn plan_deliveries(
+        &self,
+        alerts: &[AlertHistory],
+        configurations: &HashMap<String, AlertConfiguration>,
+        policy: &NotificationPolicy,
+        test: bool,
+        now: DateTime<Utc>,
+    ) -> Result<NotificationPlan, sqlx::Error> {
+        let rows = sqlx::query("SELECT channel, created_at FROM notification_log WHERE disposition = ? AND created_at >= ?")
+            .bind(NotificationDisposition::Sent.as_str())
+            .bind((now - policy.lookback()).to_rfc3339())
+            .fetch_all(&self.pool)
+            .await?;
+        let sent = rows
+            .iter()
+            .filter_map(|row| {
+                let created_at: String = row.get("created_at");
+                Some((parse_timestamp(&created_at)?, row.get::<String, _>("channel")))
+            })
+            .collect();
+        let mut limiter = NotificationLimiter::new(policy, sent);
+        
+        let mut plan = NotificationPlan::default();
+        for alert in alerts {
+            // 规则已删除的警报不再通知
+            let Some(config) = configurations.get(&alert.alert_id) else {
+                continue;
+            };
+            let mut channels = Vec::new();
+            for channel in &config.notification_methods {
+                let disposition = limiter.decide(channel, now);
+                match disposition {
+                    NotificationDisposition::Sent => channels.push(channel.clone()),
+                    NotificationDisposition::Batched => plan.batched += 1,
+                    NotificationDisposition::Overflow => plan.overflowed += 1,
+                }
+                // 测试通知只记录实际发送的，使其计入之后的频率限制
+                if test && disposition != NotificationDisposition::Sent {
+                    continue;
+                }
+                sqlx::query(
+                    r#"
+                    INSERT INTO notification_log (id, alert_history_id, rule_id, channel, disposition, value, severity, created_at)
+                    VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+                    "#,
+                )
+                .bind(Uuid::new_v4().to_string())
+                .bind(&alert.id)
+                .bind(&alert.alert_id)
+                .bind(channel)
+                .bind(disposition.as_str())
+                .bind(alert.value)
+                .bind(alert.severity.map(|severity| severity.as_str()))
+                .bind(now.to_rfc3339())
+                .execute(&self.pool)
+                .await?;
+            }
+            if !channels.is_empty() {
+                plan.deliveries.push(NotificationDelivery {
+                    alert: alert.clone(),
+                    channels,
+                    test,
+                });
+            }
+        }
+        
+        Ok(plan)
+    }
+    
+    /// 将最早一条已超过汇总间隔的通知方式的待发送通知汇总为摘要
+    async fn flush_digests(
+        &self,
+        policy: &NotificationPolicy,
+        lower_is_worse: &HashMap<String, bool>,
+        locale: MessageLocale,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<NotificationDigest>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, alert_history_id, rule_id, channel, disposition, value, severity, created_at
+            FROM notification_log
+            WHERE digest_id IS NULL AND disposition != ?
+            ORDER BY created_at ASC
+            "#,
+        )
+        .bind(NotificationDisposition::Sent.as_str())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut pending: BTreeMap<String, Vec<(String, PendingNotification)>> = BTreeMap::new();
+        for row in rows {
+            let disposition: String = row.get("disposition");
+            let created_at: String = row.get("created_at");
+            let channel: String = row.get("channel");
+            let entry = PendingNotification {
+                alert_history_id: row.get("alert_history_id"),
+                rule_id: row.get("rule_id"),
+                channel: channel.clone(),
+                disposition: NotificationDisposition::from_name(&disposition).unwrap_or(NotificationDisposition::Overflow),
+                value: row.get("value"),
+                severity: row.get::<Option<String>, _>("severity").and_then(|s| s.parse().ok()),
+                created_at: parse_timestamp(&created_at).unwrap_or(now),
+            };
+            pending.entry(channel).or_default().push((row.get("id"), entry));
+        }
+        
+        let mut digests = Vec::new();
+        for (channel, entries) in pending {
+            let oldest = entries.iter().map(|(_, entry)| entry.created_at).min().unwrap_or(now);
+            if now - oldest < policy.flush_interval(&channel) {
+                continue;
+            }
+            let (ids, entries): (Vec<String>, Vec<PendingNotification>) = entries.into_iter().unzip();
+            let Some(digest) = NotificationDigest::build(&channel, &entries, lower_is_worse, locale, now) else {
+                continue;
+            };
+            
+            sqlx::query(
+                r#"
+                INSERT INTO notification_digests
+                    (id, channel, created_at, period_start, period_end, alert_count, overflow_count, rules, alert_ids, message)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&digest.id)
+            .bind(&digest.channel)
+            .bind(digest.created_at.to_rfc3339())
+            .bind(digest.period_start.to_rfc3339())
+            .bind(digest.period_end.to_rfc3339())
+            .bind(digest.alert_count as i64)
+            .bind(digest.overflow_count as i64)
+            .bind(serde_json::to_string(&digest.rules).unwrap_or_else(|_| "[]".to_string()))
+            .bind(serde_json::to_string(&digest.alert_ids).unwrap_or_else(|_| "[]".to_string()))
+            .bind(&digest.message)
+            .execute(&self.pool)
+            .await?;
+            
+            let mut update = QueryBuilder::<Sqlite>::new("UPDATE notification_log SET digest_id = ");
+            update.push_bind(digest.id.clone()).push(" WHERE id IN (");
+            let mut separated = update.separated(", ");
+            for id in ids {
+                separated.push_bind(id);
+            }
+            update.push(")");
+            update.build().execute(&self.pool).await?;
+            
+            digests.push(digest);
+        }
+        
+        Ok(digests)
+    }
+    
+    /// 获取最近的通知摘要，按生成时间倒序排列
+    pub async fn get_notification_digests(&self, limit: u32) -> Result<Vec<NotificationDigest>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, channel, created_at, period_start, period_end, alert_count, overflow_count, rules, alert_ids, message
+            FROM notification_digests
+            ORDER BY created_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut digests = Vec::new();
+        for row in rows {
+            let timestamp = |column: &str| parse_timestamp(&row.get::<String, _>(column)).unwrap_or_else(Utc::now);
+            digests.push(NotificationDigest {
+                id: row.get("id"),
+                channel: row.get("channel"),
+                created_at: timestamp("created_at"),
+                period_start: timestamp("period_start"),
+                period_end: timestamp("period_end"),
+                alert_count: row.get::<i64, _>("alert_count") as u32,
+                overflow_count: row.get::<i64, _>("overflow_count") as u32,
+                rules: serde_json::from_str(&row.get::<String, _>("rules")).unwrap_or_default(),
+                alert_ids: serde_json::from_str(&row.get::<String, _>("alert_ids")).unwrap_or_default(),
+                message: row.get("message"),
+            });
+        }
+        
+        Ok(digests)
+    }
+    
+    /// 保存一条进程操作审计记录
+    pub async fn record_process_audit(&self, entry: &ProcessAuditEntry) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+            INSERT INTO process_audit_log (id, pid, process_name, action, previous_value, requested_value, success, error, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&entry.id)
+        .bind(entry.pid as i64)
+        .bind(&entry.process_name)
+        .bind(entry.action.as_str())
+        .bind(&entry.previous_value)
+        .bind(&entry.requested_value)
+        .bind(entry.success)
+        .bind(&entry.error)
+        .bind(entry.created_at.to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(())
+    }
+    
+    /// 获取最近的进程操作审计记录，可按 PID 过滤
+    pub async fn get_process_audit_log(&self, pid: Option<u32>, limit: u32) -> Result<Vec<ProcessAuditEntry>, sqlx::Error> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT id, pid, process_name, action, previous_value, requested_value, success, error, created_at \
+             FROM process_audit_log WHERE 1 = 1",
+        );
+        if let Some(pid) = pid {
+            query.push(" AND pid = ").push_bind(pid as i64);
+        }
+        query.push(" ORDER BY created_at DESC LIMIT ").push_bind(limit);
+        let rows = query.build().fetch_all(&self.pool).await?;
+        
+        let mut entries = Vec::new();
+        for row in rows {
+            let action: String = row.get("action");
+            // 未知操作来自更新的版本，跳过
+            let Some(action) = ProcessAuditAction::from_name(&action) else {
+                continue;
+            };
+            let created_at: String = row.get("created_at");
+            entries.push(ProcessAuditEntry {
+                id: row.get("id"),
+                pid: row.get::<i64, _>("pid") as u32,
+                process_name: row.get("process_name"),
+                action,
+                previous_value: row.get("previous_value"),
+                requested_value: row.get("requested_value"),
+                success: row.get("success"),
+                error: row.get("error"),
+                created_at: parse_timestamp(&created_at).unwrap_or_else(Utc::now),
+            });
+        }
+        
+        Ok(entries)
+    }
+    
+    /// 保存与一条历史系统数据同时采集的 cgroup 用量
+    pub async fn store_cgroup_samples(&self, historical_data_id: &str, samples: &[CgroupSample]) -> Result<(), sqlx::Error> {
+        let to_i64 = |value: Option<u64>| value.map(|value| value as i64);
+        let mut tx = self.pool.begin().await?;
+        for sample in samples {
+            sqlx::query(
+                r#"
+                INSERT INTO cgroup_stats_data (
+                    id, historical_data_id, timestamp, path, memory_current, memory_max, cpu_usage_usec,
+                    cpu_nr_periods, cpu_nr_throttled, cpu_throttled_usec, io_read_bytes, io_written_bytes, pids_current
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(Uuid::new_v4().to_string())
+            .bind(historical_data_id)
+            .bind(sample.timestamp.to_rfc3339())
+            .bind(&sample.path)
+            .bind(to_i64(sample.memory_current_bytes))
+            .bind(to_i64(sample.memory_max_bytes))
+            .bind(to_i64(sample.cpu_usage_usec))
+            .bind(to_i64(sample.cpu_nr_periods))
+            .bind(to_i64(sample.cpu_nr_throttled))
+            .bind(to_i64(sample.cpu_throttled_usec))
+            .bind(sample.io_read_bytes as i64)
+            .bind(sample.io_written_bytes as i64)
+            .bind(to_i64(sample.pids_current))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// 获取一个 cgroup 在时间范围内的用量序列，按时间排列
+    pub async fn fetch_cgroup_history(
+        &self,
+        path: &str,
+        start_time: DateTime<Utc>,
+        end_time: DateTime<Utc>,
+    ) -> Result<Vec<CgroupSample>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT timestamp, path, memory_current, memory_max, cpu_usage_usec, cpu_nr_periods, cpu_nr_throttled,
+                   cpu_throttled_usec, io_read_bytes, io_written_bytes, pids_current
+            FROM cgroup_stats_data
+            WHERE path = ? AND timestamp BETWEEN ? AND ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(path)
+        .bind(start_time.to_rfc3339())
+        .bind(end_time.to_rfc3339())
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let column = |row: &sqlx::sqlite::SqliteRow, name: &str| row.get::<Option<i64>, _>(name).map(|value| value as u64);
+        let mut samples = Vec::new();
+        for row in rows {
+            let timestamp: String = row.get("timestamp");
+            samples.push(CgroupSample {
+                timestamp: parse_timestamp(&timestamp).unwrap_or_else(Utc::now),
+                path: row.get("path"),
+                memory_current_bytes: column(&row, "memory_current"),
+                memory_max_bytes: column(&row, "memory_max"),
+                cpu_usage_usec: column(&row, "cpu_usage_usec"),
+                cpu_nr_periods: column(&row, "cpu_nr_periods"),
+                cpu_nr_throttled: column(&row, "cpu_nr_throttled"),
+                cpu_throttled_usec: column(&row, "cpu_throttled_usec"),
+                io_read_bytes: row.get::<i64, _>("io_read_bytes") as u64,
+                io_written_bytes: row.get::<i64, _>("io_written_bytes") as u64,
+                pids_current: column(&row, "pids_current"),
+            });
+        }
+        
+        Ok(samples)
+    }
+    
+    /// 获取所有进程守护规则，无法解析的规则记录日志后跳过
+    pub async fn get_supervisor_rules(&self) -> Result<Vec<SupervisorRule>, sqlx::Error> {
+        let (rules, invalid) = self.load_supervisor_rules().await?;
+        for rule in invalid {
+            eprintln!("Skipped unreadable supervisor rule {}: {}", rule.id, rule.message);
+        }
+        Ok(rules)
+    }
+    
+    /// 获取所有进程守护规则，同时返回无法解析的规则
+    pub async fn load_supervisor_rules(&self) -> Result<(Vec<SupervisorRule>, Vec<InvalidSupervisorRule>), sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, name, target, enabled, initial_backoff_secs, max_backoff_secs, max_restarts FROM supervisor_rules ORDER BY name"
+        )
+        .fetch_all(&self.pool)
+        .await?;
+        
+        let mut rules = Vec::new();
+        let mut invalid = Vec::new();
+        for row in rows {
+            let target_str: String = row.get("target");
+            let target = match serde_json::from_str::<SupervisorTarget>(&target_str) {
+                Ok(target) => target,
+                Err(e) => {
+                    invalid.push(InvalidSupervisorRule {
+                        id: row.get("id"),
+                        name: row.get("name"),
+                        message: format!("Invalid target: {}", e),
+                    });
+                    continue;
+                }
+            };
+            rules.push(SupervisorRule {
+                id: row.get("id"),
+                name: row.get("name"),
+                target,
+                enabled: row.get("enabled"),
+                initial_backoff_secs: row.get::<i64, _>("initial_backoff_secs") as u64,
+                max_backoff_secs: row.get::<i64, _>("max_backoff_secs") as u64,
+                max_restarts: row.get::<Option<i64>, _>("max_restarts").map(|value| value as u32),
+            });
+        }
+        
+        Ok((rules, invalid))
+    }
+    
+    /// 添加进程守护规则
+    pub async fn add_supervisor_rule(&self, rule: &SupervisorRule) -> Result<String, sqlx::Error> {
+        let id = Uuid::new_v4().to_string();
+        let target_json = serde_json::to_string(&rule.target).unwrap_or_else(|_| "{}".to_string());
+        
+        sqlx::query(
+            r#"
+            INSERT INTO supervisor_rules (id, name, target, enabled, initial_backoff_secs, max_backoff_secs, max_restarts)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&id)
+        .bind(&rule.name)
+        .bind(&target_json)
+        .bind(rule.enabled)
+        .bind(rule.initial_backoff_secs as i64)
+        .bind(rule.max_backoff_secs as i64)
+        .bind(rule.max_restarts.map(i64::from))
+        .execute(&self.pool)
+        .await?;
+        
+        Ok(id)
+    }
+    
+    /// 更新进程守护规则
+    pub async fn update_supervisor_rule(&self, id: &str, rule: &SupervisorRule) -> Result<(), sqlx::Error> {
+        let target_json = serde_json::to_string(&rule.target).unwrap_or_else(|_| "{}".to_string());
+        
+        let result = sqlx::query(
+            r#"
+            UPDATE supervisor_rules
+            SET name = ?, target = ?, enabled = ?, initial_backoff_secs = ?, max_backoff_secs = ?, max_restarts = ?, updated_at = CURRENT_TIMESTAMP
+            WHERE id = ?
+            "#,
+        )
+        .bind(&rule.name)
+        .bind(&target_json)
+        .bind(rule.enabled)
+        .bind(rule.initial_backoff_secs as i64)
+        .bind(rule.max_backoff_secs as i64)
+        .bind(rule.max_restarts.map(i64::from))
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+        
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        
+        Ok(())
+    }
+    
+    /// 删除进程守护规则，已记录的事件保留
+    pub async fn delete_supervisor_rule(&self, id: &str) -> Result<(), sqlx::Error> {
+        sqlx::query("DELETE FROM supervisor_rules WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        
+        Ok(())
+    }
+    
+    /// 保存进程守护事件
+    pub async fn record_supervisor_events(&self, events: &[SupervisorEvent]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        for event in events {
+            sqlx::query(
+                r#"
+                INSERT INTO supervisor_events (id, rule_id, rule_name, kind, pid, exit_code, signal, message, restart_count, created_at)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(&event.id)
+            .bind(&event.rule_id)
+            .bind(&event.rule_name)
+            .bind(event.kind.as_str())
+            .bind(event.pid.map(i64::from))
+            .bind(event.exit_code)
+            .bind(event.signal)
+            .bind(&event.message)
+            .bind(i64::from(event.restart_count))
+            .bind(event.created_at.to_rfc3339())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+    
+    /// 获取最近的进程守护事件，可按规则过滤
+    pub async fn get_supervisor_events(&self, rule_id: Option<&str>, limit: u32) -> Result<Vec<SupervisorEvent>, sqlx::Error> {
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT id, rule_id, rule_name, kind, pid, exit_code, signal, message, restart_count, created_at \
+             FROM supervisor_events WHERE 1 = 1",
+        );
+        if let Some(rule_id) = rule_id {
+            query.push(" AND rule_id = ").push_bind(rule_id);
+        }
+        // 同一次检查产生的事件时间相同，按写入顺序排列
+        query.push(" ORDER BY created_at DESC, rowid DESC LIMIT ").push_bind(limit);
+        let rows = query.build().fetch_all(&self.pool).await?;
+        
+        let mut events = Vec::new();
+        for row in rows {
+            let kind: String = row.get("kind");
+            // 未知类型来自更新的版本，跳过
+            let Some(kind) = SupervisorEventKind::from_name(&kind) else {
+                continue;
+            };
+            let created_at: String = row.get("created_at");
+            events.push(SupervisorEvent {
+                id: row.get("id"),
+                rule_id: row.get("rule_id"),
+                rule_name: row.get("rule_name"),
+                kind,
+                pid: row.get::<Option<i64>, _>("pid").map(|pid| pid as u32),
+                exit_code: row.get("exit_code"),
+                signal: row.get("signal"),
+                message: row.get("message"),
+                restart_count: row.get::<i64, _>("restart_count") as u32,
+                created_at: parse_timestamp(&created_at).unwrap_or_else(Utc::now),
+            });
+        }
+        
+        Ok(events)
+    }
+}
+
+// 数据库统计信息
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseStats {
+    pub total_records: i64,
+    pub oldest_timestamp: Option<DateTime<Utc>>,
+    pub newest_timestamp: Option<DateTime<Utc>>,
+}
+
+/// 解析以 JSON 文本存储的可选列
+fn parse_json_column<T: serde::de::DeserializeOwned>(raw: Option<String>) -> Result<Option<T>, serde_json::Error> {
+    raw.map(|json| serde_json::from_str(&json)).transpose()
+}
+
+/// 解析数据库中的时间，兼容旧版本由 `CURRENT_TIMESTAMP` 写入的 UTC 时间
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").map(|dt| dt.and_utc()))
+        .ok()
+}
+
+/// 为警报历史查询追加过滤条件，查询需以 `alert_history h LEFT JOIN alert_configurations c` 为数据源
+fn push_history_filter(query: &mut QueryBuilder<'_, Sqlite>, filter: &AlertHistoryFilter) {
+    query.push(" WHERE 1 = 1");
+    
+    if !filter.rule_ids.is_empty() {
+        query.push(" AND h.alert_id IN (");
+        let mut ids = query.separated(", ");
+        for id in &filter.rule_ids {
+            ids.push_bind(id.clone());
+        }
+        query.push(")");
+    }
+    if !filter.severities.is_empty() {
+        query.push(" AND COALESCE(h.severity, c.severity) IN (");
+        let mut severities = query.separated(", ");
+        for severity in &filter.severities {
+            severities.push_bind(severity.as_str());
+        }
+        query.push(")");
+    }
+    if !filter.targets.is_empty() {
+        query.push(" AND c.target IN (");
+        let mut targets = query.separated(", ");
+        for target in &filter.targets {
+            targets.push_bind(target.clone());
+        }
+        query.push(")");
+    }
+    if let Some(start_time) = filter.start_time {
+        query.push(" AND h.triggered_at >= ").push_bind(start_time.to_rfc3339());
+    }
+    if let Some(end_time) = filter.end_time {
+        query.push(" AND h.triggered_at <= ").push_bind(end_time.to_rfc3339());
+    }
+    if let Some(acknowledged) = filter.acknowledged {
+        query.push(" AND h.acknowledged = ").push_bind(acknowledged);
+    }
+    if let Some(suppressed) = filter.suppressed {
+        query.push(" AND h.suppressed = ").push_bind(suppressed);
+    }
+    if !filter.incident_ids.is_empty() {
+        query.push(" AND h.incident_id IN (");
+        let mut ids = query.separated(", ");
+        for id in &filter.incident_ids {
+            ids.push_bind(id.clone());
+        }
+        query.push(")");
+    }
+}
+
+/// 按规则的消息模板生成警报消息，模板无法渲染时（例如旧版本写入的无效模板）退回默认模板
+fn render_alert_message(config: &AlertConfiguration, context: &AlertMessageContext, locale: MessageLocale) -> String {
+    config
+        .message_template
+        .as_deref()
+        .and_then(|custom| template::render_template(custom, context, locale).ok())
+        .or_else(|| template::render_template(locale.default_template(), context, locale).ok())
+        .unwrap_or_else(|| context.details.clone())
+}
@@ -6,7 +6,9 @@ use tauri::{State, Manager};
 use std::process::Command;
 use cpal::traits::{HostTrait, DeviceTrait};
 mod database;
-use database::{DatabaseManager, HistoricalSystemData, DiskUsageData, NetworkTrafficData};
+mod alerting;
+mod process;
+use process::fds;
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
@@ -933,7 +935,77 @@ fn extract_unix_traceroute_hop_info(line: &str) -> (String, Option<String>, Opti
 }
 
 // 重新导出数据库模块中的类型
-pub use database::{AlertConfiguration, AlertHistory};
+pub use database::{AlertConfiguration, AlertConfigurationIssue, AlertHistory, AlertIssueAction, AlertMigrationReport, DatabaseManager, HistoricalSystemData, DiskUsageData, NetworkTrafficData};
+pub use alerting::{rules_use_metric, validate_configuration, AlertComparison, AlertMetric, AlertSeverity, MetricSnapshot, RuleEvaluation};
+pub use alerting::anomaly::{AnomalyDetection, AnomalyDirection, AnomalyMethod, AnomalyPoint, AnomalyVerdict, Baseline, MAX_ANOMALY_LOOKBACK_HOURS};
+pub use alerting::history::{AlertHistoryFilter, AlertRuleStatistics, AlertStatistics};
+pub use alerting::notification::{lower_is_worse, DigestRuleSummary, DigestSettings, NotificationDelivery, NotificationDigest, NotificationDisposition, NotificationLimiter, NotificationPlan, NotificationPolicy, PendingNotification, RateLimit};
+pub use alerting::incident::{Incident, IncidentDetail, IncidentEvent, IncidentEventKind, IncidentGrouping, IncidentStatus};
+pub use alerting::forecast::{ForecastCondition, ResourceForecast, TrendFit, MAX_FORECAST_HOURS, MAX_FORECAST_LOOKBACK_HOURS};
+pub use alerting::prometheus::{convert_prometheus_rules, PrometheusConversion, UnmappedPrometheusRule};
+pub use alerting::transfer::{export_rule_file, parse_rule_file, AlertImportIssue, AlertImportReport, AlertRuleFile, ImportConflictMode, RenamedAlertRule, RuleFileFormat};
+pub use alerting::remediation::{execute_action, script_environment, RemediationAction, RemediationPolicy, RemediationResult, RemediationStatus};
+pub use alerting::silence::{AlertSilence, SilenceMatcher, SilenceSchedule};
+pub use alerting::simulation::{backtest_rule, backtest_warmup, test_fire, AlertBacktestReport, AlertFiringEpisode, TestAlert};
+pub use alerting::template::{render_template, validate_template, AlertMessageContext, MessageEnvironment, MessageLocale, ProcessSample, TEMPLATE_PLACEHOLDERS};
+pub use alerting::escalation::{AlertEscalation, EscalationPolicy, EscalationStep};
+pub use alerting::expression::{AlertExpression, EvaluationContext, ExpressionError, ExpressionOutcome};
+pub use process::ProcessEntry;
+pub use process::audit::{ProcessAuditAction, ProcessAuditEntry};
+pub use process::cgroup::{
+    aggregate_cgroups, cgroup_usage, container_from_path, container_matches, parse_proc_cgroup, read_cgroup, systemd_unit,
+    unit_matches, CgroupGrouping, CgroupInfo, CgroupUsage, ContainerRuntime,
+};
+pub use process::cgroupfs::{
+    cgroup_root, collect_cgroups, normalize_cgroup_path, parse_cpu_stat, parse_io_stat, parse_limit, read_cgroup_node,
+    read_cgroup_stats, CgroupCpuStat, CgroupIoDevice, CgroupNode, CgroupSample, CgroupStats, CGROUP_HISTORY_DEPTH,
+};
+pub use process::environ::{parse_environ, read_environment, EnvMasking, EnvVariable, ProcessEnvironment, MASKED_VALUE};
+pub use process::fds::{
+    busiest_process, classify_fd_target, fd_matches_path, find_fd_holders, list_fds, FdHolder, FdKind, FdUsage,
+    FileHolders, OpenFd, ProcessFds,
+};
+pub use process::io::{io_top, sort_by_io, ProcessIo};
+pub use process::limits::{
+    format_limit_value, parse_limit_value, parse_limits, raise_soft_limit, read_limits, validate_soft_limit_raise, ProcessLimits,
+    ResourceLimit, ResourceLimitKind,
+};
+pub use process::priority::{
+    format_cpu_list, get_affinity, parse_cpu_list, read_priority, set_affinity, set_nice, CpuAffinity, ProcessPriority,
+    SchedulingPolicy, MAX_NICE, MIN_NICE,
+};
+pub use process::procfs::{clock_ticks_per_second, ProcStat};
+pub use process::signal::{
+    process_group, signal_process, signal_targets, terminate_gracefully, ProcessSignal, SignalOutcome, SignalScope,
+    SignalStatus, MAX_GRACE_PERIOD_SECS,
+};
+pub use process::smaps::{
+    group_mappings, parse_smaps, parse_smaps_rollup, read_memory_map, MappingGroup, MappingKind, MemoryMapping, MemoryUsage,
+    ProcessMemoryMap,
+};
+pub use process::supervisor::{
+    InvalidSupervisorRule, LaunchCommand, SupervisedState, Supervisor, SupervisorEvent, SupervisorEventKind, SupervisorRule,
+    SupervisorStatus, SupervisorTarget, BACKOFF_RESET_SECS, CHECK_INTERVAL_SECS, MAX_BACKOFF_SECS,
+};
+pub use process::threads::{build_thread_list, list_threads, thread_count, ProcessThreads, TaskState, ThreadInfo};
+pub use process::users::{list_users, user_matches, ProcessOwner, UserDirectory, UserInfo};
+pub use process::tree::{build_process_tree, process_lineage, ProcessLineage, ProcessTreeFilter, ProcessTreeNode};
+
+// 校验警报配置引用的升级策略是否存在
+async fn ensure_escalation_policy_exists(
+    db_manager: &DatabaseManager,
+    config: &AlertConfiguration,
+) -> Result<(), String> {
+    if let Some(policy_id) = &config.escalation_policy_id {
+        let policy = db_manager.get_escalation_policy(policy_id)
+            .await
+            .map_err(|e| format!("Failed to get escalation policy: {}", e))?;
+        if policy.is_none() {
+            return Err(format!("Escalation policy {} not found", policy_id));
+        }
+    }
+    Ok(())
+}
 
 // 获取所有警报配置
 #[tauri::command]
@@ -957,6 +1029,9 @@ async fn add_alert_configuration(
     let db_manager = state.db_manager.as_ref()
         .ok_or_else(|| "Database not initialized".to_string())?;
     
+    alerting::validate_configuration(&config)?;
+    ensure_escalation_policy_exists(db_manager, &config).await?;
+    
     db_manager.add_alert_configuration(&config)
         .await
         .map_err(|e| format!("Failed to add alert configuration: {}", e))
@@ -972,9 +1047,97 @@ async fn update_alert_configuration(
     let db_manager = state.db_manager.as_ref()
         .ok_or_else(|| "Database not initialized".to_string())?;
     
+    alerting::validate_configuration(&config)?;
+    ensure_escalation_policy_exists(db_manager, &config).await?;
+    
     db_manager.update_alert_configuration(&id, &config)
         .await
-        .map_err(|e| format!("Failed to update alert configuration: {}", e))
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Alert configuration {} not found", id),
+            e => format!("Failed to update alert configuration: {}", e),
+        })
+}
+
+// 校验并修复已存储的警报配置
+#[tauri::command]
+async fn migrate_alert_configurations(
+    state: State<'_, SystemState>,
+    repair: Option<bool>,
+) -> Result<AlertMigrationReport, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.migrate_alert_configurations(repair.unwrap_or(false))
+        .await
+        .map_err(|e| format!("Failed to migrate alert configurations: {}", e))
+}
+
+// 获取应用启动时对警报配置的检查结果，需要修复或禁用的规则由 migrate_alert_configurations 处理
+#[tauri::command]
+async fn get_startup_migration_report(state: State<'_, SystemState>) -> Result<AlertMigrationReport, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    Ok(db_manager.startup_migration_report().clone())
+}
+
+// 导出所有警报配置为 JSON 或 YAML 规则文件
+#[tauri::command]
+async fn export_alert_configurations(
+    state: State<'_, SystemState>,
+    format: Option<RuleFileFormat>,
+) -> Result<String, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let rules = db_manager.get_alert_configurations()
+        .await
+        .map_err(|e| format!("Failed to get alert configurations: {}", e))?;
+    
+    alerting::transfer::export_rule_file(rules, format.unwrap_or_default())
+        .map_err(|e| format!("Failed to export alert configurations: {}", e))
+}
+
+// 从 JSON 或 YAML 规则文件导入警报配置
+#[tauri::command]
+async fn import_alert_configurations(
+    state: State<'_, SystemState>,
+    content: String,
+    format: Option<RuleFileFormat>,
+    conflict_mode: Option<ImportConflictMode>,
+) -> Result<AlertImportReport, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let rules = alerting::transfer::parse_rule_file(&content, format)?;
+    
+    db_manager.import_alert_configurations(rules, conflict_mode.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to import alert configurations: {}", e))
+}
+
+// 导入 Prometheus 告警规则，无法映射的规则在报告中列出
+#[tauri::command]
+async fn import_prometheus_rules(
+    state: State<'_, SystemState>,
+    content: String,
+    conflict_mode: Option<ImportConflictMode>,
+) -> Result<AlertImportReport, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let conversion = alerting::prometheus::convert_prometheus_rules(&content)?;
+    
+    let mut report = db_manager.import_alert_configurations(conversion.rules, conflict_mode.unwrap_or_default())
+        .await
+        .map_err(|e| format!("Failed to import Prometheus rules: {}", e))?;
+    report.warnings.extend(
+        conversion.warnings
+            .into_iter()
+            .map(|(id, message)| AlertImportIssue { id, message }),
+    );
+    report.unmapped = conversion.unmapped;
+    Ok(report)
 }
 
 // 删除警报配置
@@ -991,17 +1154,21 @@ async fn delete_alert_configuration(
         .map_err(|e| format!("Failed to delete alert configuration: {}", e))
 }
 
-// 获取警报历史
+// 获取警报历史，可按规则、严重程度、时间范围、确认状态和目标过滤
 #[tauri::command]
 async fn get_alert_history(
     state: State<'_, SystemState>,
     limit: Option<u32>,
     offset: Option<u32>,
+    filter: Option<AlertHistoryFilter>,
 ) -> Result<Vec<AlertHistory>, String> {
     let db_manager = state.db_manager.as_ref()
         .ok_or_else(|| "Database not initialized".to_string())?;
     
-    db_manager.get_alert_history(limit.unwrap_or(100), offset.unwrap_or(0))
+    let filter = filter.unwrap_or_default();
+    filter.validate()?;
+    
+    db_manager.get_alert_history(&filter, Some(limit.unwrap_or(100)), offset.unwrap_or(0))
         .await
         .map_err(|e| format!("Failed to get alert history: {}", e))
 }
@@ -1018,7 +1185,173 @@ async fn acknowledge_alert(
     
     db_manager.acknowledge_alert(&id, &acknowledged_by)
         .await
-        .map_err(|e| format!("Failed to acknowledge alert: {}", e))
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Alert {} not found", id),
+            e => format!("Failed to acknowledge alert: {}", e),
+        })
+}
+
+// 批量确认匹配过滤条件的警报，按规则确认时在 rule_ids 中指定规则；
+// 过滤条件为空时需要显式指定 all 才会确认所有警报
+#[tauri::command]
+async fn acknowledge_alerts(
+    state: State<'_, SystemState>,
+    filter: AlertHistoryFilter,
+    acknowledged_by: String,
+    all: Option<bool>,
+) -> Result<u64, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    filter.validate()?;
+    if filter.is_empty() && !all.unwrap_or(false) {
+        return Err("Filter has no criteria; set all to acknowledge every alert".to_string());
+    }
+    
+    db_manager.acknowledge_alerts(&filter, &acknowledged_by)
+        .await
+        .map_err(|e| format!("Failed to acknowledge alerts: {}", e))
+}
+
+// 按通知策略决定 check_alerts 返回的警报应立即发送的通知方式，并返回到期的摘要；
+// 没有新警报时也应定期调用，以便发送到期的摘要
+#[tauri::command]
+async fn plan_notifications(
+    state: State<'_, SystemState>,
+    alerts: Vec<AlertHistory>,
+    policy: Option<NotificationPolicy>,
+    locale: Option<MessageLocale>,
+) -> Result<NotificationPlan, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let policy = policy.unwrap_or_default();
+    policy.validate()?;
+    
+    db_manager.plan_notifications(&alerts, &policy, locale.unwrap_or_default(), Utc::now())
+        .await
+        .map_err(|e| format!("Failed to plan notifications: {}", e))
+}
+
+// 获取最近的通知摘要
+#[tauri::command]
+async fn get_notification_digests(
+    state: State<'_, SystemState>,
+    limit: Option<u32>,
+) -> Result<Vec<NotificationDigest>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_notification_digests(limit.unwrap_or(50))
+        .await
+        .map_err(|e| format!("Failed to get notification digests: {}", e))
+}
+
+// 获取事件列表，未指定状态时返回所有事件
+#[tauri::command]
+async fn get_incidents(
+    state: State<'_, SystemState>,
+    statuses: Option<Vec<IncidentStatus>>,
+    limit: Option<u32>,
+) -> Result<Vec<Incident>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_incidents(&statuses.unwrap_or_default(), limit)
+        .await
+        .map_err(|e| format!("Failed to get incidents: {}", e))
+}
+
+// 获取事件详情，包括成员警报和时间线
+#[tauri::command]
+async fn get_incident(
+    state: State<'_, SystemState>,
+    id: String,
+) -> Result<IncidentDetail, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_incident(&id)
+        .await
+        .map_err(|e| format!("Failed to get incident: {}", e))?
+        .ok_or_else(|| format!("Incident {} not found", id))
+}
+
+// 确认事件，同时确认其中所有尚未确认的警报，返回确认的警报数量
+#[tauri::command]
+async fn acknowledge_incident(
+    state: State<'_, SystemState>,
+    id: String,
+    acknowledged_by: String,
+) -> Result<u64, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.acknowledge_incident(&id, &acknowledged_by)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Incident {} not found", id),
+            e => format!("Failed to acknowledge incident: {}", e),
+        })
+}
+
+// 手动解决事件
+#[tauri::command]
+async fn resolve_incident(
+    state: State<'_, SystemState>,
+    id: String,
+    resolved_by: String,
+) -> Result<IncidentEvent, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.resolve_incident(&id, &resolved_by)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Incident {} not found", id),
+            e => format!("Failed to resolve incident: {}", e),
+        })
+}
+
+// 为事件添加备注
+#[tauri::command]
+async fn add_incident_note(
+    state: State<'_, SystemState>,
+    id: String,
+    author: String,
+    note: String,
+) -> Result<IncidentEvent, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    if note.trim().is_empty() {
+        return Err("Incident note must not be empty".to_string());
+    }
+    
+    db_manager.add_incident_note(&id, &author, note.trim())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Incident {} not found", id),
+            e => format!("Failed to add incident note: {}", e),
+        })
+}
+
+// 获取警报统计：每条规则的触发次数、MTTA、MTTR 以及最吵的规则
+#[tauri::command]
+async fn get_alert_statistics(
+    state: State<'_, SystemState>,
+    filter: Option<AlertHistoryFilter>,
+    noisiest_limit: Option<u32>,
+) -> Result<AlertStatistics, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let filter = filter.unwrap_or_default();
+    filter.validate()?;
+    
+    db_manager.get_alert_statistics(&filter, noisiest_limit.unwrap_or(5) as usize)
+        .await
+        .map_err(|e| format!("Failed to get alert statistics: {}", e))
 }
 
 // 检查警报
@@ -1029,15 +1362,468 @@ async fn check_alerts(
     memory_usage: f32,
     disk_usage: f32,
     network_traffic: f64,
+    locale: Option<MessageLocale>,
+    incident_grouping: Option<IncidentGrouping>,
 ) -> Result<Vec<AlertHistory>, String> {
     let db_manager = state.db_manager.as_ref()
         .ok_or_else(|| "Database not initialized".to_string())?;
+    let locale = locale.unwrap_or_default();
+    let grouping = incident_grouping.unwrap_or_default();
+    grouping.validate()?;
     
-    db_manager.check_alerts(cpu_usage, memory_usage, disk_usage, network_traffic)
+    // 配置只加载一次，既用于判断需要采集哪些数据，也用于检查警报
+    let configurations = db_manager.get_alert_configurations()
+        .await
+        .map_err(|e| format!("Failed to check alerts: {}", e))?;
+    // 采集进程开销较大，只在有规则的消息模板引用 `{top_processes}` 时采集
+    let needs_processes = configurations.iter().filter(|config| config.enabled).any(|config| {
+        let template = config.message_template.as_deref().unwrap_or(locale.default_template());
+        alerting::template::references_placeholder(template, "top_processes")
+    });
+    let top_processes = if needs_processes {
+        tokio::task::spawn_blocking(|| collect_top_processes(3))
+            .await
+            .map_err(|e| format!("Failed to collect processes: {}", e))?
+    } else {
+        Vec::new()
+    };
+    let environment = MessageEnvironment {
+        hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
+        locale,
+        top_processes,
+    };
+    
+    // 补充表达式可能引用的负载、交换分区、核心数和文件描述符数量；
+    // 扫描所有进程的文件描述符开销较大，只在有启用的规则使用时扫描
+    let open_fds_process = if fds::is_supported() && alerting::rules_use_metric(&configurations, AlertMetric::OpenFds) {
+        tokio::task::spawn_blocking(fds::busiest_process)
+            .await
+            .map_err(|e| format!("Failed to count open file descriptors: {}", e))?
+    } else {
+        None
+    };
+    let mut sys = System::new();
+    sys.refresh_memory();
+    let swap_usage = if sys.total_swap() > 0 {
+        (sys.used_swap() as f64 / sys.total_swap() as f64) * 100.0
+    } else {
+        0.0
+    };
+    
+    let snapshot = MetricSnapshot {
+        cpu: cpu_usage as f64,
+        memory: memory_usage as f64,
+        disk: disk_usage as f64,
+        network: network_traffic,
+        load: System::load_average().one,
+        swap: swap_usage,
+        cores: state.sys.cpus().len() as f64,
+        open_fds: open_fds_process.as_ref().map(|process| process.fd_count as f64),
+        open_fds_process,
+    };
+    
+    db_manager.check_alerts(configurations, &snapshot, &environment, &grouping)
         .await
         .map_err(|e| format!("Failed to check alerts: {}", e))
 }
 
+/// 按 CPU 使用率取占用最高的若干进程，需要两次刷新才能得到 CPU 使用率
+fn collect_top_processes(limit: usize) -> Vec<ProcessSample> {
+    let mut sys = System::new();
+    sys.refresh_processes();
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    sys.refresh_processes();
+    
+    let mut processes: Vec<ProcessSample> = sys
+        .processes()
+        .iter()
+        .map(|(pid, process)| ProcessSample {
+            pid: pid.as_u32(),
+            name: process.name().to_string(),
+            cpu_usage: process.cpu_usage(),
+            memory_bytes: process.memory(),
+        })
+        .collect();
+    processes.sort_by(|a, b| b.cpu_usage.partial_cmp(&a.cpu_usage).unwrap_or(std::cmp::Ordering::Equal));
+    processes.truncate(limit);
+    processes
+}
+
+// 获取指定语言的默认警报消息模板
+#[tauri::command]
+async fn get_default_alert_template(locale: Option<MessageLocale>) -> Result<String, String> {
+    Ok(locale.unwrap_or_default().default_template().to_string())
+}
+
+// 使用示例数据或指定的上下文预览警报消息模板，未指定模板时预览默认模板
+#[tauri::command]
+async fn preview_alert_message(
+    template: Option<String>,
+    locale: Option<MessageLocale>,
+    sample: Option<AlertMessageContext>,
+) -> Result<String, String> {
+    let locale = locale.unwrap_or_default();
+    let template = template.unwrap_or_else(|| locale.default_template().to_string());
+    validate_template(&template)?;
+    
+    let sample = sample.unwrap_or_else(|| {
+        let hostname = System::host_name().unwrap_or_else(|| "Unknown".to_string());
+        AlertMessageContext::sample(&hostname, locale)
+    });
+    render_template(&template, &sample, locale)
+}
+
+// 回测和异常扫描时间范围的上限
+const MAX_BACKTEST_DAYS: i64 = 31;
+
+// 用历史数据回测一条（可能尚未保存的）警报规则，返回其在范围内的触发与恢复时间
+#[tauri::command]
+async fn backtest_alert_configuration(
+    state: State<'_, SystemState>,
+    config: AlertConfiguration,
+    start_time: String,
+    end_time: String,
+    locale: Option<MessageLocale>,
+) -> Result<AlertBacktestReport, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    validate_configuration(&config)?;
+    
+    let start_dt = DateTime::parse_from_rfc3339(&start_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid start time format: {}", e))?;
+    
+    let end_dt = DateTime::parse_from_rfc3339(&end_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid end time format: {}", e))?;
+    
+    if end_dt - start_dt > chrono::Duration::days(MAX_BACKTEST_DAYS) {
+        return Err(format!("Backtest range must not exceed {} days", MAX_BACKTEST_DAYS));
+    }
+    
+    // 额外加载规则求值所需的窗口，使范围开头的点也能求值
+    let history = db_manager.fetch_historical_data(start_dt - backtest_warmup(&config), end_dt)
+        .await
+        .map_err(|e| format!("Failed to fetch historical data: {}", e))?;
+    let cores = state.sys.cpus().len() as f64;
+    let locale = locale.unwrap_or_default();
+    
+    tokio::task::spawn_blocking(move || backtest_rule(&config, &history, start_dt, end_dt, cores, locale))
+        .await
+        .map_err(|e| format!("Failed to backtest alert configuration: {}", e))?
+}
+
+// 为规则生成一条测试警报，按与 check_alerts 警报相同的通知策略决定发送方式；
+// 返回的通知标记为测试，测试警报不写入警报历史
+#[tauri::command]
+async fn test_fire_alert(
+    state: State<'_, SystemState>,
+    rule_id: String,
+    policy: Option<NotificationPolicy>,
+    locale: Option<MessageLocale>,
+) -> Result<NotificationPlan, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let policy = policy.unwrap_or_default();
+    policy.validate()?;
+    
+    let config = db_manager.get_alert_configurations()
+        .await
+        .map_err(|e| format!("Failed to get alert configurations: {}", e))?
+        .into_iter()
+        .find(|config| config.id == rule_id)
+        .ok_or_else(|| format!("Alert configuration {} not found", rule_id))?;
+    
+    let environment = MessageEnvironment {
+        hostname: System::host_name().unwrap_or_else(|| "Unknown".to_string()),
+        locale: locale.unwrap_or_default(),
+        top_processes: Vec::new(),
+    };
+    let now = Utc::now();
+    let test = test_fire(&config, &environment, now);
+    db_manager.plan_test_notification(&test.alert, &config, &policy, now)
+        .await
+        .map_err(|e| format!("Failed to plan notifications: {}", e))
+}
+
+// 计算指标当前的异常检测基线，并判断当前值是否偏离
+#[tauri::command]
+async fn get_metric_baseline(
+    state: State<'_, SystemState>,
+    metric: AlertMetric,
+    detection: AnomalyDetection,
+    value: Option<f64>,
+) -> Result<Option<AnomalyVerdict>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    detection.validate()?;
+    
+    let now = Utc::now();
+    let start_time = detection.lookback_start(now)
+        .ok_or_else(|| "Anomaly lookback is out of range".to_string())?;
+    let series = db_manager.fetch_metric_series(metric, start_time, now)
+        .await
+        .map_err(|e| format!("Failed to fetch metric history: {}", e))?;
+    
+    // 未提供当前值时使用最近一条历史记录
+    let Some(value) = value.or_else(|| series.last().map(|(_, value)| *value)) else {
+        return Ok(None);
+    };
+    Ok(detection
+        .baseline(&series, now)
+        .map(|baseline| detection.evaluate(&baseline, value)))
+}
+
+// 在历史数据中查找异常点
+#[tauri::command]
+async fn detect_metric_anomalies(
+    state: State<'_, SystemState>,
+    metric: AlertMetric,
+    detection: AnomalyDetection,
+    start_time: String,
+    end_time: String,
+) -> Result<Vec<AnomalyPoint>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    detection.validate()?;
+    
+    let start_dt = DateTime::parse_from_rfc3339(&start_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid start time format: {}", e))?;
+    
+    let end_dt = DateTime::parse_from_rfc3339(&end_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid end time format: {}", e))?;
+    
+    if end_dt < start_dt {
+        return Err("End time must not be before start time".to_string());
+    }
+    if end_dt - start_dt > chrono::Duration::days(MAX_BACKTEST_DAYS) {
+        return Err(format!("Anomaly detection range must not exceed {} days", MAX_BACKTEST_DAYS));
+    }
+    
+    // 额外加载回看窗口内的数据，使范围开头的点也有基线可比
+    let warmup_start = detection.lookback_start(start_dt)
+        .ok_or_else(|| "Anomaly lookback is out of range".to_string())?;
+    let series = db_manager.fetch_metric_series(metric, warmup_start, end_dt)
+        .await
+        .map_err(|e| format!("Failed to fetch metric history: {}", e))?;
+    
+    let mut points = detection.scan(&series);
+    points.retain(|point| point.timestamp >= start_dt);
+    Ok(points)
+}
+
+// 预测磁盘或内存使用率到达阈值的时间
+#[tauri::command]
+async fn forecast_resource_usage(
+    state: State<'_, SystemState>,
+    metric: AlertMetric,
+    target: Option<String>,
+    lookback_hours: Option<u32>,
+    threshold: Option<f64>,
+) -> Result<Vec<ResourceForecast>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    if !alerting::forecast::supports_forecast(metric) {
+        return Err(format!(
+            "Forecasting is only available for disk_usage and memory_usage, not {}",
+            metric.as_str()
+        ));
+    }
+    
+    let condition = ForecastCondition {
+        lookback_hours: lookback_hours.unwrap_or(24),
+        threshold: threshold.unwrap_or(100.0),
+        within_hours: alerting::forecast::MAX_FORECAST_HOURS,
+    };
+    condition.validate()?;
+    
+    db_manager.forecast_resource(metric, target.as_deref(), condition.lookback_hours, condition.threshold, Utc::now())
+        .await
+        .map_err(|e| format!("Failed to forecast resource usage: {}", e))
+}
+
+// 获取警报静默列表
+#[tauri::command]
+async fn get_alert_silences(
+    state: State<'_, SystemState>,
+    include_expired: Option<bool>,
+) -> Result<Vec<AlertSilence>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let mut silences = db_manager.get_alert_silences()
+        .await
+        .map_err(|e| format!("Failed to get alert silences: {}", e))?;
+    
+    if !include_expired.unwrap_or(false) {
+        let now = Utc::now();
+        silences.retain(|silence| !silence.schedule.is_expired_at(now));
+    }
+    
+    Ok(silences)
+}
+
+// 添加警报静默
+#[tauri::command]
+async fn add_alert_silence(
+    state: State<'_, SystemState>,
+    silence: AlertSilence,
+) -> Result<String, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    silence.validate()?;
+    
+    db_manager.add_alert_silence(&silence)
+        .await
+        .map_err(|e| format!("Failed to add alert silence: {}", e))
+}
+
+// 更新警报静默
+#[tauri::command]
+async fn update_alert_silence(
+    state: State<'_, SystemState>,
+    id: String,
+    silence: AlertSilence,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    silence.validate()?;
+    
+    db_manager.update_alert_silence(&id, &silence)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Alert silence {} not found", id),
+            e => format!("Failed to update alert silence: {}", e),
+        })
+}
+
+// 删除警报静默
+#[tauri::command]
+async fn delete_alert_silence(
+    state: State<'_, SystemState>,
+    id: String,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.delete_alert_silence(&id)
+        .await
+        .map_err(|e| format!("Failed to delete alert silence: {}", e))
+}
+
+// 获取所有升级策略
+#[tauri::command]
+async fn get_escalation_policies(
+    state: State<'_, SystemState>,
+) -> Result<Vec<EscalationPolicy>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_escalation_policies()
+        .await
+        .map_err(|e| format!("Failed to get escalation policies: {}", e))
+}
+
+// 添加升级策略
+#[tauri::command]
+async fn add_escalation_policy(
+    state: State<'_, SystemState>,
+    policy: EscalationPolicy,
+) -> Result<String, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    policy.validate()?;
+    
+    db_manager.add_escalation_policy(&policy)
+        .await
+        .map_err(|e| format!("Failed to add escalation policy: {}", e))
+}
+
+// 更新升级策略
+#[tauri::command]
+async fn update_escalation_policy(
+    state: State<'_, SystemState>,
+    id: String,
+    policy: EscalationPolicy,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    policy.validate()?;
+    
+    db_manager.update_escalation_policy(&id, &policy)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Escalation policy {} not found", id),
+            e => format!("Failed to update escalation policy: {}", e),
+        })
+}
+
+// 删除升级策略
+#[tauri::command]
+async fn delete_escalation_policy(
+    state: State<'_, SystemState>,
+    id: String,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.delete_escalation_policy(&id)
+        .await
+        .map_err(|e| format!("Failed to delete escalation policy: {}", e))
+}
+
+// 处理未确认警报的升级，前端按返回的通知方式重新通知
+#[tauri::command]
+async fn process_alert_escalations(
+    state: State<'_, SystemState>,
+) -> Result<Vec<AlertEscalation>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.process_escalations(Utc::now())
+        .await
+        .map_err(|e| format!("Failed to process alert escalations: {}", e))
+}
+
+// 获取警报的升级记录
+#[tauri::command]
+async fn get_alert_escalations(
+    state: State<'_, SystemState>,
+    alert_history_id: String,
+) -> Result<Vec<AlertEscalation>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_alert_escalations(&alert_history_id)
+        .await
+        .map_err(|e| format!("Failed to get alert escalations: {}", e))
+}
+
+// 获取警报的修复动作执行记录
+#[tauri::command]
+async fn get_remediation_results(
+    state: State<'_, SystemState>,
+    alert_history_id: String,
+) -> Result<Vec<RemediationResult>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_remediation_results(&alert_history_id)
+        .await
+        .map_err(|e| format!("Failed to get remediation results: {}", e))
+}
+
 // 进程详细信息结构体
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProcessDetails {
@@ -1049,12 +1835,40 @@ pub struct ProcessDetails {
     pub cpu_usage_percent: f32,
     pub memory_usage_bytes: u64,
     pub thread_count: u32,
+    /// nice 值，无法读取时为 0
     pub priority: i32,
+    #[serde(default)]
+    pub scheduling_policy: Option<SchedulingPolicy>,
     pub status: String,
     pub start_time: Option<String>,
+    /// 用户名，无法解析时为 uid
     pub user: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    #[serde(default)]
+    pub group: Option<String>,
+    #[serde(default)]
+    pub group_id: Option<String>,
     pub parent_pid: Option<String>,
     pub working_directory: Option<String>,
+    /// 进程启动以来累计读写的磁盘字节数
+    #[serde(default)]
+    pub disk_read_bytes: u64,
+    #[serde(default)]
+    pub disk_written_bytes: u64,
+    #[serde(default)]
+    pub disk_read_bytes_per_sec: f64,
+    #[serde(default)]
+    pub disk_written_bytes_per_sec: f64,
+    /// 进程所属的 cgroup 路径、systemd 单元和容器
+    #[serde(default)]
+    pub cgroup_path: Option<String>,
+    #[serde(default)]
+    pub systemd_unit: Option<String>,
+    #[serde(default)]
+    pub container_id: Option<String>,
+    #[serde(default)]
+    pub container_runtime: Option<ContainerRuntime>,
 }
 
 // 进程排序选项
@@ -1064,6 +1878,12 @@ pub struct ProcessSortOptions {
     pub sort_order: String, // "asc" or "desc"
     pub filter_name: Option<String>,
     pub filter_user: Option<String>,
+    /// systemd 单元名的一部分
+    #[serde(default)]
+    pub filter_unit: Option<String>,
+    /// 容器 ID 或其前缀
+    #[serde(default)]
+    pub filter_container: Option<String>,
     pub min_cpu_usage: Option<f32>,
     pub max_memory_usage: Option<u64>,
 }
@@ -1076,12 +1896,22 @@ async fn get_processes_enhanced(
 ) -> Result<Vec<ProcessDetails>, String> {
     // 在后台线程执行进程信息收集
     let processes = tokio::task::spawn_blocking(move || {
-        let mut sys = System::new_all();
-        sys.refresh_all();
+        // 两次刷新之间的增量用于计算 CPU 使用率和磁盘 I/O 速率
+        let (sys, elapsed) = process::refresh_twice(System::new(), sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
         
+        let directory = UserDirectory::cached();
         let mut processes = Vec::new();
         
-        for (pid, process) in sys.processes() {
+        // Linux 上 sysinfo 把用户态线程也列为进程，线程数单独统计
+        let processes_only = sys
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind() != Some(sysinfo::ThreadKind::Userland));
+        for (pid, process) in processes_only {
+            let priority = read_priority(pid.as_u32()).ok();
+            let io = ProcessIo::from_process(pid.as_u32(), process, elapsed);
+            let owner = directory.owner(process);
+            let cgroup = read_cgroup(pid.as_u32()).unwrap_or_default();
             let process_details = ProcessDetails {
                 pid: pid.as_u32().to_string(),
                 name: process.name().to_string(),
@@ -1090,13 +1920,25 @@ async fn get_processes_enhanced(
                 exe: process.exe().map(|p| p.to_string_lossy().to_string()),
                 cpu_usage_percent: process.cpu_usage(),
                 memory_usage_bytes: process.memory(),
-                thread_count: 0, // sysinfo crate doesn't have threads() method in Process
-                priority: 0, // sysinfo crate doesn't have priority() method in Process
+                thread_count: thread_count(pid.as_u32()),
+                priority: priority.as_ref().map_or(0, |priority| priority.nice),
+                scheduling_policy: priority.and_then(|priority| priority.scheduling_policy),
                 status: format!("{:?}", process.status()),
                 start_time: Some(process.start_time().to_string()),
-                user: process.user_id().map(|uid| uid.to_string()),
+                user: owner.display_user(),
+                user_id: owner.user_id,
+                group: owner.group_name,
+                group_id: owner.group_id,
                 parent_pid: process.parent().map(|p| p.as_u32().to_string()),
                 working_directory: process.cwd().map(|p| p.to_string_lossy().to_string()),
+                disk_read_bytes: io.read_bytes,
+                disk_written_bytes: io.written_bytes,
+                disk_read_bytes_per_sec: io.read_bytes_per_sec,
+                disk_written_bytes_per_sec: io.written_bytes_per_sec,
+                cgroup_path: Some(cgroup.path).filter(|path| !path.is_empty()),
+                systemd_unit: cgroup.systemd_unit,
+                container_id: cgroup.container_id,
+                container_runtime: cgroup.container_runtime,
             };
             
             processes.push(process_details);
@@ -1110,7 +1952,16 @@ async fn get_processes_enhanced(
             }
             
             if let Some(filter_user) = &options.filter_user {
-                processes.retain(|p| p.user.as_ref().map_or(false, |user| user.contains(filter_user)));
+                // 按 uid 或用户名匹配
+                processes.retain(|p| user_matches(filter_user, p.user_id.as_deref(), p.user.as_deref()));
+            }
+            
+            if let Some(filter_unit) = &options.filter_unit {
+                processes.retain(|p| unit_matches(filter_unit, p.systemd_unit.as_deref()));
+            }
+            
+            if let Some(filter_container) = &options.filter_container {
+                processes.retain(|p| container_matches(filter_container, p.container_id.as_deref()));
             }
             
             if let Some(min_cpu) = options.min_cpu_usage {
@@ -1151,6 +2002,19 @@ async fn get_processes_enhanced(
                         processes.sort_by(|a, b| a.pid.cmp(&b.pid));
                     }
                 },
+                // 磁盘 I/O 按每秒速率排序
+                "disk_read" | "disk_write" | "disk_io" => {
+                    let rate = |p: &ProcessDetails| match options.sort_by.as_str() {
+                        "disk_read" => p.disk_read_bytes_per_sec,
+                        "disk_write" => p.disk_written_bytes_per_sec,
+                        _ => p.disk_read_bytes_per_sec + p.disk_written_bytes_per_sec,
+                    };
+                    if options.sort_order == "desc" {
+                        processes.sort_by(|a, b| rate(b).total_cmp(&rate(a)));
+                    } else {
+                        processes.sort_by(|a, b| rate(a).total_cmp(&rate(b)));
+                    }
+                },
                 _ => {
                     // 默认按CPU使用率降序排序
                     processes.sort_by(|a, b| b.cpu_usage_percent.partial_cmp(&a.cpu_usage_percent).unwrap_or(std::cmp::Ordering::Equal));
@@ -1171,16 +2035,19 @@ async fn get_processes_enhanced(
 #[tauri::command]
 async fn get_process_details(_state: State<'_, SystemState>, pid: String) -> Result<ProcessDetails, String> {
     // 在后台线程执行进程详细信息收集
-    let process_details = tokio::task::spawn_blocking(move || {
-        let mut sys = System::new_all();
-        sys.refresh_all();
-        
+    let process_details = tokio::task::spawn_blocking(move || {
         // 解析PID
         let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
         let pid = sysinfo::Pid::from_u32(pid_num);
         
+        let (sys, elapsed) = process::refresh_twice(System::new(), sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        
         // 查找进程
         if let Some(process) = sys.process(pid) {
+            let priority = read_priority(pid_num).ok();
+            let io = ProcessIo::from_process(pid_num, process, elapsed);
+            let owner = UserDirectory::cached().owner(process);
+            let cgroup = read_cgroup(pid_num).unwrap_or_default();
             Ok(ProcessDetails {
                 pid: pid.as_u32().to_string(),
                 name: process.name().to_string(),
@@ -1189,13 +2056,25 @@ async fn get_process_details(_state: State<'_, SystemState>, pid: String) -> Res
                 exe: process.exe().map(|p| p.to_string_lossy().to_string()),
                 cpu_usage_percent: process.cpu_usage(),
                 memory_usage_bytes: process.memory(),
-                thread_count: 0, // sysinfo crate doesn't have threads() method in Process
-                priority: 0, // sysinfo crate doesn't have priority() method in Process
+                thread_count: thread_count(pid.as_u32()),
+                priority: priority.as_ref().map_or(0, |priority| priority.nice),
+                scheduling_policy: priority.and_then(|priority| priority.scheduling_policy),
                 status: format!("{:?}", process.status()),
                 start_time: Some(process.start_time().to_string()),
-                user: process.user_id().map(|uid| uid.to_string()),
+                user: owner.display_user(),
+                user_id: owner.user_id,
+                group: owner.group_name,
+                group_id: owner.group_id,
                 parent_pid: process.parent().map(|p| p.as_u32().to_string()),
                 working_directory: process.cwd().map(|p| p.to_string_lossy().to_string()),
+                disk_read_bytes: io.read_bytes,
+                disk_written_bytes: io.written_bytes,
+                disk_read_bytes_per_sec: io.read_bytes_per_sec,
+                disk_written_bytes_per_sec: io.written_bytes_per_sec,
+                cgroup_path: Some(cgroup.path).filter(|path| !path.is_empty()),
+                systemd_unit: cgroup.systemd_unit,
+                container_id: cgroup.container_id,
+                container_runtime: cgroup.container_runtime,
             })
         } else {
             Err(format!("Process with PID {} not found", pid))
@@ -1208,59 +2087,513 @@ async fn get_process_details(_state: State<'_, SystemState>, pid: String) -> Res
     }
 }
 
-// 终止进程
+// 获取进程树，每个节点附带子树合计的 CPU 和内存占用；过滤时保留匹配进程的祖先链
+#[tauri::command]
+async fn get_process_tree(filter: Option<ProcessTreeFilter>) -> Result<Vec<ProcessTreeNode>, String> {
+    tokio::task::spawn_blocking(move || build_process_tree(&ProcessEntry::snapshot(), filter.as_ref()))
+        .await
+        .map_err(|e| format!("Failed to build process tree: {}", e))
+}
+
+// 获取进程的祖先链和后代子树
+#[tauri::command]
+async fn get_process_lineage(pid: String) -> Result<ProcessLineage, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    
+    tokio::task::spawn_blocking(move || process_lineage(&ProcessEntry::snapshot(), pid_num))
+        .await
+        .map_err(|e| format!("Failed to get process lineage: {}", e))?
+        .ok_or_else(|| format!("Process with PID {} not found", pid))
+}
+
+// 终止进程，force 为 true 时发送 SIGKILL，否则发送 SIGTERM
 #[tauri::command]
 async fn terminate_process(_state: State<'_, SystemState>, pid: String, force: bool) -> Result<String, String> {
-    // 在后台线程执行进程终止
-    let result = tokio::task::spawn_blocking(move || {
-        // 解析PID
-        let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
-        
-        // 使用系统命令终止进程
-        let output = if cfg!(target_os = "windows") {
-            if force {
-                // 强制终止
-                Command::new("taskkill")
-                    .args(["/F", "/PID", &pid_num.to_string()])
-                    .output()
-            } else {
-                // 正常终止
-                Command::new("taskkill")
-                    .args(["/PID", &pid_num.to_string()])
-                    .output()
-            }
-        } else {
-            if force {
-                // Unix/Linux强制终止
-                Command::new("kill")
-                    .args(["-9", &pid_num.to_string()])
-                    .output()
-            } else {
-                // Unix/Linux正常终止
-                Command::new("kill")
-                    .args([&pid_num.to_string()])
-                    .output()
-            }
-        };
-        
-        match output {
-            Ok(result) => {
-                if result.status.success() {
-                    Ok(format!("Process {} terminated successfully", pid))
-                } else {
-                    let stderr_str = String::from_utf8_lossy(&result.stderr);
-                    let stdout_str = String::from_utf8_lossy(&result.stdout);
-                    Err(format!("Failed to terminate process {}: stdout={}, stderr={}", pid, stdout_str, stderr_str))
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    let signal = if force { ProcessSignal::Kill } else { ProcessSignal::Term };
+
+    let outcomes = tokio::task::spawn_blocking(move || signal_process(pid_num, signal, SignalScope::Process))
+        .await
+        .map_err(|e| format!("终止进程时发生错误: {}", e))??;
+    match outcomes.into_iter().next() {
+        Some(outcome) if outcome.status.is_success() => Ok(format!("Process {} terminated successfully", pid)),
+        Some(outcome) => Err(format!(
+            "Failed to terminate process {}: {}",
+            pid,
+            outcome.error.unwrap_or_else(|| format!("{:?}", outcome.status))
+        )),
+        None => Err(format!("Process with PID {} not found", pid)),
+    }
+}
+
+// 向进程、进程树或进程组发送信号，按 PID 返回结果
+#[tauri::command]
+async fn send_process_signal(
+    pid: String,
+    signal: String,
+    scope: Option<SignalScope>,
+) -> Result<Vec<SignalOutcome>, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    let signal = ProcessSignal::from_name(&signal).ok_or_else(|| format!("Unsupported signal: {}", signal))?;
+
+    tokio::task::spawn_blocking(move || signal_process(pid_num, signal, scope.unwrap_or_default()))
+        .await
+        .map_err(|e| format!("Failed to send signal: {}", e))?
+}
+
+// 逐级终止进程、进程树或进程组：先 SIGTERM，等待期后仍未退出的进程发送 SIGKILL
+#[tauri::command]
+async fn terminate_process_gracefully(
+    pid: String,
+    scope: Option<SignalScope>,
+    grace_period_secs: Option<u64>,
+) -> Result<Vec<SignalOutcome>, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    let grace_period_secs = grace_period_secs.unwrap_or(5);
+    if grace_period_secs > MAX_GRACE_PERIOD_SECS {
+        return Err(format!("Grace period cannot exceed {} seconds", MAX_GRACE_PERIOD_SECS));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        terminate_gracefully(pid_num, scope.unwrap_or_default(), std::time::Duration::from_secs(grace_period_secs))
+    })
+    .await
+    .map_err(|e| format!("Failed to terminate process: {}", e))?
+}
+
+// 列出进程的线程及各线程的 CPU 使用率，按使用率从高到低排列
+#[tauri::command]
+async fn get_process_threads(pid: String, sample_interval_ms: Option<u64>) -> Result<ProcessThreads, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    // 采样间隔限制在 100ms 到 5s 之间
+    let interval = std::time::Duration::from_millis(sample_interval_ms.unwrap_or(500).clamp(100, 5000));
+    
+    tokio::task::spawn_blocking(move || list_threads(pid_num, interval))
+        .await
+        .map_err(|e| format!("Failed to list process threads: {}", e))?
+}
+
+// 进程内存映射：RSS、PSS、USS、交换空间，以及按映射文件汇总的用量
+#[tauri::command]
+async fn get_process_memory_map(pid: String) -> Result<ProcessMemoryMap, String> {
+    let pid_num: u32 = pid.parse().map_err(|_| "Invalid PID format".to_string())?;
+    tokio::task::spawn_blocking(move || read_memory_map(pid_num))
+        .await
+        .map_err(|e| format!("Failed to read process memory map: {}", e))?
+}
+
+// 读取进程启动时的环境变量，键名像是密钥的变量按 masking 规则隐藏值
+#[tauri::command]
+async fn get_process_environment(pid: String, masking: Option<EnvMasking>) -> Result<ProcessEnvironment, String> {
+    let pid_num: u32 = pid.parse().map_err(|_| "Invalid PID format".to_string())?;
+    let masking = masking.unwrap_or_default();
+    tokio::task::spawn_blocking(move || read_environment(pid_num, &masking))
+        .await
+        .map_err(|e| format!("Failed to read process environment: {}", e))?
+}
+
+// 读取进程的资源限制
+#[tauri::command]
+async fn get_process_limits(pid: String) -> Result<ProcessLimits, String> {
+    let pid_num: u32 = pid.parse().map_err(|_| "Invalid PID format".to_string())?;
+    tokio::task::spawn_blocking(move || read_limits(pid_num))
+        .await
+        .map_err(|e| format!("Failed to read process limits: {}", e))?
+}
+
+// 提高进程某项资源的软限制，soft_limit 为数值或 "unlimited"；成功与失败都记入审计日志
+#[tauri::command]
+async fn raise_process_soft_limit(
+    state: State<'_, SystemState>,
+    pid: String,
+    resource: String,
+    soft_limit: String,
+) -> Result<ResourceLimit, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    let kind = ResourceLimitKind::from_name(&resource)
+        .ok_or_else(|| format!("Unknown resource limit: {}", resource))?;
+    let value = parse_limit_value(&soft_limit)?;
+    
+    let (process_name, previous, result) = tokio::task::spawn_blocking(move || {
+        let previous = read_limits(pid_num)
+            .ok()
+            .and_then(|limits| limits.limits.into_iter().find(|limit| limit.kind == kind));
+        (process::process_name(pid_num), previous, raise_soft_limit(pid_num, kind, value))
+    })
+    .await
+    .map_err(|e| format!("Failed to raise process limit: {}", e))?;
+    
+    let entry = ProcessAuditEntry::record(
+        pid_num,
+        process_name,
+        ProcessAuditAction::SetLimit,
+        previous.map(|limit| format!("{} soft={}", kind.as_str(), format_limit_value(limit.soft_limit))),
+        format!("{} soft={}", kind.as_str(), format_limit_value(value)),
+        &result,
+    );
+    db_manager.record_process_audit(&entry)
+        .await
+        .map_err(|e| format!("Failed to record process audit entry: {}", e))?;
+    result
+}
+
+// 按 systemd 单元或容器合计 CPU 和内存
+#[tauri::command]
+async fn get_cgroup_usage(group_by: Option<CgroupGrouping>) -> Result<Vec<CgroupUsage>, String> {
+    let grouping = group_by.unwrap_or_default();
+    tokio::task::spawn_blocking(move || cgroup_usage(grouping))
+        .await
+        .map_err(|e| format!("Failed to aggregate cgroup usage: {}", e))
+}
+
+// 浏览 cgroup v2 层级：返回 path 对应的 cgroup 及其直接子 cgroup，path 省略时为根
+#[tauri::command]
+async fn get_cgroup_node(path: Option<String>) -> Result<CgroupNode, String> {
+    let path = path.unwrap_or_else(|| "/".to_string());
+    tokio::task::spawn_blocking(move || {
+        let root = cgroup_root().ok_or_else(|| "cgroup v2 hierarchy is not mounted".to_string())?;
+        read_cgroup_node(&root, &path)
+    })
+    .await
+    .map_err(|e| format!("Failed to read cgroup: {}", e))?
+}
+
+// 获取所有进程守护规则
+#[tauri::command]
+async fn get_supervisor_rules(state: State<'_, SystemState>) -> Result<Vec<SupervisorRule>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_supervisor_rules()
+        .await
+        .map_err(|e| format!("Failed to get supervisor rules: {}", e))
+}
+
+// 添加进程守护规则，返回新规则的 ID；下一次检查时开始守护
+#[tauri::command]
+async fn add_supervisor_rule(
+    state: State<'_, SystemState>,
+    rule: SupervisorRule,
+) -> Result<String, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    rule.validate()?;
+    
+    db_manager.add_supervisor_rule(&rule)
+        .await
+        .map_err(|e| format!("Failed to add supervisor rule: {}", e))
+}
+
+// 更新进程守护规则，停用或更换目标时停止由应用启动的进程，下一次检查按新规则重新启动
+#[tauri::command]
+async fn update_supervisor_rule(
+    state: State<'_, SystemState>,
+    supervisor: State<'_, Supervisor>,
+    id: String,
+    rule: SupervisorRule,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    rule.validate()?;
+    
+    let previous = db_manager.get_supervisor_rules()
+        .await
+        .map_err(|e| format!("Failed to get supervisor rules: {}", e))?
+        .into_iter()
+        .find(|existing| existing.id == id);
+    
+    db_manager.update_supervisor_rule(&id, &rule)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => format!("Supervisor rule {} not found", id),
+            e => format!("Failed to update supervisor rule: {}", e),
+        })?;
+    
+    let target_changed = previous.map(|previous| previous.target).as_ref() != Some(&rule.target);
+    if !rule.enabled || target_changed {
+        if let Some(event) = supervisor.stop(&id, Utc::now()) {
+            db_manager.record_supervisor_events(&[event])
+                .await
+                .map_err(|e| format!("Failed to record supervisor event: {}", e))?;
+        }
+    }
+    Ok(())
+}
+
+// 删除进程守护规则，并停止由应用启动的进程；已记录的事件保留
+#[tauri::command]
+async fn delete_supervisor_rule(
+    state: State<'_, SystemState>,
+    supervisor: State<'_, Supervisor>,
+    id: String,
+) -> Result<(), String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.delete_supervisor_rule(&id)
+        .await
+        .map_err(|e| format!("Failed to delete supervisor rule: {}", e))?;
+    
+    if let Some(event) = supervisor.stop(&id, Utc::now()) {
+        db_manager.record_supervisor_events(&[event])
+            .await
+            .map_err(|e| format!("Failed to record supervisor event: {}", e))?;
+    }
+    Ok(())
+}
+
+// 执行一次进程守护检查并记录产生的事件
+async fn supervise_once(db_manager: &DatabaseManager, supervisor: &Supervisor) -> Result<Vec<SupervisorEvent>, String> {
+    let rules = db_manager.get_supervisor_rules()
+        .await
+        .map_err(|e| format!("Failed to get supervisor rules: {}", e))?;
+    let supervisor = supervisor.clone();
+    let events = tokio::task::spawn_blocking(move || supervisor.tick(&rules, &ProcessEntry::snapshot(), Utc::now()))
+        .await
+        .map_err(|e| format!("Failed to run supervisor: {}", e))?;
+    
+    db_manager.record_supervisor_events(&events)
+        .await
+        .map_err(|e| format!("Failed to record supervisor events: {}", e))?;
+    Ok(events)
+}
+
+// 后台进程守护任务：先按已记录的事件恢复重启次数和放弃状态，再每 CHECK_INTERVAL_SECS 秒检查一次
+async fn run_supervisor_loop(db_manager: DatabaseManager, supervisor: Supervisor) {
+    match db_manager.get_supervisor_rules().await {
+        Ok(rules) => {
+            for rule in rules.iter().filter(|rule| rule.enabled) {
+                match db_manager.get_supervisor_events(Some(&rule.id), 100).await {
+                    Ok(events) => supervisor.restore(rule, &events),
+                    Err(e) => eprintln!("Failed to restore supervisor rule {}: {}", rule.id, e),
                 }
-            },
-            Err(e) => Err(format!("Failed to execute terminate command: {}", e)),
+            }
         }
-    }).await;
+        Err(e) => eprintln!("Failed to restore supervisor rules: {}", e),
+    }
     
-    match result {
-        Ok(res) => res,
-        Err(e) => Err(format!("终止进程时发生错误: {}", e)),
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(CHECK_INTERVAL_SECS));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    loop {
+        interval.tick().await;
+        if let Err(e) = supervise_once(&db_manager, &supervisor).await {
+            eprintln!("Supervisor check failed: {}", e);
+        }
+    }
+}
+
+// 立即执行一次进程守护检查：记录退出和重启事件并返回；后台任务也会周期性检查
+#[tauri::command]
+async fn run_supervisor(
+    state: State<'_, SystemState>,
+    supervisor: State<'_, Supervisor>,
+) -> Result<Vec<SupervisorEvent>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    supervise_once(db_manager, supervisor.inner()).await
+}
+
+// 获取各进程守护规则的当前状态
+#[tauri::command]
+async fn get_supervisor_status(
+    state: State<'_, SystemState>,
+    supervisor: State<'_, Supervisor>,
+) -> Result<Vec<SupervisorStatus>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    let (rules, invalid) = db_manager.load_supervisor_rules()
+        .await
+        .map_err(|e| format!("Failed to get supervisor rules: {}", e))?;
+    let mut statuses = supervisor.status(&rules);
+    statuses.extend(invalid.iter().map(InvalidSupervisorRule::status));
+    Ok(statuses)
+}
+
+// 获取最近的进程守护事件，可按规则过滤
+#[tauri::command]
+async fn get_supervisor_events(
+    state: State<'_, SystemState>,
+    rule_id: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<SupervisorEvent>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    
+    db_manager.get_supervisor_events(rule_id.as_deref(), limit.unwrap_or(100))
+        .await
+        .map_err(|e| format!("Failed to get supervisor events: {}", e))
+}
+
+// 列出系统用户及其所属组，供按用户过滤进程时选择
+#[tauri::command]
+async fn get_system_users() -> Result<Vec<UserInfo>, String> {
+    tokio::task::spawn_blocking(list_users)
+        .await
+        .map_err(|e| format!("Failed to list users: {}", e))
+}
+
+// 磁盘 I/O 排行：采样一段时间内各进程的读写速率，返回读写最多的进程
+#[tauri::command]
+async fn get_io_top(limit: Option<usize>, sample_interval_ms: Option<u64>) -> Result<Vec<ProcessIo>, String> {
+    // 采样间隔限制在 200ms 到 5s 之间
+    let interval = std::time::Duration::from_millis(sample_interval_ms.unwrap_or(1000).clamp(200, 5000));
+    let limit = limit.unwrap_or(15);
+    
+    tokio::task::spawn_blocking(move || io_top(interval, limit))
+        .await
+        .map_err(|e| format!("Failed to sample process I/O: {}", e))
+}
+
+// 列出进程打开的文件描述符
+#[tauri::command]
+async fn get_process_open_files(pid: String) -> Result<ProcessFds, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    if !fds::is_supported() {
+        return Err("Listing open files requires /proc and is only supported on Linux".to_string());
+    }
+    
+    tokio::task::spawn_blocking(move || fds::list_fds(pid_num))
+        .await
+        .map_err(|e| format!("Failed to list open files: {}", e))?
+}
+
+// 查找打开了某个文件或目录的进程，类似 lsof <path>
+#[tauri::command]
+async fn find_file_holders(path: String) -> Result<FileHolders, String> {
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+    if !fds::is_supported() {
+        return Err("Finding open files requires /proc and is only supported on Linux".to_string());
+    }
+    
+    tokio::task::spawn_blocking(move || fds::find_fd_holders(path.trim()))
+        .await
+        .map_err(|e| format!("Failed to find file holders: {}", e))
+}
+
+// 按文件描述符数量列出进程，用于排查泄漏
+#[tauri::command]
+async fn get_fd_usage(limit: Option<usize>) -> Result<Vec<FdUsage>, String> {
+    if !fds::is_supported() {
+        return Err("File descriptor usage requires /proc and is only supported on Linux".to_string());
     }
+    
+    let mut usage = tokio::task::spawn_blocking(fds::fd_usage)
+        .await
+        .map_err(|e| format!("Failed to get file descriptor usage: {}", e))?;
+    usage.truncate(limit.unwrap_or(20));
+    Ok(usage)
+}
+
+// 获取进程的 nice 值和调度策略
+#[tauri::command]
+async fn get_process_priority(pid: String) -> Result<ProcessPriority, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    
+    tokio::task::spawn_blocking(move || read_priority(pid_num))
+        .await
+        .map_err(|e| format!("Failed to get process priority: {}", e))?
+}
+
+// 修改进程的 nice 值，成功与失败都记入审计日志
+#[tauri::command]
+async fn set_process_priority(
+    state: State<'_, SystemState>,
+    pid: String,
+    nice: i32,
+) -> Result<ProcessPriority, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    
+    let (process_name, previous, result) = tokio::task::spawn_blocking(move || {
+        let previous = read_priority(pid_num).ok();
+        (process::process_name(pid_num), previous, set_nice(pid_num, nice))
+    })
+    .await
+    .map_err(|e| format!("Failed to set process priority: {}", e))?;
+    
+    let entry = ProcessAuditEntry::record(
+        pid_num,
+        process_name,
+        ProcessAuditAction::Renice,
+        previous.map(|priority| priority.nice.to_string()),
+        nice.to_string(),
+        &result,
+    );
+    db_manager.record_process_audit(&entry)
+        .await
+        .map_err(|e| format!("Failed to record process audit entry: {}", e))?;
+    result
+}
+
+// 获取进程的 CPU 亲和性
+#[tauri::command]
+async fn get_process_affinity(pid: String) -> Result<CpuAffinity, String> {
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    
+    tokio::task::spawn_blocking(move || get_affinity(pid_num))
+        .await
+        .map_err(|e| format!("Failed to get CPU affinity: {}", e))?
+}
+
+// 设置进程的 CPU 亲和性，cpus 为 "0-3,6" 形式的列表；成功与失败都记入审计日志
+#[tauri::command]
+async fn set_process_affinity(
+    state: State<'_, SystemState>,
+    pid: String,
+    cpus: String,
+) -> Result<CpuAffinity, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let pid_num = pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string())?;
+    let cpu_numbers = parse_cpu_list(&cpus)?;
+    let requested = format_cpu_list(&cpu_numbers);
+    
+    let (process_name, previous, result) = tokio::task::spawn_blocking(move || {
+        let previous = get_affinity(pid_num).ok();
+        (process::process_name(pid_num), previous, set_affinity(pid_num, &cpu_numbers))
+    })
+    .await
+    .map_err(|e| format!("Failed to set CPU affinity: {}", e))?;
+    
+    let entry = ProcessAuditEntry::record(
+        pid_num,
+        process_name,
+        ProcessAuditAction::SetAffinity,
+        previous.map(|affinity| affinity.cpu_list),
+        requested,
+        &result,
+    );
+    db_manager.record_process_audit(&entry)
+        .await
+        .map_err(|e| format!("Failed to record process audit entry: {}", e))?;
+    result
+}
+
+// 获取进程操作审计记录，可按 PID 过滤
+#[tauri::command]
+async fn get_process_audit_log(
+    state: State<'_, SystemState>,
+    pid: Option<String>,
+    limit: Option<u32>,
+) -> Result<Vec<ProcessAuditEntry>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let pid_num = pid
+        .map(|pid| pid.parse::<u32>().map_err(|_| "Invalid PID format".to_string()))
+        .transpose()?;
+    
+    db_manager.get_process_audit_log(pid_num, limit.unwrap_or(100))
+        .await
+        .map_err(|e| format!("Failed to get process audit log: {}", e))
 }
 
 // 保持向后兼容性的原始get_processes函数
@@ -1273,7 +2606,11 @@ async fn get_processes(_state: State<'_, SystemState>) -> Result<Vec<HashMap<Str
         
         let mut processes = Vec::new();
         
-        for (pid, process) in sys.processes() {
+        let processes_only = sys
+            .processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind() != Some(sysinfo::ThreadKind::Userland));
+        for (pid, process) in processes_only {
             let mut process_map = HashMap::new();
             
             process_map.insert("pid".to_string(), pid.as_u32().to_string());
@@ -1444,21 +2781,65 @@ async fn store_historical_data(
     let db_manager = state.db_manager.as_ref()
         .ok_or_else(|| "Database not initialized".to_string())?;
     
+    // 文件描述符数量由后端采集，用于泄漏告警
+    let open_fds = tokio::task::spawn_blocking(|| fds::is_supported().then(fds::max_fd_count))
+        .await
+        .map_err(|e| format!("Failed to count open file descriptors: {}", e))?;
+    // cgroup 用量同样由后端采集，只记录根下的 slice 及其中的单元
+    let cgroups = tokio::task::spawn_blocking(|| {
+        cgroup_root().map(|root| collect_cgroups(&root, CGROUP_HISTORY_DEPTH)).unwrap_or_default()
+    })
+    .await
+    .map_err(|e| format!("Failed to read cgroup usage: {}", e))?;
+    
+    let timestamp = Utc::now();
     let historical_data = HistoricalSystemData {
         id: Uuid::new_v4().to_string(),
-        timestamp: Utc::now(),
+        timestamp,
         cpu_usage,
         memory_usage,
         memory_total,
         disk_usage,
         network_traffic,
         system_load,
+        open_fds,
     };
     
     db_manager.store_historical_data(&historical_data)
         .await
-        .map(|_| historical_data.id)
-        .map_err(|e| format!("Failed to store historical data: {}", e))
+        .map_err(|e| format!("Failed to store historical data: {}", e))?;
+    
+    // 系统数据已保存，cgroup 用量保存失败只记录日志，调用方仍能取得记录 ID
+    let samples: Vec<CgroupSample> = cgroups.iter().map(|stats| CgroupSample::from_stats(timestamp, stats)).collect();
+    if let Err(e) = db_manager.store_cgroup_samples(&historical_data.id, &samples).await {
+        eprintln!("Failed to store cgroup usage for {}: {}", historical_data.id, e);
+    }
+    Ok(historical_data.id)
+}
+
+// 获取一个 cgroup 在时间范围内的用量序列
+#[tauri::command]
+async fn fetch_cgroup_history(
+    state: State<'_, SystemState>,
+    path: String,
+    start_time: String,
+    end_time: String,
+) -> Result<Vec<CgroupSample>, String> {
+    let db_manager = state.db_manager.as_ref()
+        .ok_or_else(|| "Database not initialized".to_string())?;
+    let path = normalize_cgroup_path(&path)?;
+    
+    let start_dt = DateTime::parse_from_rfc3339(&start_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid start time format: {}", e))?;
+    
+    let end_dt = DateTime::parse_from_rfc3339(&end_time)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid end time format: {}", e))?;
+    
+    db_manager.fetch_cgroup_history(&path, start_dt, end_dt)
+        .await
+        .map_err(|e| format!("Failed to fetch cgroup history: {}", e))
 }
 
 // 获取历史数据命令
@@ -1537,13 +2918,13 @@ async fn get_database_stats(
 // 初始化数据库命令
 #[tauri::command]
 async fn init_database(
-    _state: State<'_, SystemState>,
+    state: State<'_, SystemState>,
     _app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
-    // 注意：这里我们需要一个可变引用，但State提供了不可变引用
-    // 这是一个限制，我们需要在应用启动时初始化数据库
-    // 或者使用其他方法来处理可变状态
-    Err("Database should be initialized during app startup".to_string())
+    // 数据库在应用启动时初始化，这里只报告是否初始化成功
+    state.db_manager.as_ref()
+        .map(|_| ())
+        .ok_or_else(|| "Database failed to initialize during app startup".to_string())
 }
 
 // 初始化Tauri应用
@@ -1551,12 +2932,24 @@ async fn init_database(
 pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
-        .manage(SystemState::default())
+        .manage(Supervisor::default())
         .setup(|app| {
-            // 初始化数据库
-            let _state = app.state::<SystemState>();
-            // 注意：由于State的限制，我们需要在setup中处理数据库初始化
-            // 这里我们暂时跳过数据库初始化，在实际使用时再初始化
+            // 数据库只在启动时打开一次，命令和后台进程守护共用同一个管理器
+            let mut state = SystemState::default();
+            if let Err(e) = tauri::async_runtime::block_on(state.init_database(app.app_handle().clone())) {
+                eprintln!("{}", e);
+            }
+            let db_manager = state.db_manager.clone();
+            app.manage(state);
+            
+            // 进程守护在后台周期性运行，不依赖前端调用
+            let supervisor = app.state::<Supervisor>().inner().clone();
+            match db_manager {
+                Some(db_manager) => {
+                    tauri::async_runtime::spawn(run_supervisor_loop(db_manager, supervisor));
+                }
+                None => eprintln!("Supervisor disabled, database is not available"),
+            }
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -1573,6 +2966,32 @@ pub fn run() {
             get_processes_enhanced,
             get_process_details,
             terminate_process,
+            send_process_signal,
+            terminate_process_gracefully,
+            get_process_threads,
+            get_io_top,
+            get_system_users,
+            get_process_memory_map,
+            get_process_environment,
+            get_process_limits,
+            raise_process_soft_limit,
+            get_cgroup_usage,
+            get_cgroup_node,
+            get_supervisor_rules,
+            add_supervisor_rule,
+            update_supervisor_rule,
+            delete_supervisor_rule,
+            run_supervisor,
+            get_supervisor_status,
+            get_supervisor_events,
+            get_process_open_files,
+            find_file_holders,
+            get_fd_usage,
+            get_process_priority,
+            set_process_priority,
+            get_process_affinity,
+            set_process_affinity,
+            get_process_audit_log,
             get_gpu_info,
             get_network_connections,
             diagnose_network_ping,
@@ -1580,12 +2999,47 @@ pub fn run() {
             get_alert_configurations,
             add_alert_configuration,
             update_alert_configuration,
+            migrate_alert_configurations,
+            get_startup_migration_report,
             delete_alert_configuration,
+            export_alert_configurations,
+            import_alert_configurations,
+            import_prometheus_rules,
             get_alert_history,
             acknowledge_alert,
+            acknowledge_alerts,
+            get_alert_statistics,
             check_alerts,
+            get_default_alert_template,
+            preview_alert_message,
+            get_metric_baseline,
+            detect_metric_anomalies,
+            forecast_resource_usage,
+            get_alert_silences,
+            add_alert_silence,
+            update_alert_silence,
+            delete_alert_silence,
+            get_escalation_policies,
+            add_escalation_policy,
+            update_escalation_policy,
+            delete_escalation_policy,
+            process_alert_escalations,
+            get_alert_escalations,
+            get_remediation_results,
+            backtest_alert_configuration,
+            test_fire_alert,
+            get_incidents,
+            get_incident,
+            acknowledge_incident,
+            resolve_incident,
+            add_incident_note,
+            plan_notifications,
+            get_notification_digests,
+            get_process_tree,
+            get_process_lineage,
             store_historical_data,
             fetch_historical_data,
+            fetch_cgroup_history,
             export_historical_data,
             prune_historical_data,
             get_database_stats,
@@ -0,0 +1,77 @@
+//! 进程操作审计
+//! 记录对进程优先级、CPU 亲和性、资源限制等属性的每次修改，失败的尝试同样记录
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// 被审计的操作类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessAuditAction {
+    Renice,
+    SetAffinity,
+    /// 提高资源的软限制
+    SetLimit,
+}
+
+impl ProcessAuditAction {
+    /// 操作的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Renice => "renice",
+            Self::SetAffinity => "set_affinity",
+            Self::SetLimit => "set_limit",
+        }
+    }
+
+    /// 按存储名称解析操作
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "renice" => Some(Self::Renice),
+            "set_affinity" => Some(Self::SetAffinity),
+            "set_limit" => Some(Self::SetLimit),
+            _ => None,
+        }
+    }
+}
+
+// 一条审计记录
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessAuditEntry {
+    pub id: String,
+    pub pid: u32,
+    pub process_name: Option<String>,
+    pub action: ProcessAuditAction,
+    /// 修改前的值，无法读取时为空
+    pub previous_value: Option<String>,
+    /// 请求设置的值
+    pub requested_value: String,
+    pub success: bool,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ProcessAuditEntry {
+    /// 按操作结果生成审计记录
+    pub fn record<T>(
+        pid: u32,
+        process_name: Option<String>,
+        action: ProcessAuditAction,
+        previous_value: Option<String>,
+        requested_value: String,
+        result: &Result<T, String>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            pid,
+            process_name,
+            action,
+            previous_value,
+            requested_value,
+            success: result.is_ok(),
+            error: result.as_ref().err().cloned(),
+            created_at: Utc::now(),
+        }
+    }
+}
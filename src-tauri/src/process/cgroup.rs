@@ -0,0 +1,206 @@
+//! 进程所属的 cgroup、systemd 单元与容器
+//! 解析 /proc/<pid>/cgroup：cgroup v2 只有 "0::<path>" 一行，v1 与混合模式下
+//! 优先使用 name=systemd 层级的路径；容器 ID 从 Docker、Podman、containerd、CRI-O 和
+//! Kubernetes 的路径命名中识别
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ProcessEntry;
+
+// 容器运行时
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerRuntime {
+    Docker,
+    Podman,
+    Containerd,
+    Crio,
+    /// kubepods 下无法确定具体运行时的容器
+    Kubernetes,
+}
+
+// 从 /proc/<pid>/cgroup 解析出的归属
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct CgroupInfo {
+    pub path: String,
+    /// 路径中最深的 service、scope 等单元，只有 slice 时为空
+    pub systemd_unit: Option<String>,
+    /// 完整的 64 位十六进制容器 ID
+    pub container_id: Option<String>,
+    pub container_runtime: Option<ContainerRuntime>,
+}
+
+// 聚合的分组方式
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CgroupGrouping {
+    #[default]
+    Unit,
+    Container,
+}
+
+// 一个单元或容器中所有进程的合计
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CgroupUsage {
+    /// 单元名或容器 ID
+    pub key: String,
+    pub container_runtime: Option<ContainerRuntime>,
+    pub process_count: u32,
+    pub cpu_usage_percent: f32,
+    pub memory_usage_bytes: u64,
+    pub pids: Vec<u32>,
+}
+
+// systemd 单元的后缀，slice 只是分组，不作为进程的单元
+const UNIT_SUFFIXES: &[&str] = &[".service", ".scope", ".socket", ".mount", ".swap"];
+
+// 运行时在 systemd 驱动下使用的 scope 前缀
+const SCOPE_PREFIXES: &[(&str, ContainerRuntime)] = &[
+    ("docker-", ContainerRuntime::Docker),
+    ("libpod-", ContainerRuntime::Podman),
+    ("cri-containerd-", ContainerRuntime::Containerd),
+    ("crio-", ContainerRuntime::Crio),
+];
+
+const CONTAINER_ID_LENGTH: usize = 64;
+
+fn is_container_id(value: &str) -> bool {
+    value.len() == CONTAINER_ID_LENGTH && value.bytes().all(|byte| byte.is_ascii_hexdigit())
+}
+
+/// 取路径中最深的 systemd 单元
+pub fn systemd_unit(path: &str) -> Option<String> {
+    path.split('/')
+        .rev()
+        .find(|component| UNIT_SUFFIXES.iter().any(|suffix| component.ends_with(suffix)))
+        .map(str::to_string)
+}
+
+/// 从 cgroup 路径识别容器 ID 和运行时
+pub fn container_from_path(path: &str) -> Option<(String, Option<ContainerRuntime>)> {
+    let components: Vec<&str> = path.split('/').filter(|component| !component.is_empty()).collect();
+    let in_kubepods = components.iter().any(|component| component.starts_with("kubepods"));
+    for (index, component) in components.iter().enumerate().rev() {
+        let name = component.strip_suffix(".scope").unwrap_or(component);
+        // systemd 驱动：docker-<id>.scope、cri-containerd-<id>.scope 等
+        if let Some((id, runtime)) = SCOPE_PREFIXES
+            .iter()
+            .find_map(|(prefix, runtime)| Some((name.strip_prefix(prefix)?, *runtime)))
+        {
+            if is_container_id(id) {
+                return Some((id.to_string(), Some(runtime)));
+            }
+        }
+        // cgroupfs 驱动：/docker/<id>、/kubepods/burstable/pod<uid>/<id>
+        if is_container_id(name) {
+            let runtime = match index.checked_sub(1).map(|parent| components[parent]) {
+                Some("docker") => Some(ContainerRuntime::Docker),
+                Some("libpod_parent") => Some(ContainerRuntime::Podman),
+                _ if in_kubepods => Some(ContainerRuntime::Kubernetes),
+                _ => None,
+            };
+            return Some((name.to_string(), runtime));
+        }
+    }
+    None
+}
+
+/// 解析 /proc/<pid>/cgroup 的内容，内容为空时返回 None
+pub fn parse_proc_cgroup(content: &str) -> Option<CgroupInfo> {
+    // (层级编号, 控制器, 路径)
+    let entries: Vec<(&str, &str, &str)> = content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(3, ':');
+            Some((parts.next()?, parts.next()?, parts.next()?))
+        })
+        .collect();
+    let systemd = entries.iter().find(|(_, controllers, _)| *controllers == "name=systemd");
+    let unified = entries.iter().find(|(hierarchy, controllers, _)| *hierarchy == "0" && controllers.is_empty());
+    let path = systemd.or(unified).or(entries.first())?.2;
+
+    // 所选路径中没有容器时检查其余层级，v1 下容器可能只出现在部分控制器中
+    let container = container_from_path(path)
+        .or_else(|| entries.iter().find_map(|(_, _, other)| container_from_path(other)));
+    Some(CgroupInfo {
+        path: path.to_string(),
+        systemd_unit: systemd_unit(path),
+        container_id: container.as_ref().map(|(id, _)| id.clone()),
+        container_runtime: container.and_then(|(_, runtime)| runtime),
+    })
+}
+
+/// 读取进程的 cgroup 归属，进程不存在或平台不支持时返回 None
+pub fn read_cgroup(pid: u32) -> Option<CgroupInfo> {
+    std::fs::read_to_string(format!("/proc/{}/cgroup", pid))
+        .ok()
+        .and_then(|content| parse_proc_cgroup(&content))
+}
+
+/// 单元过滤：单元名包含过滤文本（不区分大小写）
+pub fn unit_matches(filter: &str, unit: Option<&str>) -> bool {
+    let filter = filter.trim().to_lowercase();
+    unit.is_some_and(|unit| unit.to_lowercase().contains(&filter))
+}
+
+/// 容器过滤：容器 ID 以过滤文本开头，可使用 12 位的短 ID
+pub fn container_matches(filter: &str, container_id: Option<&str>) -> bool {
+    let filter = filter.trim().to_lowercase();
+    !filter.is_empty() && container_id.is_some_and(|id| id.starts_with(&filter))
+}
+
+/// 按单元或容器合计 CPU 和内存，不属于任何单元或容器的进程不计入；结果按 CPU 从高到低排列
+pub fn aggregate_cgroups(processes: &[(ProcessEntry, CgroupInfo)], grouping: CgroupGrouping) -> Vec<CgroupUsage> {
+    let mut groups: Vec<CgroupUsage> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for (entry, info) in processes {
+        let key = match grouping {
+            CgroupGrouping::Unit => info.systemd_unit.as_ref(),
+            CgroupGrouping::Container => info.container_id.as_ref(),
+        };
+        let Some(key) = key else {
+            continue;
+        };
+        let position = *index.entry(key.clone()).or_insert_with(|| {
+            groups.push(CgroupUsage {
+                key: key.clone(),
+                container_runtime: None,
+                process_count: 0,
+                cpu_usage_percent: 0.0,
+                memory_usage_bytes: 0,
+                pids: Vec::new(),
+            });
+            groups.len() - 1
+        });
+        let group = &mut groups[position];
+        group.container_runtime = group.container_runtime.or(info.container_runtime);
+        group.process_count += 1;
+        group.cpu_usage_percent += entry.cpu_usage_percent;
+        group.memory_usage_bytes += entry.memory_usage_bytes;
+        group.pids.push(entry.pid);
+    }
+    for group in &mut groups {
+        group.pids.sort_unstable();
+    }
+    groups.sort_by(|a, b| {
+        b.cpu_usage_percent
+            .total_cmp(&a.cpu_usage_percent)
+            .then(b.memory_usage_bytes.cmp(&a.memory_usage_bytes))
+            .then(a.key.cmp(&b.key))
+    });
+    groups
+}
+
+/// 采样所有进程并按单元或容器合计
+pub fn cgroup_usage(grouping: CgroupGrouping) -> Vec<CgroupUsage> {
+    let processes: Vec<(ProcessEntry, CgroupInfo)> = ProcessEntry::snapshot()
+        .into_iter()
+        .filter_map(|entry| {
+            let info = read_cgroup(entry.pid)?;
+            Some((entry, info))
+        })
+        .collect();
+    aggregate_cgroups(&processes, grouping)
+}
@@ -0,0 +1,258 @@
+//! cgroup v2 层级浏览
+//! 读取 /sys/fs/cgroup（混合模式下为 /sys/fs/cgroup/unified）中各 cgroup 的
+//! memory.current、memory.max、cpu.stat、io.stat 和 pids.current；
+//! 控制器未启用时对应的文件不存在，相应字段为空
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 记入历史数据的层级深度：根下的 slice 及其中的单元
+pub const CGROUP_HISTORY_DEPTH: usize = 2;
+
+// cpu.stat 中的累计值，单位为微秒
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct CgroupCpuStat {
+    pub usage_usec: u64,
+    pub user_usec: u64,
+    pub system_usec: u64,
+    /// 以下三项仅在启用 cpu 控制器时存在
+    pub nr_periods: u64,
+    pub nr_throttled: u64,
+    pub throttled_usec: u64,
+}
+
+impl CgroupCpuStat {
+    /// 被限流的调度周期所占比例
+    pub fn throttled_ratio(&self) -> f64 {
+        if self.nr_periods == 0 {
+            0.0
+        } else {
+            self.nr_throttled as f64 / self.nr_periods as f64
+        }
+    }
+}
+
+// io.stat 中一个块设备的累计值
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CgroupIoDevice {
+    /// "major:minor"
+    pub device: String,
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+    pub read_ios: u64,
+    pub write_ios: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CgroupStats {
+    /// 相对于层级根的路径，根为 "/"，与 /proc/<pid>/cgroup 中的路径一致
+    pub path: String,
+    pub name: String,
+    pub depth: usize,
+    pub memory_current_bytes: Option<u64>,
+    /// None 表示不限制或未启用 memory 控制器
+    pub memory_max_bytes: Option<u64>,
+    pub cpu: Option<CgroupCpuStat>,
+    pub io_read_bytes: u64,
+    pub io_written_bytes: u64,
+    pub io_devices: Vec<CgroupIoDevice>,
+    pub pids_current: Option<u64>,
+    pub pids_max: Option<u64>,
+    pub child_count: u32,
+}
+
+// 浏览时的一个节点：当前 cgroup 及其直接子 cgroup
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CgroupNode {
+    pub cgroup: CgroupStats,
+    /// 按名称排列
+    pub children: Vec<CgroupStats>,
+}
+
+// 历史数据中的一个 cgroup 采样
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CgroupSample {
+    pub timestamp: DateTime<Utc>,
+    pub path: String,
+    pub memory_current_bytes: Option<u64>,
+    pub memory_max_bytes: Option<u64>,
+    pub cpu_usage_usec: Option<u64>,
+    pub cpu_nr_periods: Option<u64>,
+    pub cpu_nr_throttled: Option<u64>,
+    pub cpu_throttled_usec: Option<u64>,
+    pub io_read_bytes: u64,
+    pub io_written_bytes: u64,
+    pub pids_current: Option<u64>,
+}
+
+impl CgroupSample {
+    pub fn from_stats(timestamp: DateTime<Utc>, stats: &CgroupStats) -> Self {
+        Self {
+            timestamp,
+            path: stats.path.clone(),
+            memory_current_bytes: stats.memory_current_bytes,
+            memory_max_bytes: stats.memory_max_bytes,
+            cpu_usage_usec: stats.cpu.map(|cpu| cpu.usage_usec),
+            cpu_nr_periods: stats.cpu.map(|cpu| cpu.nr_periods),
+            cpu_nr_throttled: stats.cpu.map(|cpu| cpu.nr_throttled),
+            cpu_throttled_usec: stats.cpu.map(|cpu| cpu.throttled_usec),
+            io_read_bytes: stats.io_read_bytes,
+            io_written_bytes: stats.io_written_bytes,
+            pids_current: stats.pids_current,
+        }
+    }
+}
+
+/// 解析 memory.current、memory.max 等单值文件，"max" 为 None
+pub fn parse_limit(content: &str) -> Option<u64> {
+    content.trim().parse().ok()
+}
+
+/// 解析 cpu.stat 的 "key value" 行，未知的键被忽略
+pub fn parse_cpu_stat(content: &str) -> CgroupCpuStat {
+    let mut stat = CgroupCpuStat::default();
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(' ') else {
+            continue;
+        };
+        let Ok(value) = value.trim().parse::<u64>() else {
+            continue;
+        };
+        match key {
+            "usage_usec" => stat.usage_usec = value,
+            "user_usec" => stat.user_usec = value,
+            "system_usec" => stat.system_usec = value,
+            "nr_periods" => stat.nr_periods = value,
+            "nr_throttled" => stat.nr_throttled = value,
+            "throttled_usec" => stat.throttled_usec = value,
+            _ => {}
+        }
+    }
+    stat
+}
+
+/// 解析 io.stat："8:0 rbytes=1 wbytes=2 rios=3 wios=4 dbytes=0 dios=0"，按设备号排列
+pub fn parse_io_stat(content: &str) -> Vec<CgroupIoDevice> {
+    let mut devices: Vec<CgroupIoDevice> = content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let mut device = CgroupIoDevice {
+                device: fields.next()?.to_string(),
+                read_bytes: 0,
+                written_bytes: 0,
+                read_ios: 0,
+                write_ios: 0,
+            };
+            for (key, value) in fields.filter_map(|field| field.split_once('=')) {
+                let value = value.parse().unwrap_or(0);
+                match key {
+                    "rbytes" => device.read_bytes = value,
+                    "wbytes" => device.written_bytes = value,
+                    "rios" => device.read_ios = value,
+                    "wios" => device.write_ios = value,
+                    _ => {}
+                }
+            }
+            Some(device)
+        })
+        .collect();
+    devices.sort_by(|a, b| a.device.cmp(&b.device));
+    devices
+}
+
+/// 本机的 cgroup v2 层级根；只挂载了 v1 时返回 None
+pub fn cgroup_root() -> Option<PathBuf> {
+    ["/sys/fs/cgroup", "/sys/fs/cgroup/unified"]
+        .into_iter()
+        .map(PathBuf::from)
+        .find(|root| root.join("cgroup.controllers").is_file())
+}
+
+/// 规范化相对于层级根的路径，拒绝 "." 和 ".." 以免越出层级
+pub fn normalize_cgroup_path(path: &str) -> Result<String, String> {
+    let components: Vec<&str> = path.split('/').filter(|component| !component.is_empty()).collect();
+    if components.iter().any(|component| *component == "." || *component == "..") {
+        return Err(format!("Invalid cgroup path: {}", path));
+    }
+    Ok(format!("/{}", components.join("/")))
+}
+
+fn read_file(dir: &Path, name: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(name)).ok()
+}
+
+fn child_dirs(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.file_type().is_ok_and(|file_type| file_type.is_dir()))
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+/// 读取一个 cgroup 的资源用量，`path` 相对于层级根 `root`
+pub fn read_cgroup_stats(root: &Path, path: &str) -> Result<CgroupStats, String> {
+    let path = normalize_cgroup_path(path)?;
+    let dir = root.join(path.trim_start_matches('/'));
+    if !dir.is_dir() {
+        return Err(format!("Cgroup {} not found", path));
+    }
+
+    let io_devices = read_file(&dir, "io.stat").map(|content| parse_io_stat(&content)).unwrap_or_default();
+    let depth = path.split('/').filter(|component| !component.is_empty()).count();
+    Ok(CgroupStats {
+        name: path.rsplit('/').next().filter(|name| !name.is_empty()).unwrap_or("/").to_string(),
+        depth,
+        memory_current_bytes: read_file(&dir, "memory.current").and_then(|content| parse_limit(&content)),
+        memory_max_bytes: read_file(&dir, "memory.max").and_then(|content| parse_limit(&content)),
+        cpu: read_file(&dir, "cpu.stat").map(|content| parse_cpu_stat(&content)),
+        io_read_bytes: io_devices.iter().map(|device| device.read_bytes).sum(),
+        io_written_bytes: io_devices.iter().map(|device| device.written_bytes).sum(),
+        io_devices,
+        pids_current: read_file(&dir, "pids.current").and_then(|content| parse_limit(&content)),
+        pids_max: read_file(&dir, "pids.max").and_then(|content| parse_limit(&content)),
+        child_count: child_dirs(&dir).len() as u32,
+        path,
+    })
+}
+
+fn join_cgroup_path(parent: &str, name: &str) -> String {
+    format!("{}/{}", parent.trim_end_matches('/'), name)
+}
+
+/// 读取一个 cgroup 及其直接子 cgroup，用于逐级浏览
+pub fn read_cgroup_node(root: &Path, path: &str) -> Result<CgroupNode, String> {
+    let cgroup = read_cgroup_stats(root, path)?;
+    let dir = root.join(cgroup.path.trim_start_matches('/'));
+    let children = child_dirs(&dir)
+        .into_iter()
+        .filter_map(|name| read_cgroup_stats(root, &join_cgroup_path(&cgroup.path, &name)).ok())
+        .collect();
+    Ok(CgroupNode { cgroup, children })
+}
+
+/// 按先序列出深度不超过 `max_depth` 的 cgroup，根的深度为 0
+pub fn collect_cgroups(root: &Path, max_depth: usize) -> Vec<CgroupStats> {
+    let mut cgroups = Vec::new();
+    let mut pending = vec!["/".to_string()];
+    while let Some(path) = pending.pop() {
+        let Ok(stats) = read_cgroup_stats(root, &path) else {
+            continue;
+        };
+        if stats.depth < max_depth {
+            let dir = root.join(path.trim_start_matches('/'));
+            // 逆序入栈，使子 cgroup 按名称顺序输出
+            pending.extend(child_dirs(&dir).iter().rev().map(|name| join_cgroup_path(&path, name)));
+        }
+        cgroups.push(stats);
+    }
+    cgroups
+}
@@ -0,0 +1,117 @@
+//! 进程环境变量
+//! 读取 /proc/<pid>/environ，即进程启动时的环境；进程运行中自行修改的变量不会反映在这里。
+//! 键名像是密钥的变量默认隐藏其值，隐藏规则可由调用方配置
+
+use serde::{Deserialize, Serialize};
+
+/// 隐藏后显示的值
+pub const MASKED_VALUE: &str = "********";
+
+// 默认视为敏感信息的键名片段
+const DEFAULT_SECRET_PATTERNS: &[&str] = &[
+    "PASSWORD",
+    "PASSWD",
+    "SECRET",
+    "TOKEN",
+    "API_KEY",
+    "APIKEY",
+    "ACCESS_KEY",
+    "PRIVATE_KEY",
+    "CREDENTIAL",
+    "AUTH",
+    "COOKIE",
+    "SESSION",
+];
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_patterns() -> Vec<String> {
+    DEFAULT_SECRET_PATTERNS.iter().map(|pattern| pattern.to_string()).collect()
+}
+
+// 敏感变量的隐藏规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EnvMasking {
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 键名包含其中任一片段（不区分大小写）时隐藏值
+    #[serde(default = "default_patterns")]
+    pub patterns: Vec<String>,
+}
+
+impl Default for EnvMasking {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            patterns: default_patterns(),
+        }
+    }
+}
+
+impl EnvMasking {
+    pub fn is_secret(&self, key: &str) -> bool {
+        if !self.enabled {
+            return false;
+        }
+        let key = key.to_uppercase();
+        self.patterns
+            .iter()
+            .map(|pattern| pattern.trim())
+            .any(|pattern| !pattern.is_empty() && key.contains(&pattern.to_uppercase()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct EnvVariable {
+    pub key: String,
+    /// 被隐藏时为 MASKED_VALUE
+    pub value: String,
+    pub masked: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessEnvironment {
+    pub pid: u32,
+    /// 按键名排列
+    pub variables: Vec<EnvVariable>,
+    pub masked_count: u32,
+}
+
+/// 解析 environ 的内容：以 NUL 分隔的 KEY=VALUE，非 UTF-8 字节按有损方式转换
+pub fn parse_environ(content: &[u8], masking: &EnvMasking) -> Vec<EnvVariable> {
+    let mut variables: Vec<EnvVariable> = content
+        .split(|byte| *byte == 0)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (key, value) = entry.split_once('=').unwrap_or((&entry, ""));
+            let masked = masking.is_secret(key);
+            EnvVariable {
+                key: key.to_string(),
+                value: if masked { MASKED_VALUE.to_string() } else { value.to_string() },
+                masked,
+            }
+        })
+        .collect();
+    variables.sort_by(|a, b| a.key.cmp(&b.key));
+    variables
+}
+
+/// 读取进程的环境变量；只能读取自己的进程，root 除外
+pub fn read_environment(pid: u32, masking: &EnvMasking) -> Result<ProcessEnvironment, String> {
+    let content = std::fs::read(format!("/proc/{}/environ", pid)).map_err(|error| match error.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Permission denied: cannot read environment of process {} owned by another user", pid)
+        }
+        _ => format!("Failed to read environment of process {}: {}", pid, error),
+    })?;
+    let variables = parse_environ(&content, masking);
+    Ok(ProcessEnvironment {
+        pid,
+        masked_count: variables.iter().filter(|variable| variable.masked).count() as u32,
+        variables,
+    })
+}
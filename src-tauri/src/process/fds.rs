@@ -0,0 +1,260 @@
+//! 打开的文件描述符
+//! 读取 /proc/<pid>/fd 的符号链接，按类型分类；反向查找打开了某个路径的进程，
+//! 以及按文件描述符数量排列进程，供泄漏告警使用
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+const DELETED_SUFFIX: &str = " (deleted)";
+
+// 文件描述符类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum FdKind {
+    File,
+    Socket,
+    Pipe,
+    /// eventfd、epoll、inotify 等没有实际文件的描述符
+    AnonInode,
+    /// 文件已被删除但仍处于打开状态，仍占用磁盘空间
+    Deleted,
+    Other,
+}
+
+// 一个打开的文件描述符
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct OpenFd {
+    pub fd: u32,
+    pub kind: FdKind,
+    /// 链接目标；已删除的文件去掉 " (deleted)" 后缀
+    pub target: String,
+    /// 普通文件和已删除文件的大小
+    pub size_bytes: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessFds {
+    pub pid: u32,
+    pub fd_count: u32,
+    pub counts_by_kind: BTreeMap<FdKind, u32>,
+    /// 按描述符编号排列
+    pub fds: Vec<OpenFd>,
+}
+
+// 打开了某个路径的进程
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FdHolder {
+    pub pid: u32,
+    pub process_name: String,
+    pub fd: u32,
+    pub kind: FdKind,
+    pub target: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FileHolders {
+    pub path: String,
+    pub holders: Vec<FdHolder>,
+    /// 因权限不足无法检查的进程数，不为 0 时结果可能不完整
+    pub inaccessible_processes: u32,
+}
+
+// 单个进程的文件描述符数量
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FdUsage {
+    pub pid: u32,
+    pub process_name: String,
+    pub fd_count: u32,
+}
+
+/// 按 /proc/<pid>/fd/<n> 的链接目标分类
+pub fn classify_fd_target(target: &str) -> (FdKind, String) {
+    if let Some(path) = target.strip_suffix(DELETED_SUFFIX).filter(|path| path.starts_with('/')) {
+        return (FdKind::Deleted, path.to_string());
+    }
+    let kind = if target.starts_with("socket:") {
+        FdKind::Socket
+    } else if target.starts_with("pipe:") {
+        FdKind::Pipe
+    } else if target.starts_with("anon_inode:") {
+        FdKind::AnonInode
+    } else if target.starts_with('/') {
+        FdKind::File
+    } else {
+        FdKind::Other
+    };
+    (kind, target.to_string())
+}
+
+/// 判断描述符是否指向 `path`；`path` 为目录时，目录下的文件同样算作匹配
+pub fn fd_matches_path(target: &str, path: &str) -> bool {
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        // 根目录
+        return target.starts_with('/');
+    }
+    target == path || target.strip_prefix(path).is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn fd_dir(pid: u32) -> String {
+    format!("/proc/{}/fd", pid)
+}
+
+fn read_error(pid: u32, error: std::io::Error) -> String {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Permission denied: cannot read open files of process {} owned by another user", pid)
+        }
+        _ => format!("Failed to read open files of process {}: {}", pid, error),
+    }
+}
+
+/// 读取进程打开的文件描述符；读取期间关闭的描述符会被跳过
+pub fn list_fds(pid: u32) -> Result<ProcessFds, String> {
+    let dir = fd_dir(pid);
+    let entries = std::fs::read_dir(&dir).map_err(|e| read_error(pid, e))?;
+
+    let mut fds = Vec::new();
+    for entry in entries.flatten() {
+        let Some(fd) = entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) else {
+            continue;
+        };
+        let Ok(link) = std::fs::read_link(entry.path()) else {
+            continue;
+        };
+        let (kind, target) = classify_fd_target(&link.to_string_lossy());
+        // 通过 /proc 中的链接取元数据，已删除的文件同样可以读取
+        let size_bytes = matches!(kind, FdKind::File | FdKind::Deleted)
+            .then(|| std::fs::metadata(entry.path()).ok())
+            .flatten()
+            .filter(|metadata| metadata.is_file())
+            .map(|metadata| metadata.len());
+        fds.push(OpenFd {
+            fd,
+            kind,
+            target,
+            size_bytes,
+        });
+    }
+    fds.sort_by_key(|open| open.fd);
+
+    let mut counts_by_kind = BTreeMap::new();
+    for open in &fds {
+        *counts_by_kind.entry(open.kind).or_insert(0) += 1;
+    }
+    Ok(ProcessFds {
+        pid,
+        fd_count: fds.len() as u32,
+        counts_by_kind,
+        fds,
+    })
+}
+
+/// 进程打开的文件描述符数量，无法读取时返回 None
+pub fn fd_count(pid: u32) -> Option<u32> {
+    std::fs::read_dir(fd_dir(pid)).ok().map(|entries| entries.count() as u32)
+}
+
+// /proc 下所有进程的 PID
+fn proc_pids() -> Vec<u32> {
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let mut pids: Vec<u32> = entries
+        .flatten()
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+fn proc_name(pid: u32) -> String {
+    std::fs::read_to_string(format!("/proc/{}/comm", pid))
+        .map(|name| name.trim_end().to_string())
+        .unwrap_or_default()
+}
+
+/// 查找打开了 `path` 的所有进程，包括已删除但仍打开的同名文件
+pub fn find_fd_holders(path: &str) -> FileHolders {
+    // 尽量解析为规范路径，使符号链接和相对路径也能匹配；文件已被删除时保留原路径
+    let path = std::fs::canonicalize(path)
+        .map(|canonical| canonical.to_string_lossy().to_string())
+        .unwrap_or_else(|_| path.to_string());
+
+    let mut holders = Vec::new();
+    let mut inaccessible_processes = 0;
+    for pid in proc_pids() {
+        let fds = match list_fds(pid) {
+            Ok(fds) => fds,
+            Err(error) if error.starts_with("Permission denied") => {
+                inaccessible_processes += 1;
+                continue;
+            }
+            Err(_) => continue,
+        };
+        let matching: Vec<OpenFd> = fds
+            .fds
+            .into_iter()
+            .filter(|open| matches!(open.kind, FdKind::File | FdKind::Deleted) && fd_matches_path(&open.target, &path))
+            .collect();
+        if matching.is_empty() {
+            continue;
+        }
+        let process_name = proc_name(pid);
+        holders.extend(matching.into_iter().map(|open| FdHolder {
+            pid,
+            process_name: process_name.clone(),
+            fd: open.fd,
+            kind: open.kind,
+            target: open.target,
+        }));
+    }
+
+    FileHolders {
+        path,
+        holders,
+        inaccessible_processes,
+    }
+}
+
+/// 按文件描述符数量从多到少列出可读取的进程
+pub fn fd_usage() -> Vec<FdUsage> {
+    let mut usage: Vec<FdUsage> = proc_pids()
+        .into_iter()
+        .filter_map(|pid| {
+            Some(FdUsage {
+                pid,
+                fd_count: fd_count(pid)?,
+                process_name: proc_name(pid),
+            })
+        })
+        .collect();
+    usage.sort_by(|a, b| b.fd_count.cmp(&a.fd_count).then(a.pid.cmp(&b.pid)));
+    usage
+}
+
+/// 单个进程持有的最大文件描述符数量，记录在历史数据中
+pub fn max_fd_count() -> u32 {
+    proc_pids().into_iter().filter_map(fd_count).max().unwrap_or(0)
+}
+
+/// 持有文件描述符最多的进程，作为 `fds` 告警指标；没有可读取的进程时返回 None
+pub fn busiest_process() -> Option<FdUsage> {
+    let (pid, fd_count) = proc_pids()
+        .into_iter()
+        .filter_map(|pid| Some((pid, fd_count(pid)?)))
+        .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(&a.0)))?;
+    Some(FdUsage {
+        pid,
+        process_name: proc_name(pid),
+        fd_count,
+    })
+}
+
+/// /proc 是否可用；不可用的平台上文件描述符相关功能返回空结果
+pub fn is_supported() -> bool {
+    Path::new("/proc/self/fd").is_dir()
+}
@@ -0,0 +1,83 @@
+//! 进程磁盘 I/O
+//! 基于 sysinfo 的 disk_usage（Linux 上读取 /proc/<pid>/io），两次刷新之间的增量换算为每秒速率；
+//! 无权读取其他用户进程的计数时各项为 0
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Process, System, ThreadKind};
+
+use super::refresh_twice;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessIo {
+    pub pid: u32,
+    pub name: String,
+    /// 进程启动以来累计读取的字节数
+    pub read_bytes: u64,
+    pub written_bytes: u64,
+    pub read_bytes_per_sec: f64,
+    pub written_bytes_per_sec: f64,
+}
+
+impl ProcessIo {
+    /// 由累计值和采样间隔内的增量计算速率
+    pub fn new(
+        pid: u32,
+        name: String,
+        (read_bytes, written_bytes): (u64, u64),
+        (read_delta, written_delta): (u64, u64),
+        elapsed: Duration,
+    ) -> Self {
+        let secs = elapsed.as_secs_f64();
+        let rate = |delta: u64| if secs > 0.0 { delta as f64 / secs } else { 0.0 };
+        Self {
+            pid,
+            name,
+            read_bytes,
+            written_bytes,
+            read_bytes_per_sec: rate(read_delta),
+            written_bytes_per_sec: rate(written_delta),
+        }
+    }
+
+    /// 从已刷新两次的 sysinfo 进程读取，`elapsed` 为两次刷新的间隔
+    pub fn from_process(pid: u32, process: &Process, elapsed: Duration) -> Self {
+        let usage = process.disk_usage();
+        Self::new(
+            pid,
+            process.name().to_string(),
+            (usage.total_read_bytes, usage.total_written_bytes),
+            (usage.read_bytes, usage.written_bytes),
+            elapsed,
+        )
+    }
+
+    pub fn total_bytes_per_sec(&self) -> f64 {
+        self.read_bytes_per_sec + self.written_bytes_per_sec
+    }
+}
+
+/// 按读写速率之和从高到低排列，速率相同时按累计读写量
+pub fn sort_by_io(entries: &mut [ProcessIo]) {
+    entries.sort_by(|a, b| {
+        b.total_bytes_per_sec()
+            .total_cmp(&a.total_bytes_per_sec())
+            .then((b.read_bytes + b.written_bytes).cmp(&(a.read_bytes + a.written_bytes)))
+            .then(a.pid.cmp(&b.pid))
+    });
+}
+
+/// 采样 `interval` 内各进程的磁盘 I/O，返回读写最多的 `limit` 个进程
+pub fn io_top(interval: Duration, limit: usize) -> Vec<ProcessIo> {
+    let (sys, elapsed) = refresh_twice(System::new(), interval);
+    let mut entries: Vec<ProcessIo> = sys
+        .processes()
+        .iter()
+        .filter(|(_, process)| process.thread_kind() != Some(ThreadKind::Userland))
+        .map(|(pid, process)| ProcessIo::from_process(pid.as_u32(), process, elapsed))
+        .collect();
+    sort_by_io(&mut entries);
+    entries.truncate(limit);
+    entries
+}
@@ -0,0 +1,268 @@
+//! 进程资源限制
+//! 解析 /proc/<pid>/limits 得到各项软、硬限制；Linux 上可通过 prlimit 提高软限制，
+//! 软限制不能超过硬限制，修改其他用户的进程需要 root 或 CAP_SYS_RESOURCE
+
+use serde::{Deserialize, Serialize};
+
+// 资源类型，对应 getrlimit(2) 中的 RLIMIT_* 常量
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResourceLimitKind {
+    Cpu,
+    Fsize,
+    Data,
+    Stack,
+    Core,
+    Rss,
+    Nproc,
+    Nofile,
+    Memlock,
+    As,
+    Locks,
+    Sigpending,
+    Msgqueue,
+    Nice,
+    Rtprio,
+    Rttime,
+}
+
+impl ResourceLimitKind {
+    pub const ALL: [Self; 16] = [
+        Self::Cpu,
+        Self::Fsize,
+        Self::Data,
+        Self::Stack,
+        Self::Core,
+        Self::Rss,
+        Self::Nproc,
+        Self::Nofile,
+        Self::Memlock,
+        Self::As,
+        Self::Locks,
+        Self::Sigpending,
+        Self::Msgqueue,
+        Self::Nice,
+        Self::Rtprio,
+        Self::Rttime,
+    ];
+
+    /// 与 ulimit、prlimit(1) 一致的名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cpu => "cpu",
+            Self::Fsize => "fsize",
+            Self::Data => "data",
+            Self::Stack => "stack",
+            Self::Core => "core",
+            Self::Rss => "rss",
+            Self::Nproc => "nproc",
+            Self::Nofile => "nofile",
+            Self::Memlock => "memlock",
+            Self::As => "as",
+            Self::Locks => "locks",
+            Self::Sigpending => "sigpending",
+            Self::Msgqueue => "msgqueue",
+            Self::Nice => "nice",
+            Self::Rtprio => "rtprio",
+            Self::Rttime => "rttime",
+        }
+    }
+
+    /// /proc/<pid>/limits 中的名称
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Cpu => "Max cpu time",
+            Self::Fsize => "Max file size",
+            Self::Data => "Max data size",
+            Self::Stack => "Max stack size",
+            Self::Core => "Max core file size",
+            Self::Rss => "Max resident set",
+            Self::Nproc => "Max processes",
+            Self::Nofile => "Max open files",
+            Self::Memlock => "Max locked memory",
+            Self::As => "Max address space",
+            Self::Locks => "Max file locks",
+            Self::Sigpending => "Max pending signals",
+            Self::Msgqueue => "Max msgqueue size",
+            Self::Nice => "Max nice priority",
+            Self::Rtprio => "Max realtime priority",
+            Self::Rttime => "Max realtime timeout",
+        }
+    }
+
+    /// 接受 "nofile"、"RLIMIT_NOFILE" 或 "Max open files"，不区分大小写
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim();
+        let short = name
+            .get(..7)
+            .filter(|prefix| prefix.eq_ignore_ascii_case("RLIMIT_"))
+            .map_or(name, |_| &name[7..]);
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(short) || kind.label().eq_ignore_ascii_case(name))
+    }
+}
+
+// 一项资源限制，None 表示不限制
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ResourceLimit {
+    pub kind: ResourceLimitKind,
+    pub soft_limit: Option<u64>,
+    pub hard_limit: Option<u64>,
+    /// 如 bytes、files、seconds，部分限制没有单位
+    pub units: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessLimits {
+    pub pid: u32,
+    pub limits: Vec<ResourceLimit>,
+}
+
+/// 解析限制值："unlimited" 为 None
+pub fn parse_limit_value(value: &str) -> Result<Option<u64>, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("unlimited") {
+        return Ok(None);
+    }
+    value.parse().map(Some).map_err(|_| format!("Invalid limit value: {}", value))
+}
+
+pub fn format_limit_value(value: Option<u64>) -> String {
+    value.map_or_else(|| "unlimited".to_string(), |value| value.to_string())
+}
+
+/// 解析 /proc/<pid>/limits；各列按表头中的位置截取，无法识别的行被跳过
+pub fn parse_limits(content: &str) -> Vec<ResourceLimit> {
+    let mut lines = content.lines();
+    let Some(header) = lines.next() else {
+        return Vec::new();
+    };
+    let (Some(soft_column), Some(hard_column), Some(units_column)) =
+        (header.find("Soft Limit"), header.find("Hard Limit"), header.find("Units"))
+    else {
+        return Vec::new();
+    };
+    let column = |line: &str, start: usize, end: usize| -> String {
+        line.get(start..end.min(line.len())).unwrap_or("").trim().to_string()
+    };
+
+    lines
+        .filter_map(|line| {
+            let kind = ResourceLimitKind::from_name(&column(line, 0, soft_column))?;
+            let soft_limit = parse_limit_value(&column(line, soft_column, hard_column)).ok()?;
+            let hard_limit = parse_limit_value(&column(line, hard_column, units_column)).ok()?;
+            let units = Some(column(line, units_column, line.len())).filter(|units| !units.is_empty());
+            Some(ResourceLimit {
+                kind,
+                soft_limit,
+                hard_limit,
+                units,
+            })
+        })
+        .collect()
+}
+
+/// 读取进程的资源限制
+pub fn read_limits(pid: u32) -> Result<ProcessLimits, String> {
+    let content = std::fs::read_to_string(format!("/proc/{}/limits", pid)).map_err(|error| match error.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        std::io::ErrorKind::PermissionDenied => format!("Permission denied: cannot read limits of process {}", pid),
+        _ => format!("Failed to read limits of process {}: {}", pid, error),
+    })?;
+    Ok(ProcessLimits {
+        pid,
+        limits: parse_limits(&content),
+    })
+}
+
+/// 校验新的软限制：只允许提高，且不能超过硬限制
+pub fn validate_soft_limit_raise(current: &ResourceLimit, soft_limit: Option<u64>) -> Result<(), String> {
+    // None 表示不限制，视为最大值
+    let rank = |value: Option<u64>| value.unwrap_or(u64::MAX);
+    if rank(soft_limit) < rank(current.soft_limit) {
+        return Err(format!(
+            "New soft limit {} for {} is below the current soft limit {}; only raising is supported",
+            format_limit_value(soft_limit),
+            current.kind.as_str(),
+            format_limit_value(current.soft_limit)
+        ));
+    }
+    if rank(soft_limit) > rank(current.hard_limit) {
+        return Err(format!(
+            "New soft limit {} for {} exceeds the hard limit {}",
+            format_limit_value(soft_limit),
+            current.kind.as_str(),
+            format_limit_value(current.hard_limit)
+        ));
+    }
+    Ok(())
+}
+
+/// 把进程某项资源的软限制提高到 `soft_limit`，返回修改后的限制
+#[cfg(target_os = "linux")]
+pub fn raise_soft_limit(pid: u32, kind: ResourceLimitKind, soft_limit: Option<u64>) -> Result<ResourceLimit, String> {
+    let raw = libc::pid_t::try_from(pid).ok().filter(|raw| *raw > 0).ok_or_else(|| format!("Invalid PID {}", pid))?;
+    let resource = match kind {
+        ResourceLimitKind::Cpu => libc::RLIMIT_CPU,
+        ResourceLimitKind::Fsize => libc::RLIMIT_FSIZE,
+        ResourceLimitKind::Data => libc::RLIMIT_DATA,
+        ResourceLimitKind::Stack => libc::RLIMIT_STACK,
+        ResourceLimitKind::Core => libc::RLIMIT_CORE,
+        ResourceLimitKind::Rss => libc::RLIMIT_RSS,
+        ResourceLimitKind::Nproc => libc::RLIMIT_NPROC,
+        ResourceLimitKind::Nofile => libc::RLIMIT_NOFILE,
+        ResourceLimitKind::Memlock => libc::RLIMIT_MEMLOCK,
+        ResourceLimitKind::As => libc::RLIMIT_AS,
+        ResourceLimitKind::Locks => libc::RLIMIT_LOCKS,
+        ResourceLimitKind::Sigpending => libc::RLIMIT_SIGPENDING,
+        ResourceLimitKind::Msgqueue => libc::RLIMIT_MSGQUEUE,
+        ResourceLimitKind::Nice => libc::RLIMIT_NICE,
+        ResourceLimitKind::Rtprio => libc::RLIMIT_RTPRIO,
+        ResourceLimitKind::Rttime => libc::RLIMIT_RTTIME,
+    };
+    let to_value = |raw: libc::rlim_t| (raw != libc::RLIM_INFINITY).then_some(raw);
+    let error = |error: std::io::Error| match error.raw_os_error() {
+        Some(libc::ESRCH) => format!("Process with PID {} not found", pid),
+        Some(libc::EPERM) => format!(
+            "Permission denied: changing limits of process {} requires root or CAP_SYS_RESOURCE",
+            pid
+        ),
+        _ => format!("Failed to change {} limit of process {}: {}", kind.as_str(), pid, error),
+    };
+
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: new_limit 为空时只读取当前限制，内核写入 current
+    if unsafe { libc::prlimit(raw, resource, std::ptr::null(), &mut current) } != 0 {
+        return Err(error(std::io::Error::last_os_error()));
+    }
+    let current = ResourceLimit {
+        kind,
+        soft_limit: to_value(current.rlim_cur),
+        hard_limit: to_value(current.rlim_max),
+        units: None,
+    };
+    validate_soft_limit_raise(&current, soft_limit)?;
+
+    let requested = libc::rlimit {
+        rlim_cur: soft_limit.unwrap_or(libc::RLIM_INFINITY),
+        rlim_max: current.hard_limit.unwrap_or(libc::RLIM_INFINITY),
+    };
+    // SAFETY: 只传入指向栈上结构体的指针，不读取旧值
+    if unsafe { libc::prlimit(raw, resource, &requested, std::ptr::null_mut()) } != 0 {
+        return Err(error(std::io::Error::last_os_error()));
+    }
+    read_limits(pid)?
+        .limits
+        .into_iter()
+        .find(|limit| limit.kind == kind)
+        .ok_or_else(|| format!("Failed to read {} limit of process {}", kind.as_str(), pid))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn raise_soft_limit(_pid: u32, _kind: ResourceLimitKind, _soft_limit: Option<u64>) -> Result<ResourceLimit, String> {
+    Err("Changing resource limits is only supported on Linux".to_string())
+}
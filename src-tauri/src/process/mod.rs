@@ -0,0 +1,87 @@
+//! 进程管理
+//! 进程树、信号等按功能拆分为子模块，Tauri 命令仍在 lib.rs 中注册
+
+pub mod audit;
+pub mod cgroup;
+pub mod cgroupfs;
+pub mod environ;
+pub mod fds;
+pub mod io;
+pub mod limits;
+pub mod priority;
+pub mod procfs;
+pub mod signal;
+pub mod smaps;
+pub mod supervisor;
+pub mod threads;
+pub mod tree;
+pub mod users;
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use sysinfo::{Pid, ProcessRefreshKind, System, ThreadKind};
+
+// 进程快照中的一条记录，供进程树等需要整体视图的功能使用
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessEntry {
+    pub pid: u32,
+    pub parent_pid: Option<u32>,
+    pub name: String,
+    pub command_line: String,
+    /// 用户名，无法解析时为 uid
+    pub user: Option<String>,
+    #[serde(default)]
+    pub user_id: Option<String>,
+    pub cpu_usage_percent: f32,
+    pub memory_usage_bytes: u64,
+}
+
+impl ProcessEntry {
+    /// 采集当前所有进程，需要两次刷新才能得到 CPU 使用率
+    pub fn snapshot() -> Vec<Self> {
+        let (sys, _) = refresh_twice(System::new(), sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+        Self::collect(&sys)
+    }
+
+    /// 从已刷新的 `System` 中读取所有进程；Linux 上 sysinfo 会把用户态线程也列为进程，这里跳过
+    pub fn collect(sys: &System) -> Vec<Self> {
+        let directory = users::UserDirectory::cached();
+        sys.processes()
+            .iter()
+            .filter(|(_, process)| process.thread_kind() != Some(ThreadKind::Userland))
+            .map(|(pid, process)| {
+                let owner = directory.owner(process);
+                Self {
+                    pid: pid.as_u32(),
+                    parent_pid: process.parent().map(|parent| parent.as_u32()),
+                    name: process.name().to_string(),
+                    command_line: process.cmd().join(" "),
+                    user: owner.display_user(),
+                    user_id: owner.user_id,
+                    cpu_usage_percent: process.cpu_usage(),
+                    memory_usage_bytes: process.memory(),
+                }
+            })
+            .collect()
+    }
+}
+
+/// 刷新两次进程信息，返回刷新后的 `System` 和两次刷新的间隔。
+/// CPU 使用率和 I/O 速率依赖两次刷新之间的增量；命令行、用户和工作目录
+/// 只有显式指定时才会读取，在第一次刷新时一并获取
+pub fn refresh_twice(mut sys: System, interval: Duration) -> (System, Duration) {
+    sys.refresh_processes_specifics(ProcessRefreshKind::everything());
+    let started = Instant::now();
+    std::thread::sleep(interval);
+    sys.refresh_processes();
+    (sys, started.elapsed())
+}
+
+/// 读取单个进程的名称，进程不存在时返回 None
+pub fn process_name(pid: u32) -> Option<String> {
+    let mut sys = System::new();
+    let pid = Pid::from_u32(pid);
+    sys.refresh_process(pid);
+    sys.process(pid).map(|process| process.name().to_string())
+}
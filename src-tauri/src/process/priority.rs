@@ -0,0 +1,300 @@
+//! 进程优先级与 CPU 亲和性
+//! Linux 上从 /proc/<pid>/stat 读取 nice 值和调度策略，通过 setpriority 和
+//! sched_setaffinity 修改；权限不足时返回说明原因的错误
+//! Linux 的 setpriority 只作用于单个线程，修改 nice 值时逐个处理 /proc/<pid>/task 下的线程
+
+use serde::{Deserialize, Serialize};
+
+#[cfg(target_os = "linux")]
+use super::procfs::ProcStat;
+
+pub const MIN_NICE: i32 = -20;
+pub const MAX_NICE: i32 = 19;
+
+// 调度策略，对应 sched(7) 中的 SCHED_* 常量
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SchedulingPolicy {
+    /// SCHED_OTHER，普通分时调度
+    Other,
+    Fifo,
+    RoundRobin,
+    Batch,
+    Idle,
+    Deadline,
+}
+
+impl SchedulingPolicy {
+    /// 按内核中的策略编号解析
+    pub fn from_number(policy: u32) -> Option<Self> {
+        match policy {
+            0 => Some(Self::Other),
+            1 => Some(Self::Fifo),
+            2 => Some(Self::RoundRobin),
+            3 => Some(Self::Batch),
+            5 => Some(Self::Idle),
+            6 => Some(Self::Deadline),
+            _ => None,
+        }
+    }
+
+    /// 是否为实时调度策略，实时进程的 nice 值不影响调度
+    pub fn is_realtime(&self) -> bool {
+        matches!(self, Self::Fifo | Self::RoundRobin | Self::Deadline)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessPriority {
+    pub pid: u32,
+    /// -20（最高）到 19（最低）
+    pub nice: i32,
+    /// 无法获取时为空（非 Linux 平台）
+    pub scheduling_policy: Option<SchedulingPolicy>,
+    /// 实时优先级，非实时策略为 0
+    pub realtime_priority: u32,
+    /// 进程属于其他用户，当前用户只能读取优先级，修改需要特权
+    #[serde(default)]
+    pub permission_denied: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CpuAffinity {
+    pub pid: u32,
+    /// 允许运行的 CPU 编号，升序
+    pub cpus: Vec<usize>,
+    /// 与 taskset 相同的列表写法，例如 "0-3,6"
+    pub cpu_list: String,
+}
+
+impl CpuAffinity {
+    pub fn new(pid: u32, mut cpus: Vec<usize>) -> Self {
+        cpus.sort_unstable();
+        cpus.dedup();
+        let cpu_list = format_cpu_list(&cpus);
+        Self { pid, cpus, cpu_list }
+    }
+}
+
+/// 解析 "0-3,6" 形式的 CPU 列表
+pub fn parse_cpu_list(list: &str) -> Result<Vec<usize>, String> {
+    let mut cpus = Vec::new();
+    for part in list.split(',').map(str::trim).filter(|part| !part.is_empty()) {
+        let parse = |value: &str| {
+            value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Invalid CPU number '{}' in '{}'", value.trim(), list))
+        };
+        match part.split_once('-') {
+            Some((start, end)) => {
+                let (start, end) = (parse(start)?, parse(end)?);
+                if start > end {
+                    return Err(format!("Invalid CPU range '{}'", part));
+                }
+                cpus.extend(start..=end);
+            }
+            None => cpus.push(parse(part)?),
+        }
+    }
+    if cpus.is_empty() {
+        return Err("CPU list cannot be empty".to_string());
+    }
+    cpus.sort_unstable();
+    cpus.dedup();
+    Ok(cpus)
+}
+
+/// 把升序的 CPU 编号合并为 "0-3,6" 形式
+pub fn format_cpu_list(cpus: &[usize]) -> String {
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for &cpu in cpus {
+        match ranges.last_mut() {
+            Some((_, end)) if *end + 1 == cpu => *end = cpu,
+            _ => ranges.push((cpu, cpu)),
+        }
+    }
+    ranges
+        .iter()
+        .map(|(start, end)| if start == end { start.to_string() } else { format!("{}-{}", start, end) })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// 读取进程的 nice 值和调度策略
+#[cfg(target_os = "linux")]
+pub fn read_priority(pid: u32) -> Result<ProcessPriority, String> {
+    let stat = ProcStat::read(pid).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        _ => format!("Failed to read priority of process {}: {}", pid, e),
+    })?;
+    Ok(ProcessPriority {
+        pid,
+        nice: stat.nice as i32,
+        scheduling_policy: SchedulingPolicy::from_number(stat.policy),
+        realtime_priority: stat.realtime_priority,
+        permission_denied: owned_by_other_user(pid),
+    })
+}
+
+// 与 kill(pid, 0) 返回 EPERM 的判断一致：root 可以修改任何进程
+#[cfg(target_os = "linux")]
+fn owned_by_other_user(pid: u32) -> bool {
+    use std::os::unix::fs::MetadataExt;
+
+    // SAFETY: geteuid 没有参数，总是成功
+    let euid = unsafe { libc::geteuid() };
+    euid != 0
+        && std::fs::metadata(format!("/proc/{}", pid))
+            .map(|metadata| metadata.uid() != euid)
+            .unwrap_or(false)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+pub fn read_priority(pid: u32) -> Result<ProcessPriority, String> {
+    let id = libc::id_t::try_from(pid).map_err(|_| format!("Invalid PID {}", pid))?;
+    // SAFETY: getpriority 只读取参数；-1 也是合法的 nice 值，需再确认进程是否存在
+    let nice = unsafe { libc::getpriority(libc::PRIO_PROCESS, id) };
+    // EPERM 说明进程存在但属于其他用户，nice 值仍然有效
+    let permission_denied = match unsafe { libc::kill(pid as libc::pid_t, 0) } {
+        0 => false,
+        _ => {
+            let error = std::io::Error::last_os_error();
+            if error.raw_os_error() != Some(libc::EPERM) {
+                return Err(priority_error(pid, error, None));
+            }
+            true
+        }
+    };
+    Ok(ProcessPriority {
+        pid,
+        nice,
+        scheduling_policy: None,
+        realtime_priority: 0,
+        permission_denied,
+    })
+}
+
+#[cfg(not(unix))]
+pub fn read_priority(_pid: u32) -> Result<ProcessPriority, String> {
+    Err("Reading process priority is not supported on this platform".to_string())
+}
+
+/// 修改进程的 nice 值，返回修改后的优先级
+/// Linux 上对进程的所有线程生效，修改期间新建的线程继承主线程的 nice 值
+#[cfg(unix)]
+pub fn set_nice(pid: u32, nice: i32) -> Result<ProcessPriority, String> {
+    if !(MIN_NICE..=MAX_NICE).contains(&nice) {
+        return Err(format!("Nice value must be between {} and {}", MIN_NICE, MAX_NICE));
+    }
+    let id = libc::id_t::try_from(pid).ok().filter(|id| *id > 0).ok_or_else(|| format!("Invalid PID {}", pid))?;
+    // 先处理主线程，权限不足时不会留下只改了一部分线程的进程
+    for tid in std::iter::once(id).chain(thread_ids(id).into_iter().filter(|tid| *tid != id)) {
+        // SAFETY: setpriority 只读取参数，ID 为正数时只作用于单个线程或进程
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, tid, nice) } != 0 {
+            let error = std::io::Error::last_os_error();
+            // 枚举之后退出的线程直接跳过
+            if tid != id && error.raw_os_error() == Some(libc::ESRCH) {
+                continue;
+            }
+            return Err(priority_error(pid, error, Some(nice)));
+        }
+    }
+    read_priority(pid)
+}
+
+// /proc/<pid>/task 下的线程 ID（含主线程）；读取失败时只处理主线程
+#[cfg(target_os = "linux")]
+fn thread_ids(pid: libc::id_t) -> Vec<libc::id_t> {
+    std::fs::read_dir(format!("/proc/{}/task", pid))
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// 其他 Unix 平台的 setpriority 作用于整个进程
+#[cfg(all(unix, not(target_os = "linux")))]
+fn thread_ids(_pid: libc::id_t) -> Vec<libc::id_t> {
+    Vec::new()
+}
+
+#[cfg(not(unix))]
+pub fn set_nice(_pid: u32, _nice: i32) -> Result<ProcessPriority, String> {
+    Err("Changing process priority is not supported on this platform".to_string())
+}
+
+#[cfg(unix)]
+fn priority_error(pid: u32, error: std::io::Error, nice: Option<i32>) -> String {
+    match (error.raw_os_error(), nice) {
+        (Some(libc::ESRCH), _) => format!("Process with PID {} not found", pid),
+        (Some(libc::EACCES), Some(nice)) => format!(
+            "Permission denied: setting nice {} on process {} requires root or CAP_SYS_NICE",
+            nice, pid
+        ),
+        (Some(libc::EPERM), _) => format!("Permission denied: process {} belongs to another user", pid),
+        _ => format!("Failed to change priority of process {}: {}", pid, error),
+    }
+}
+
+/// 读取进程的 CPU 亲和性
+#[cfg(target_os = "linux")]
+pub fn get_affinity(pid: u32) -> Result<CpuAffinity, String> {
+    let raw = libc::pid_t::try_from(pid).ok().filter(|raw| *raw > 0).ok_or_else(|| format!("Invalid PID {}", pid))?;
+    // SAFETY: cpu_set_t 为纯数据结构，全零即空集合；内核按传入的大小写入
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    if unsafe { libc::sched_getaffinity(raw, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0 {
+        return Err(affinity_error(pid, std::io::Error::last_os_error()));
+    }
+    let cpus = (0..libc::CPU_SETSIZE as usize)
+        .filter(|cpu| unsafe { libc::CPU_ISSET(*cpu, &set) })
+        .collect();
+    Ok(CpuAffinity::new(pid, cpus))
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn get_affinity(_pid: u32) -> Result<CpuAffinity, String> {
+    Err("CPU affinity is only supported on Linux".to_string())
+}
+
+/// 设置进程的 CPU 亲和性，返回内核实际生效的结果
+#[cfg(target_os = "linux")]
+pub fn set_affinity(pid: u32, cpus: &[usize]) -> Result<CpuAffinity, String> {
+    let raw = libc::pid_t::try_from(pid).ok().filter(|raw| *raw > 0).ok_or_else(|| format!("Invalid PID {}", pid))?;
+    if cpus.is_empty() {
+        return Err("CPU list cannot be empty".to_string());
+    }
+    if let Some(cpu) = cpus.iter().find(|cpu| **cpu >= libc::CPU_SETSIZE as usize) {
+        return Err(format!("CPU {} exceeds the maximum of {}", cpu, libc::CPU_SETSIZE - 1));
+    }
+
+    // SAFETY: 同 get_affinity，CPU 编号已检查不超过集合大小
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for cpu in cpus {
+        unsafe { libc::CPU_SET(*cpu, &mut set) };
+    }
+    if unsafe { libc::sched_setaffinity(raw, std::mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+        return Err(affinity_error(pid, std::io::Error::last_os_error()));
+    }
+    get_affinity(pid)
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn set_affinity(_pid: u32, _cpus: &[usize]) -> Result<CpuAffinity, String> {
+    Err("CPU affinity is only supported on Linux".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn affinity_error(pid: u32, error: std::io::Error) -> String {
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => format!("Process with PID {} not found", pid),
+        Some(libc::EPERM) => format!(
+            "Permission denied: changing CPU affinity of process {} requires owning it or CAP_SYS_NICE",
+            pid
+        ),
+        Some(libc::EINVAL) => "None of the requested CPUs are online or allowed by the process's cpuset".to_string(),
+        _ => format!("Failed to change CPU affinity of process {}: {}", pid, error),
+    }
+}
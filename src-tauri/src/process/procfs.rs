@@ -0,0 +1,97 @@
+//! /proc 解析
+//! 读取 Linux 的 /proc/<pid>/stat 等文件，补充 sysinfo 没有提供的字段；
+//! 解析函数只处理文本，便于在其他平台上用固定样本测试
+
+use std::io;
+
+// /proc/<pid>/stat 中用到的字段，字段含义见 proc(5)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProcStat {
+    pub pid: u32,
+    pub comm: String,
+    /// 进程状态字符，例如 R、S、D、Z、T
+    pub state: char,
+    pub parent_pid: u32,
+    pub process_group: i32,
+    pub session: i32,
+    /// 用户态 CPU 时间，单位为时钟滴答
+    pub user_ticks: u64,
+    /// 内核态 CPU 时间，单位为时钟滴答
+    pub system_ticks: u64,
+    pub priority: i64,
+    pub nice: i64,
+    pub num_threads: u32,
+    /// 进程启动时间，为系统启动后的时钟滴答数
+    pub start_ticks: u64,
+    pub realtime_priority: u32,
+    pub policy: u32,
+}
+
+impl ProcStat {
+    /// 解析 stat 文件内容；进程名可能包含空格和括号，以最后一个 ')' 为界
+    pub fn parse(content: &str) -> Option<Self> {
+        let open = content.find('(')?;
+        let close = content.rfind(')')?;
+        let pid = content[..open].trim().parse().ok()?;
+        let comm = content.get(open + 1..close)?.to_string();
+
+        // fields[0] 对应第 3 个字段 state
+        let fields: Vec<&str> = content[close + 1..].split_whitespace().collect();
+        let field = |number: usize| fields.get(number - 3).copied();
+        let number = |index: usize| field(index)?.parse::<u64>().ok();
+        let signed = |index: usize| field(index)?.parse::<i64>().ok();
+
+        Some(Self {
+            pid,
+            comm,
+            state: field(3)?.chars().next()?,
+            parent_pid: number(4)? as u32,
+            process_group: signed(5)? as i32,
+            session: signed(6)? as i32,
+            user_ticks: number(14)?,
+            system_ticks: number(15)?,
+            priority: signed(18)?,
+            nice: signed(19)?,
+            num_threads: number(20)? as u32,
+            start_ticks: number(22)?,
+            // 很旧的内核没有这两个字段
+            realtime_priority: number(40).unwrap_or(0) as u32,
+            policy: number(41).unwrap_or(0) as u32,
+        })
+    }
+
+    pub fn read(pid: u32) -> io::Result<Self> {
+        Self::read_path(&format!("/proc/{}/stat", pid))
+    }
+
+    /// 读取线程的 stat，路径为 /proc/<pid>/task/<tid>/stat
+    pub fn read_task(pid: u32, tid: u32) -> io::Result<Self> {
+        Self::read_path(&format!("/proc/{}/task/{}/stat", pid, tid))
+    }
+
+    fn read_path(path: &str) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("Malformed {}", path)))
+    }
+
+    pub fn is_zombie(&self) -> bool {
+        self.state == 'Z'
+    }
+}
+
+/// 每秒时钟滴答数，用于把 stat 中的 CPU 时间换算为秒
+#[cfg(unix)]
+pub fn clock_ticks_per_second() -> u64 {
+    // SAFETY: sysconf 只读取参数
+    let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+    if ticks > 0 {
+        ticks as u64
+    } else {
+        100
+    }
+}
+
+#[cfg(not(unix))]
+pub fn clock_ticks_per_second() -> u64 {
+    100
+}
@@ -0,0 +1,377 @@
+//! 进程信号
+//! Unix 上直接调用 kill(2) 发送信号，按 PID 返回结构化结果而不是解析命令输出；
+//! 支持对整个进程树或进程组发送信号，以及先 SIGTERM、超时后 SIGKILL 的逐级终止
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::System;
+
+#[cfg(target_os = "linux")]
+use super::procfs::ProcStat;
+use super::tree::{process_lineage, ProcessTreeNode};
+use super::ProcessEntry;
+
+/// 逐级终止时允许的最长等待时间
+pub const MAX_GRACE_PERIOD_SECS: u64 = 300;
+
+// 轮询进程是否已退出的间隔
+const EXIT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+// 支持发送的信号
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessSignal {
+    #[serde(rename = "SIGTERM")]
+    Term,
+    #[serde(rename = "SIGKILL")]
+    Kill,
+    #[serde(rename = "SIGINT")]
+    Int,
+    #[serde(rename = "SIGHUP")]
+    Hup,
+    #[serde(rename = "SIGSTOP")]
+    Stop,
+    #[serde(rename = "SIGCONT")]
+    Cont,
+    #[serde(rename = "SIGUSR1")]
+    Usr1,
+    #[serde(rename = "SIGUSR2")]
+    Usr2,
+}
+
+impl ProcessSignal {
+    pub const ALL: [ProcessSignal; 8] = [
+        ProcessSignal::Term,
+        ProcessSignal::Kill,
+        ProcessSignal::Int,
+        ProcessSignal::Hup,
+        ProcessSignal::Stop,
+        ProcessSignal::Cont,
+        ProcessSignal::Usr1,
+        ProcessSignal::Usr2,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProcessSignal::Term => "SIGTERM",
+            ProcessSignal::Kill => "SIGKILL",
+            ProcessSignal::Int => "SIGINT",
+            ProcessSignal::Hup => "SIGHUP",
+            ProcessSignal::Stop => "SIGSTOP",
+            ProcessSignal::Cont => "SIGCONT",
+            ProcessSignal::Usr1 => "SIGUSR1",
+            ProcessSignal::Usr2 => "SIGUSR2",
+        }
+    }
+
+    /// 解析信号名，接受 "SIGTERM"、"term" 等写法，不区分大小写
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_uppercase();
+        let name = name.strip_prefix("SIG").unwrap_or(&name);
+        Self::ALL.into_iter().find(|signal| &signal.as_str()[3..] == name)
+    }
+
+    #[cfg(unix)]
+    fn number(&self) -> libc::c_int {
+        match self {
+            ProcessSignal::Term => libc::SIGTERM,
+            ProcessSignal::Kill => libc::SIGKILL,
+            ProcessSignal::Int => libc::SIGINT,
+            ProcessSignal::Hup => libc::SIGHUP,
+            ProcessSignal::Stop => libc::SIGSTOP,
+            ProcessSignal::Cont => libc::SIGCONT,
+            ProcessSignal::Usr1 => libc::SIGUSR1,
+            ProcessSignal::Usr2 => libc::SIGUSR2,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn to_sysinfo(self) -> sysinfo::Signal {
+        match self {
+            ProcessSignal::Term => sysinfo::Signal::Term,
+            ProcessSignal::Kill => sysinfo::Signal::Kill,
+            ProcessSignal::Int => sysinfo::Signal::Interrupt,
+            ProcessSignal::Hup => sysinfo::Signal::Hangup,
+            ProcessSignal::Stop => sysinfo::Signal::Stop,
+            ProcessSignal::Cont => sysinfo::Signal::Continue,
+            ProcessSignal::Usr1 => sysinfo::Signal::User1,
+            ProcessSignal::Usr2 => sysinfo::Signal::User2,
+        }
+    }
+}
+
+// 信号的作用范围
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalScope {
+    /// 只发给指定进程
+    #[default]
+    Process,
+    /// 指定进程及其所有后代，后代先于祖先
+    Tree,
+    /// 与指定进程同一进程组的所有进程
+    Group,
+}
+
+// 单个进程的信号发送结果
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SignalStatus {
+    /// 信号已送达
+    Delivered,
+    /// 逐级终止中，进程在等待期内收到 SIGTERM 后退出
+    Terminated,
+    /// 逐级终止中，进程在等待期后被 SIGKILL 终止
+    Killed,
+    NotFound,
+    PermissionDenied,
+    /// 当前平台不支持该信号
+    Unsupported,
+    Failed,
+}
+
+impl SignalStatus {
+    pub fn is_success(&self) -> bool {
+        matches!(self, SignalStatus::Delivered | SignalStatus::Terminated | SignalStatus::Killed)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SignalOutcome {
+    pub pid: u32,
+    pub name: Option<String>,
+    /// 最后发送的信号，逐级终止时为 SIGKILL 表示已升级
+    pub signal: ProcessSignal,
+    pub status: SignalStatus,
+    pub error: Option<String>,
+}
+
+/// 确定信号要发给哪些进程；进程不存在时返回 None。
+/// 进程树和进程组中会跳过 init 进程和本程序自身，避免误杀；本程序所在的进程组由 signal_process 拒绝
+pub fn signal_targets(entries: &[ProcessEntry], pid: u32, scope: SignalScope) -> Option<Vec<u32>> {
+    let own_pid = std::process::id();
+    let protected = |candidate: u32| candidate <= 1 || candidate == own_pid;
+
+    match scope {
+        SignalScope::Process => entries.iter().any(|entry| entry.pid == pid).then(|| vec![pid]),
+        SignalScope::Tree => {
+            let lineage = process_lineage(entries, pid)?;
+            let mut pids = Vec::new();
+            collect_post_order(&lineage.subtree, &mut pids);
+            pids.retain(|candidate| *candidate == pid || !protected(*candidate));
+            Some(pids)
+        }
+        SignalScope::Group => {
+            if !entries.iter().any(|entry| entry.pid == pid) {
+                return None;
+            }
+            let Some(group) = process_group(pid) else {
+                return Some(vec![pid]);
+            };
+            // 进程组组长最后处理，与进程树一致
+            let mut pids: Vec<u32> = entries
+                .iter()
+                .map(|entry| entry.pid)
+                .filter(|candidate| *candidate != group && !protected(*candidate))
+                .filter(|candidate| process_group(*candidate) == Some(group))
+                .collect();
+            pids.sort_unstable();
+            if entries.iter().any(|entry| entry.pid == group) && (group == pid || !protected(group)) {
+                pids.push(group);
+            }
+            Some(pids)
+        }
+    }
+}
+
+fn collect_post_order(node: &ProcessTreeNode, pids: &mut Vec<u32>) {
+    for child in &node.children {
+        collect_post_order(child, pids);
+    }
+    pids.push(node.process.pid);
+}
+
+/// 向 `pid` 所在范围内的进程发送信号
+pub fn signal_process(pid: u32, signal: ProcessSignal, scope: SignalScope) -> Result<Vec<SignalOutcome>, String> {
+    let (targets, names) = resolve(pid, scope)?;
+    Ok(targets
+        .into_iter()
+        .map(|target| {
+            let (status, error) = deliver(target, signal);
+            SignalOutcome {
+                pid: target,
+                name: names.get(&target).cloned(),
+                signal,
+                status,
+                error,
+            }
+        })
+        .collect())
+}
+
+/// 逐级终止：先发送 SIGTERM，等待 `grace_period` 后对仍在运行的进程发送 SIGKILL
+pub fn terminate_gracefully(pid: u32, scope: SignalScope, grace_period: Duration) -> Result<Vec<SignalOutcome>, String> {
+    let (targets, names) = resolve(pid, scope)?;
+
+    let mut outcomes: Vec<SignalOutcome> = targets
+        .into_iter()
+        .map(|target| {
+            let (status, error) = deliver(target, ProcessSignal::Term);
+            SignalOutcome {
+                pid: target,
+                name: names.get(&target).cloned(),
+                signal: ProcessSignal::Term,
+                status,
+                error,
+            }
+        })
+        .collect();
+
+    let deadline = Instant::now() + grace_period;
+    loop {
+        let waiting = outcomes
+            .iter()
+            .any(|outcome| outcome.status == SignalStatus::Delivered && is_running(outcome.pid));
+        if !waiting || Instant::now() >= deadline {
+            break;
+        }
+        std::thread::sleep(EXIT_POLL_INTERVAL);
+    }
+
+    for outcome in &mut outcomes {
+        match outcome.status {
+            SignalStatus::Delivered if !is_running(outcome.pid) => outcome.status = SignalStatus::Terminated,
+            // 无法发送 SIGTERM 时直接强制终止
+            SignalStatus::Delivered | SignalStatus::Unsupported => {
+                let (status, error) = deliver(outcome.pid, ProcessSignal::Kill);
+                outcome.signal = ProcessSignal::Kill;
+                outcome.error = error;
+                outcome.status = match status {
+                    SignalStatus::Delivered => SignalStatus::Killed,
+                    // 在两次检查之间退出
+                    SignalStatus::NotFound => SignalStatus::Terminated,
+                    other => other,
+                };
+            }
+            _ => {}
+        }
+    }
+    Ok(outcomes)
+}
+
+fn resolve(pid: u32, scope: SignalScope) -> Result<(Vec<u32>, HashMap<u32, String>), String> {
+    if pid == 0 {
+        return Err("Invalid PID 0".to_string());
+    }
+    if pid == std::process::id() {
+        return Err("Refusing to signal the monitor's own process".to_string());
+    }
+    // 进程组中可能包含启动本程序的 shell 等进程
+    if scope == SignalScope::Group && process_group(pid).is_some_and(|group| Some(group) == process_group(std::process::id())) {
+        return Err("Refusing to signal the monitor's own process group".to_string());
+    }
+
+    let mut sys = System::new();
+    sys.refresh_processes();
+    let entries = ProcessEntry::collect(&sys);
+    let targets = signal_targets(&entries, pid, scope).ok_or_else(|| format!("Process with PID {} not found", pid))?;
+    let names = entries.into_iter().map(|entry| (entry.pid, entry.name)).collect();
+    Ok((targets, names))
+}
+
+/// 读取进程所属的进程组，非 Unix 平台没有进程组
+#[cfg(unix)]
+pub fn process_group(pid: u32) -> Option<u32> {
+    let pid = libc::pid_t::try_from(pid).ok().filter(|pid| *pid > 0)?;
+    // SAFETY: getpgid 只读取参数，不涉及内存访问
+    let group = unsafe { libc::getpgid(pid) };
+    u32::try_from(group).ok()
+}
+
+#[cfg(not(unix))]
+pub fn process_group(_pid: u32) -> Option<u32> {
+    None
+}
+
+#[cfg(unix)]
+fn deliver(pid: u32, signal: ProcessSignal) -> (SignalStatus, Option<String>) {
+    // kill(2) 对 0 和负数 PID 会发给整个进程组，必须排除
+    let Some(raw) = libc::pid_t::try_from(pid).ok().filter(|raw| *raw > 0) else {
+        return (SignalStatus::NotFound, Some(format!("Invalid PID {}", pid)));
+    };
+    // SAFETY: kill 只读取参数，不涉及内存访问
+    if unsafe { libc::kill(raw, signal.number()) } == 0 {
+        return (SignalStatus::Delivered, None);
+    }
+
+    let error = std::io::Error::last_os_error();
+    let status = match error.raw_os_error() {
+        Some(libc::ESRCH) => SignalStatus::NotFound,
+        Some(libc::EPERM) => SignalStatus::PermissionDenied,
+        _ => SignalStatus::Failed,
+    };
+    (status, Some(error.to_string()))
+}
+
+#[cfg(not(unix))]
+fn deliver(pid: u32, signal: ProcessSignal) -> (SignalStatus, Option<String>) {
+    let pid = sysinfo::Pid::from_u32(pid);
+    let mut sys = System::new();
+    if !sys.refresh_process(pid) {
+        return (SignalStatus::NotFound, None);
+    }
+    let Some(process) = sys.process(pid) else {
+        return (SignalStatus::NotFound, None);
+    };
+    let sent = match process.kill_with(signal.to_sysinfo()) {
+        Some(sent) => sent,
+        // Windows 不支持 SIGTERM，改用不带 /F 的 taskkill 请求进程正常退出
+        None if signal == ProcessSignal::Term => std::process::Command::new("taskkill")
+            .args(["/PID", &pid.as_u32().to_string()])
+            .output()
+            .is_ok_and(|output| output.status.success()),
+        None if signal == ProcessSignal::Kill => process.kill(),
+        None => {
+            return (
+                SignalStatus::Unsupported,
+                Some(format!("{} is not supported on this platform", signal.as_str())),
+            )
+        }
+    };
+    if sent {
+        (SignalStatus::Delivered, None)
+    } else {
+        (SignalStatus::Failed, Some(format!("Failed to send {}", signal.as_str())))
+    }
+}
+
+/// 判断进程是否仍在运行；僵尸进程视为已退出
+#[cfg(unix)]
+fn is_running(pid: u32) -> bool {
+    let Some(raw) = libc::pid_t::try_from(pid).ok().filter(|raw| *raw > 0) else {
+        return false;
+    };
+    // SAFETY: 信号 0 只检查进程是否存在
+    let exists = unsafe { libc::kill(raw, 0) } == 0
+        || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM);
+    exists && !is_zombie(pid)
+}
+
+#[cfg(not(unix))]
+fn is_running(pid: u32) -> bool {
+    System::new().refresh_process(sysinfo::Pid::from_u32(pid))
+}
+
+#[cfg(target_os = "linux")]
+fn is_zombie(pid: u32) -> bool {
+    ProcStat::read(pid).is_ok_and(|stat| stat.is_zombie())
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn is_zombie(pid: u32) -> bool {
+    let mut sys = System::new();
+    sys.refresh_process(sysinfo::Pid::from_u32(pid));
+    sys.process(sysinfo::Pid::from_u32(pid))
+        .is_some_and(|process| process.status() == sysinfo::ProcessStatus::Zombie)
+}
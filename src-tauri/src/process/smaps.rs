@@ -0,0 +1,266 @@
+//! 进程内存映射
+//! 解析 /proc/<pid>/smaps_rollup 得到 RSS、PSS、USS、交换空间以及共享与私有内存，
+//! 解析 /proc/<pid>/smaps 得到各映射的用量，并按映射的文件（堆、栈、匿名内存等）汇总
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+// 内存用量，单位均为字节
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct MemoryUsage {
+    pub rss_bytes: u64,
+    /// 按共享进程数分摊后的用量
+    pub pss_bytes: u64,
+    /// 仅本进程使用的内存，即私有页之和
+    pub uss_bytes: u64,
+    pub swap_bytes: u64,
+    pub swap_pss_bytes: u64,
+    pub shared_clean_bytes: u64,
+    pub shared_dirty_bytes: u64,
+    pub private_clean_bytes: u64,
+    pub private_dirty_bytes: u64,
+    pub anonymous_bytes: u64,
+}
+
+impl MemoryUsage {
+    pub fn shared_bytes(&self) -> u64 {
+        self.shared_clean_bytes + self.shared_dirty_bytes
+    }
+
+    pub fn private_bytes(&self) -> u64 {
+        self.private_clean_bytes + self.private_dirty_bytes
+    }
+
+    /// 记录一个 "Rss:  1308 kB" 形式的字段，不关心的字段被忽略
+    fn add_field(&mut self, key: &str, bytes: u64) {
+        match key {
+            "Rss" => self.rss_bytes += bytes,
+            "Pss" => self.pss_bytes += bytes,
+            "Swap" => self.swap_bytes += bytes,
+            "SwapPss" => self.swap_pss_bytes += bytes,
+            "Shared_Clean" => self.shared_clean_bytes += bytes,
+            "Shared_Dirty" => self.shared_dirty_bytes += bytes,
+            "Private_Clean" => {
+                self.private_clean_bytes += bytes;
+                self.uss_bytes += bytes;
+            }
+            "Private_Dirty" => {
+                self.private_dirty_bytes += bytes;
+                self.uss_bytes += bytes;
+            }
+            "Anonymous" => self.anonymous_bytes += bytes,
+            _ => {}
+        }
+    }
+
+    fn accumulate(&mut self, other: &MemoryUsage) {
+        self.rss_bytes += other.rss_bytes;
+        self.pss_bytes += other.pss_bytes;
+        self.uss_bytes += other.uss_bytes;
+        self.swap_bytes += other.swap_bytes;
+        self.swap_pss_bytes += other.swap_pss_bytes;
+        self.shared_clean_bytes += other.shared_clean_bytes;
+        self.shared_dirty_bytes += other.shared_dirty_bytes;
+        self.private_clean_bytes += other.private_clean_bytes;
+        self.private_dirty_bytes += other.private_dirty_bytes;
+        self.anonymous_bytes += other.anonymous_bytes;
+    }
+}
+
+// 映射的来源
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MappingKind {
+    /// 可执行文件、共享库或 mmap 的文件
+    File,
+    Heap,
+    Stack,
+    /// 没有名称的匿名映射
+    Anonymous,
+    /// [vdso]、[vvar]、[anon:name] 等内核或用户命名的映射
+    Special,
+}
+
+impl MappingKind {
+    pub fn from_pathname(pathname: &str) -> Self {
+        if pathname.is_empty() {
+            Self::Anonymous
+        } else if pathname == "[heap]" {
+            Self::Heap
+        } else if pathname == "[stack]" || pathname.starts_with("[stack:") {
+            Self::Stack
+        } else if pathname.starts_with('[') {
+            Self::Special
+        } else {
+            Self::File
+        }
+    }
+}
+
+// smaps 中的一个映射
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MemoryMapping {
+    pub start_address: u64,
+    pub end_address: u64,
+    /// 如 "r-xp"，最后一位 p 表示私有映射，s 表示共享映射
+    pub permissions: String,
+    pub offset: u64,
+    /// 映射的文件路径或 [heap] 等名称，匿名映射为空
+    pub pathname: String,
+    pub kind: MappingKind,
+    pub size_bytes: u64,
+    pub usage: MemoryUsage,
+}
+
+// 映射同一文件（或同为堆、栈、匿名内存）的映射的合计
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MappingGroup {
+    /// 文件路径或 [heap]、[stack]、[anon] 等名称
+    pub backing: String,
+    pub kind: MappingKind,
+    pub mapping_count: u32,
+    pub size_bytes: u64,
+    pub usage: MemoryUsage,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessMemoryMap {
+    pub pid: u32,
+    pub summary: MemoryUsage,
+    /// 按 PSS 从高到低排列
+    pub groups: Vec<MappingGroup>,
+}
+
+// 匿名映射在汇总中使用的名称
+const ANONYMOUS_BACKING: &str = "[anon]";
+
+// 拆出 "Key:   123 kB" 形式的字段，返回字段名和字节数
+fn parse_field(line: &str) -> Option<(&str, u64)> {
+    let (key, value) = line.split_once(':')?;
+    let kb: u64 = value.trim().strip_suffix("kB")?.trim().parse().ok()?;
+    Some((key.trim(), kb * 1024))
+}
+
+// 解析映射的首行："start-end perms offset dev inode [pathname]"
+fn parse_header(line: &str) -> Option<MemoryMapping> {
+    let mut rest = line;
+    let mut fields = Vec::with_capacity(5);
+    for _ in 0..5 {
+        let trimmed = rest.trim_start();
+        let end = trimmed.find(char::is_whitespace).unwrap_or(trimmed.len());
+        if end == 0 {
+            return None;
+        }
+        fields.push(&trimmed[..end]);
+        rest = &trimmed[end..];
+    }
+
+    let (start, end) = fields[0].split_once('-')?;
+    let start_address = u64::from_str_radix(start, 16).ok()?;
+    let end_address = u64::from_str_radix(end, 16).ok()?;
+    if fields[1].len() != 4 {
+        return None;
+    }
+    let offset = u64::from_str_radix(fields[2], 16).ok()?;
+    // 路径中可能含有空格，保留首尾之外的全部内容
+    let pathname = rest.trim().to_string();
+
+    Some(MemoryMapping {
+        start_address,
+        end_address,
+        permissions: fields[1].to_string(),
+        offset,
+        kind: MappingKind::from_pathname(&pathname),
+        pathname,
+        size_bytes: end_address.saturating_sub(start_address),
+        usage: MemoryUsage::default(),
+    })
+}
+
+/// 解析 smaps_rollup；也可用于单个映射的字段块
+pub fn parse_smaps_rollup(content: &str) -> MemoryUsage {
+    let mut usage = MemoryUsage::default();
+    for (key, bytes) in content.lines().filter_map(parse_field) {
+        usage.add_field(key, bytes);
+    }
+    usage
+}
+
+/// 解析 smaps，返回各映射及其用量
+pub fn parse_smaps(content: &str) -> Vec<MemoryMapping> {
+    let mut mappings: Vec<MemoryMapping> = Vec::new();
+    for line in content.lines() {
+        if let Some(mapping) = parse_header(line) {
+            mappings.push(mapping);
+        } else if let (Some(mapping), Some((key, bytes))) = (mappings.last_mut(), parse_field(line)) {
+            mapping.usage.add_field(key, bytes);
+        }
+    }
+    mappings
+}
+
+/// 按映射的文件汇总，匿名映射合为一组；结果按 PSS 从高到低排列
+pub fn group_mappings(mappings: &[MemoryMapping]) -> Vec<MappingGroup> {
+    let mut groups: Vec<MappingGroup> = Vec::new();
+    let mut index: HashMap<&str, usize> = HashMap::new();
+    for mapping in mappings {
+        let backing = match mapping.kind {
+            MappingKind::Anonymous => ANONYMOUS_BACKING,
+            _ => mapping.pathname.as_str(),
+        };
+        let position = *index.entry(backing).or_insert_with(|| {
+            groups.push(MappingGroup {
+                backing: backing.to_string(),
+                kind: mapping.kind,
+                mapping_count: 0,
+                size_bytes: 0,
+                usage: MemoryUsage::default(),
+            });
+            groups.len() - 1
+        });
+        let group = &mut groups[position];
+        group.mapping_count += 1;
+        group.size_bytes += mapping.size_bytes;
+        group.usage.accumulate(&mapping.usage);
+    }
+    groups.sort_by(|a, b| {
+        b.usage
+            .pss_bytes
+            .cmp(&a.usage.pss_bytes)
+            .then(b.usage.rss_bytes.cmp(&a.usage.rss_bytes))
+            .then(a.backing.cmp(&b.backing))
+    });
+    groups
+}
+
+fn read_error(pid: u32, error: std::io::Error) -> String {
+    match error.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        std::io::ErrorKind::PermissionDenied => {
+            format!("Permission denied: cannot read memory map of process {} owned by another user", pid)
+        }
+        _ => format!("Failed to read memory map of process {}: {}", pid, error),
+    }
+}
+
+/// 读取进程的内存映射；内核不提供 smaps_rollup（4.14 以前）时由各映射累加得到合计
+pub fn read_memory_map(pid: u32) -> Result<ProcessMemoryMap, String> {
+    let smaps = std::fs::read_to_string(format!("/proc/{}/smaps", pid)).map_err(|e| read_error(pid, e))?;
+    let mappings = parse_smaps(&smaps);
+    let summary = match std::fs::read_to_string(format!("/proc/{}/smaps_rollup", pid)) {
+        Ok(rollup) => parse_smaps_rollup(&rollup),
+        Err(_) => {
+            let mut summary = MemoryUsage::default();
+            for mapping in &mappings {
+                summary.accumulate(&mapping.usage);
+            }
+            summary
+        }
+    };
+    Ok(ProcessMemoryMap {
+        pid,
+        summary,
+        groups: group_mappings(&mappings),
+    })
+}
@@ -0,0 +1,642 @@
+//! 进程守护
+//! 两类规则：保持命令运行（由应用启动，退出后按指数退避重启），以及按名称监视进程
+//! （进程消失时记录事件，配置了重启命令时同样重启）。由应用启动的进程可以取得退出码，
+//! 监视的外部进程只能发现其已退出。应用启动后由后台任务每 CHECK_INTERVAL_SECS 秒检查一次；
+//! 规则和事件保存在数据库中，应用重启后按事件恢复重启次数和放弃状态
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::signal::{terminate_gracefully, SignalScope};
+use super::ProcessEntry;
+
+/// 进程持续运行超过这段时间后，退避间隔从初始值重新计算
+pub const BACKOFF_RESET_SECS: i64 = 60;
+
+/// 后台检查的间隔
+pub const CHECK_INTERVAL_SECS: u64 = 5;
+
+/// 退避间隔的上限，一天
+pub const MAX_BACKOFF_SECS: u64 = 24 * 60 * 60;
+
+// 停用或删除规则时，由应用启动的进程在 SIGTERM 后等待的时间
+const STOP_GRACE_PERIOD: std::time::Duration = std::time::Duration::from_secs(10);
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_initial_backoff_secs() -> u64 {
+    1
+}
+
+fn default_max_backoff_secs() -> u64 {
+    300
+}
+
+// 由守护进程启动的命令，不经过 shell，标准输入输出重定向到空设备
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LaunchCommand {
+    pub program: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub working_directory: Option<String>,
+}
+
+impl LaunchCommand {
+    fn validate(&self) -> Result<(), String> {
+        if !Path::new(&self.program).is_absolute() {
+            return Err(format!("Program path must be absolute, got `{}`", self.program));
+        }
+        if let Some(directory) = &self.working_directory {
+            if !Path::new(directory).is_absolute() {
+                return Err(format!("Working directory must be absolute, got `{}`", directory));
+            }
+        }
+        Ok(())
+    }
+
+    /// 与进程快照中的命令行比较时使用的文本
+    pub fn command_line(&self) -> String {
+        std::iter::once(self.program.as_str())
+            .chain(self.args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn spawn(&self) -> std::io::Result<Child> {
+        let mut command = Command::new(&self.program);
+        command
+            .args(&self.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        if let Some(directory) = &self.working_directory {
+            command.current_dir(directory);
+        }
+        command.spawn()
+    }
+}
+
+// 守护的对象
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SupervisorTarget {
+    /// 保持命令运行；已有命令行完全相同的进程在运行时直接接管，不重复启动
+    Command(LaunchCommand),
+    /// 监视名称匹配（不区分大小写）的进程
+    Process {
+        process_name: String,
+        /// 进程未运行时用于启动它的命令，为空时只记录事件
+        #[serde(default)]
+        restart_command: Option<LaunchCommand>,
+    },
+}
+
+impl SupervisorTarget {
+    /// 进程退出后用于重启的命令
+    pub fn launch_command(&self) -> Option<&LaunchCommand> {
+        match self {
+            Self::Command(command) => Some(command),
+            Self::Process { restart_command, .. } => restart_command.as_ref(),
+        }
+    }
+
+    /// 在进程快照中查找属于该对象的进程，不包括应用自身
+    fn find(&self, processes: &[ProcessEntry]) -> Option<u32> {
+        let own_pid = std::process::id();
+        processes
+            .iter()
+            .filter(|entry| entry.pid != own_pid)
+            .find(|entry| match self {
+                Self::Command(command) => entry.command_line == command.command_line(),
+                Self::Process { process_name, .. } => entry.name.eq_ignore_ascii_case(process_name.trim()),
+            })
+            .map(|entry| entry.pid)
+    }
+}
+
+// 守护规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SupervisorRule {
+    #[serde(default)]
+    pub id: String,
+    pub name: String,
+    pub target: SupervisorTarget,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// 第一次重启前等待的秒数，之后每次连续失败翻倍
+    #[serde(default = "default_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+    /// 累计重启次数上限，达到后放弃；为空时不限制
+    #[serde(default)]
+    pub max_restarts: Option<u32>,
+}
+
+impl SupervisorRule {
+    /// 校验规则，返回可直接展示给用户的错误信息
+    pub fn validate(&self) -> Result<(), String> {
+        if self.name.trim().is_empty() {
+            return Err("Supervisor rule name must not be empty".to_string());
+        }
+        match &self.target {
+            SupervisorTarget::Command(command) => command.validate()?,
+            SupervisorTarget::Process { process_name, restart_command } => {
+                if process_name.trim().is_empty() {
+                    return Err("Process name must not be empty".to_string());
+                }
+                if let Some(command) = restart_command {
+                    command.validate()?;
+                }
+            }
+        }
+        if self.initial_backoff_secs == 0 {
+            return Err("Initial backoff must be at least one second".to_string());
+        }
+        if self.max_backoff_secs < self.initial_backoff_secs {
+            return Err("Maximum backoff must not be shorter than the initial backoff".to_string());
+        }
+        if self.max_backoff_secs > MAX_BACKOFF_SECS {
+            return Err(format!("Maximum backoff must not exceed {} seconds (one day)", MAX_BACKOFF_SECS));
+        }
+        Ok(())
+    }
+
+    /// 第 `consecutive_failures` 次连续失败后的等待时间
+    pub fn backoff_delay(&self, consecutive_failures: u32) -> Duration {
+        let exponent = consecutive_failures.saturating_sub(1).min(63);
+        let secs = self
+            .initial_backoff_secs
+            .saturating_mul(1u64 << exponent)
+            .min(self.max_backoff_secs)
+            .min(MAX_BACKOFF_SECS);
+        Duration::seconds(secs as i64)
+    }
+}
+
+// 守护事件类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisorEventKind {
+    Started,
+    Restarted,
+    /// 以退出码 0 正常退出
+    Exited,
+    /// 以非零退出码或信号退出，或监视的进程消失
+    Crashed,
+    /// 监视的进程在重启前自行恢复运行
+    Recovered,
+    RestartFailed,
+    /// 达到重启次数上限
+    GaveUp,
+    /// 规则停用或删除时停止了由应用启动的进程
+    Stopped,
+}
+
+impl SupervisorEventKind {
+    /// 事件类型的存储名称
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Started => "started",
+            Self::Restarted => "restarted",
+            Self::Exited => "exited",
+            Self::Crashed => "crashed",
+            Self::Recovered => "recovered",
+            Self::RestartFailed => "restart_failed",
+            Self::GaveUp => "gave_up",
+            Self::Stopped => "stopped",
+        }
+    }
+
+    /// 按存储名称解析事件类型
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "started" => Some(Self::Started),
+            "restarted" => Some(Self::Restarted),
+            "exited" => Some(Self::Exited),
+            "crashed" => Some(Self::Crashed),
+            "recovered" => Some(Self::Recovered),
+            "restart_failed" => Some(Self::RestartFailed),
+            "gave_up" => Some(Self::GaveUp),
+            "stopped" => Some(Self::Stopped),
+            _ => None,
+        }
+    }
+}
+
+// 一条守护事件
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SupervisorEvent {
+    pub id: String,
+    pub rule_id: String,
+    pub rule_name: String,
+    pub kind: SupervisorEventKind,
+    pub pid: Option<u32>,
+    /// 仅由应用启动的进程可以取得退出码
+    pub exit_code: Option<i32>,
+    /// 终止进程的信号编号
+    pub signal: Option<i32>,
+    pub message: Option<String>,
+    /// 事件发生时的累计重启次数
+    pub restart_count: u32,
+    pub created_at: DateTime<Utc>,
+}
+
+// 规则当前的运行状态
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SupervisedState {
+    /// 尚未检查或尚未见到监视的进程
+    #[default]
+    Pending,
+    Running,
+    /// 等待退避结束后重启
+    Backoff,
+    /// 监视的进程已退出且没有重启命令
+    Stopped,
+    /// 达到重启次数上限
+    Failed,
+    Disabled,
+    /// 存储的规则无法解析，不会被守护
+    Invalid,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SupervisorStatus {
+    pub rule_id: String,
+    pub rule_name: String,
+    pub state: SupervisedState,
+    pub pid: Option<u32>,
+    /// 进程由应用启动，退出时可以取得退出码
+    pub launched: bool,
+    pub restart_count: u32,
+    pub consecutive_failures: u32,
+    pub last_exit_code: Option<i32>,
+    pub running_since: Option<DateTime<Utc>>,
+    pub next_restart_at: Option<DateTime<Utc>>,
+    /// 规则无法解析时的原因
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+// 数据库中无法解析的规则
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InvalidSupervisorRule {
+    pub id: String,
+    pub name: String,
+    pub message: String,
+}
+
+impl InvalidSupervisorRule {
+    /// 以 Invalid 状态出现在状态列表中
+    pub fn status(&self) -> SupervisorStatus {
+        SupervisorStatus {
+            rule_id: self.id.clone(),
+            rule_name: self.name.clone(),
+            state: SupervisedState::Invalid,
+            pid: None,
+            launched: false,
+            restart_count: 0,
+            consecutive_failures: 0,
+            last_exit_code: None,
+            running_since: None,
+            next_restart_at: None,
+            error: Some(self.message.clone()),
+        }
+    }
+}
+
+// 跟踪中的进程
+#[derive(Debug)]
+enum Tracked {
+    /// 由应用启动，可以取得退出码
+    Child(Child),
+    /// 接管或监视的外部进程
+    Pid(u32),
+}
+
+impl Tracked {
+    fn pid(&self) -> u32 {
+        match self {
+            Self::Child(child) => child.id(),
+            Self::Pid(pid) => *pid,
+        }
+    }
+}
+
+// 进程的退出信息
+struct ExitInfo {
+    pid: u32,
+    code: Option<i32>,
+    signal: Option<i32>,
+    message: Option<String>,
+}
+
+impl ExitInfo {
+    fn from_status(pid: u32, status: ExitStatus) -> Self {
+        #[cfg(unix)]
+        let signal = std::os::unix::process::ExitStatusExt::signal(&status);
+        #[cfg(not(unix))]
+        let signal = None;
+        Self {
+            pid,
+            code: status.code(),
+            signal,
+            message: Some(status.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct RuleRuntime {
+    rule_name: String,
+    tracked: Option<Tracked>,
+    state: SupervisedState,
+    restart_count: u32,
+    consecutive_failures: u32,
+    last_exit_code: Option<i32>,
+    running_since: Option<DateTime<Utc>>,
+    next_restart_at: Option<DateTime<Utc>>,
+}
+
+impl RuleRuntime {
+    fn event(&self, rule_id: &str, kind: SupervisorEventKind, pid: Option<u32>, now: DateTime<Utc>) -> SupervisorEvent {
+        SupervisorEvent {
+            id: Uuid::new_v4().to_string(),
+            rule_id: rule_id.to_string(),
+            rule_name: self.rule_name.clone(),
+            kind,
+            pid,
+            exit_code: None,
+            signal: None,
+            message: None,
+            restart_count: self.restart_count,
+            created_at: now,
+        }
+    }
+}
+
+/// 守护进程的运行状态，由 Tauri 托管；规则本身保存在数据库中
+#[derive(Debug, Clone, Default)]
+pub struct Supervisor {
+    runtimes: Arc<Mutex<HashMap<String, RuleRuntime>>>,
+}
+
+impl Supervisor {
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, RuleRuntime>> {
+        self.runtimes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// 执行一次检查：发现退出的进程，到期时重启，停止已停用或删除的规则启动的进程
+    pub fn tick(&self, rules: &[SupervisorRule], processes: &[ProcessEntry], now: DateTime<Utc>) -> Vec<SupervisorEvent> {
+        let mut events = Vec::new();
+        let active: HashSet<&str> = rules.iter().filter(|rule| rule.enabled).map(|rule| rule.id.as_str()).collect();
+        let stale: Vec<String> = self.lock().keys().filter(|id| !active.contains(id.as_str())).cloned().collect();
+        for id in stale {
+            events.extend(self.stop(&id, now));
+        }
+
+        let mut runtimes = self.lock();
+        for rule in rules.iter().filter(|rule| rule.enabled) {
+            let runtime = runtimes.entry(rule.id.clone()).or_default();
+            runtime.rule_name = rule.name.clone();
+            check_rule(rule, runtime, processes, now, &mut events);
+        }
+        events
+    }
+
+    /// 停止跟踪规则；由应用启动的进程在后台逐级终止，返回 Stopped 事件
+    pub fn stop(&self, rule_id: &str, now: DateTime<Utc>) -> Option<SupervisorEvent> {
+        let mut runtime = self.lock().remove(rule_id)?;
+        let Some(Tracked::Child(mut child)) = runtime.tracked.take() else {
+            return None;
+        };
+        let pid = child.id();
+        std::thread::spawn(move || {
+            let _ = terminate_gracefully(pid, SignalScope::Process, STOP_GRACE_PERIOD);
+            // 回收子进程，避免留下僵尸进程
+            let _ = child.wait();
+        });
+        Some(runtime.event(rule_id, SupervisorEventKind::Stopped, Some(pid), now))
+    }
+
+    /// 应用重启后按规则最近的事件（从新到旧）恢复重启次数、连续失败次数和放弃状态；
+    /// 已在跟踪的规则不受影响
+    pub fn restore(&self, rule: &SupervisorRule, events: &[SupervisorEvent]) {
+        let mut runtimes = self.lock();
+        if runtimes.contains_key(&rule.id) {
+            return;
+        }
+        let mut runtime = RuleRuntime {
+            rule_name: rule.name.clone(),
+            ..Default::default()
+        };
+        let mut last_start = None;
+        for event in events.iter().rev() {
+            runtime.restart_count = event.restart_count;
+            match event.kind {
+                SupervisorEventKind::Started | SupervisorEventKind::Restarted | SupervisorEventKind::Recovered => {
+                    last_start = Some(event.created_at);
+                    runtime.state = SupervisedState::Pending;
+                }
+                SupervisorEventKind::Exited | SupervisorEventKind::Crashed => {
+                    if last_start.is_some_and(|since| event.created_at - since >= Duration::seconds(BACKOFF_RESET_SECS)) {
+                        runtime.consecutive_failures = 0;
+                    }
+                    runtime.consecutive_failures += 1;
+                    runtime.last_exit_code = event.exit_code;
+                    last_start = None;
+                }
+                SupervisorEventKind::RestartFailed => runtime.consecutive_failures += 1,
+                SupervisorEventKind::GaveUp => runtime.state = SupervisedState::Failed,
+                // 停用或删除后重新开始计数
+                SupervisorEventKind::Stopped => {
+                    runtime = RuleRuntime {
+                        rule_name: rule.name.clone(),
+                        ..Default::default()
+                    };
+                    last_start = None;
+                }
+            }
+        }
+        runtimes.insert(rule.id.clone(), runtime);
+    }
+
+    /// 各规则的当前状态，按规则顺序排列
+    pub fn status(&self, rules: &[SupervisorRule]) -> Vec<SupervisorStatus> {
+        let runtimes = self.lock();
+        rules
+            .iter()
+            .map(|rule| {
+                let runtime = runtimes.get(&rule.id).filter(|_| rule.enabled);
+                SupervisorStatus {
+                    rule_id: rule.id.clone(),
+                    rule_name: rule.name.clone(),
+                    state: match runtime {
+                        Some(runtime) => runtime.state,
+                        None if rule.enabled => SupervisedState::Pending,
+                        None => SupervisedState::Disabled,
+                    },
+                    pid: runtime.and_then(|runtime| runtime.tracked.as_ref()).map(Tracked::pid),
+                    launched: runtime.is_some_and(|runtime| matches!(runtime.tracked, Some(Tracked::Child(_)))),
+                    restart_count: runtime.map_or(0, |runtime| runtime.restart_count),
+                    consecutive_failures: runtime.map_or(0, |runtime| runtime.consecutive_failures),
+                    last_exit_code: runtime.and_then(|runtime| runtime.last_exit_code),
+                    running_since: runtime.and_then(|runtime| runtime.running_since),
+                    next_restart_at: runtime.and_then(|runtime| runtime.next_restart_at),
+                    error: None,
+                }
+            })
+            .collect()
+    }
+}
+
+fn check_rule(
+    rule: &SupervisorRule,
+    runtime: &mut RuleRuntime,
+    processes: &[ProcessEntry],
+    now: DateTime<Utc>,
+    events: &mut Vec<SupervisorEvent>,
+) {
+    // 检查跟踪中的进程是否已退出
+    let exit = match &mut runtime.tracked {
+        Some(Tracked::Child(child)) => {
+            let pid = child.id();
+            match child.try_wait() {
+                Ok(None) => None,
+                Ok(Some(status)) => Some(ExitInfo::from_status(pid, status)),
+                Err(e) => Some(ExitInfo {
+                    pid,
+                    code: None,
+                    signal: None,
+                    message: Some(format!("Failed to wait for process: {}", e)),
+                }),
+            }
+        }
+        Some(Tracked::Pid(pid)) => {
+            let alive = match &rule.target {
+                SupervisorTarget::Command(_) => processes.iter().any(|entry| entry.pid == *pid),
+                // 同名进程可能有多个，任意一个仍在运行即视为运行中
+                SupervisorTarget::Process { .. } => match rule.target.find(processes) {
+                    Some(found) => {
+                        *pid = found;
+                        true
+                    }
+                    None => false,
+                },
+            };
+            (!alive).then(|| ExitInfo {
+                pid: *pid,
+                code: None,
+                signal: None,
+                message: Some("Process is no longer running".to_string()),
+            })
+        }
+        None => None,
+    };
+    if let Some(exit) = exit {
+        handle_exit(rule, runtime, exit, now, events);
+    }
+    if runtime.tracked.is_some() {
+        return;
+    }
+
+    // 已有实例在运行时直接接管
+    if let Some(pid) = rule.target.find(processes) {
+        if matches!(runtime.state, SupervisedState::Backoff | SupervisedState::Stopped | SupervisedState::Failed) {
+            events.push(runtime.event(&rule.id, SupervisorEventKind::Recovered, Some(pid), now));
+        }
+        runtime.tracked = Some(Tracked::Pid(pid));
+        runtime.state = SupervisedState::Running;
+        runtime.running_since = Some(now);
+        runtime.next_restart_at = None;
+        return;
+    }
+
+    let Some(command) = rule.target.launch_command() else {
+        return;
+    };
+    let kind = match runtime.state {
+        SupervisedState::Pending => SupervisorEventKind::Started,
+        SupervisedState::Backoff if runtime.next_restart_at.unwrap_or(now) <= now => SupervisorEventKind::Restarted,
+        _ => return,
+    };
+    if kind == SupervisorEventKind::Restarted {
+        runtime.restart_count += 1;
+    }
+    match command.spawn() {
+        Ok(child) => {
+            let pid = child.id();
+            runtime.tracked = Some(Tracked::Child(child));
+            runtime.state = SupervisedState::Running;
+            runtime.running_since = Some(now);
+            runtime.next_restart_at = None;
+            events.push(runtime.event(&rule.id, kind, Some(pid), now));
+        }
+        Err(e) => {
+            let mut event = runtime.event(&rule.id, SupervisorEventKind::RestartFailed, None, now);
+            event.message = Some(format!("Failed to start {}: {}", command.program, e));
+            events.push(event);
+            schedule_restart(rule, runtime, now, events);
+        }
+    }
+}
+
+fn handle_exit(
+    rule: &SupervisorRule,
+    runtime: &mut RuleRuntime,
+    exit: ExitInfo,
+    now: DateTime<Utc>,
+    events: &mut Vec<SupervisorEvent>,
+) {
+    // 运行足够久之后退出，不算作连续失败
+    if runtime
+        .running_since
+        .is_some_and(|since| now - since >= Duration::seconds(BACKOFF_RESET_SECS))
+    {
+        runtime.consecutive_failures = 0;
+    }
+    let kind = if exit.code == Some(0) {
+        SupervisorEventKind::Exited
+    } else {
+        SupervisorEventKind::Crashed
+    };
+    let mut event = runtime.event(&rule.id, kind, Some(exit.pid), now);
+    event.exit_code = exit.code;
+    event.signal = exit.signal;
+    event.message = exit.message;
+    events.push(event);
+
+    runtime.tracked = None;
+    runtime.last_exit_code = exit.code;
+    runtime.running_since = None;
+    if rule.target.launch_command().is_some() {
+        schedule_restart(rule, runtime, now, events);
+    } else {
+        runtime.state = SupervisedState::Stopped;
+    }
+}
+
+fn schedule_restart(rule: &SupervisorRule, runtime: &mut RuleRuntime, now: DateTime<Utc>, events: &mut Vec<SupervisorEvent>) {
+    if rule.max_restarts.is_some_and(|max| runtime.restart_count >= max) {
+        runtime.state = SupervisedState::Failed;
+        runtime.next_restart_at = None;
+        let mut event = runtime.event(&rule.id, SupervisorEventKind::GaveUp, None, now);
+        event.message = Some(format!("Gave up after {} restarts", runtime.restart_count));
+        events.push(event);
+        return;
+    }
+    runtime.consecutive_failures += 1;
+    runtime.state = SupervisedState::Backoff;
+    let delay = rule.backoff_delay(runtime.consecutive_failures);
+    runtime.next_restart_at = Some(now.checked_add_signed(delay).unwrap_or(DateTime::<Utc>::MAX_UTC));
+}
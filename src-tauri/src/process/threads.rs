@@ -0,0 +1,141 @@
+//! 进程线程
+//! 从 /proc/<pid>/task 读取每个线程的状态和 CPU 时间，间隔采样两次得到 CPU 使用率，
+//! 用于找出服务中占满 CPU 的线程
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::procfs::ProcStat;
+
+// 线程状态，对应 stat 中的状态字符
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskState {
+    Running,
+    Sleeping,
+    /// 不可中断的等待，通常是磁盘 I/O
+    DiskSleep,
+    Stopped,
+    TracingStop,
+    Zombie,
+    Dead,
+    Idle,
+    Unknown,
+}
+
+impl TaskState {
+    pub fn from_code(code: char) -> Self {
+        match code {
+            'R' => Self::Running,
+            'S' => Self::Sleeping,
+            'D' => Self::DiskSleep,
+            'T' => Self::Stopped,
+            't' => Self::TracingStop,
+            'Z' => Self::Zombie,
+            'X' | 'x' => Self::Dead,
+            'I' => Self::Idle,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    pub name: String,
+    pub state: TaskState,
+    /// 累计 CPU 时间（用户态 + 内核态），单位为秒
+    pub cpu_time_secs: f64,
+    pub user_time_secs: f64,
+    pub system_time_secs: f64,
+    /// 采样间隔内的 CPU 使用率，100 表示占满一个核心
+    pub cpu_usage_percent: f32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessThreads {
+    pub pid: u32,
+    pub thread_count: u32,
+    /// 按 CPU 使用率从高到低排列
+    pub threads: Vec<ThreadInfo>,
+    pub sample_interval_ms: u64,
+}
+
+/// 根据两次采样计算线程列表；采样间隔内新建的线程，其 CPU 时间全部计入本次间隔
+pub fn build_thread_list(
+    previous: &[ProcStat],
+    current: &[ProcStat],
+    elapsed: Duration,
+    ticks_per_second: u64,
+) -> Vec<ThreadInfo> {
+    let ticks_per_second = ticks_per_second.max(1) as f64;
+    let elapsed_ticks = elapsed.as_secs_f64() * ticks_per_second;
+
+    let mut threads: Vec<ThreadInfo> = current
+        .iter()
+        .map(|stat| {
+            let total = stat.user_ticks + stat.system_ticks;
+            let before = previous
+                .iter()
+                .find(|earlier| earlier.pid == stat.pid)
+                .map_or(0, |earlier| earlier.user_ticks + earlier.system_ticks);
+            let cpu_usage_percent = if elapsed_ticks > 0.0 {
+                (total.saturating_sub(before) as f64 / elapsed_ticks * 100.0) as f32
+            } else {
+                0.0
+            };
+            ThreadInfo {
+                tid: stat.pid,
+                name: stat.comm.clone(),
+                state: TaskState::from_code(stat.state),
+                cpu_time_secs: total as f64 / ticks_per_second,
+                user_time_secs: stat.user_ticks as f64 / ticks_per_second,
+                system_time_secs: stat.system_ticks as f64 / ticks_per_second,
+                cpu_usage_percent,
+            }
+        })
+        .collect();
+    threads.sort_by(|a, b| b.cpu_usage_percent.total_cmp(&a.cpu_usage_percent).then(a.tid.cmp(&b.tid)));
+    threads
+}
+
+/// 进程的线程数，无法读取时为 0
+pub fn thread_count(pid: u32) -> u32 {
+    ProcStat::read(pid).map_or(0, |stat| stat.num_threads)
+}
+
+/// 读取 /proc/<pid>/task 下所有线程的 stat，读取期间退出的线程会被跳过
+#[cfg(target_os = "linux")]
+fn read_tasks(pid: u32) -> Result<Vec<ProcStat>, String> {
+    let entries = std::fs::read_dir(format!("/proc/{}/task", pid)).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("Process with PID {} not found", pid),
+        _ => format!("Failed to list threads of process {}: {}", pid, e),
+    })?;
+    Ok(entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse::<u32>().ok())
+        .filter_map(|tid| ProcStat::read_task(pid, tid).ok())
+        .collect())
+}
+
+/// 列出进程的线程，间隔 `sample_interval` 采样两次计算 CPU 使用率
+#[cfg(target_os = "linux")]
+pub fn list_threads(pid: u32, sample_interval: Duration) -> Result<ProcessThreads, String> {
+    let previous = read_tasks(pid)?;
+    let started = std::time::Instant::now();
+    std::thread::sleep(sample_interval);
+    let current = read_tasks(pid)?;
+    let threads = build_thread_list(&previous, &current, started.elapsed(), super::procfs::clock_ticks_per_second());
+
+    Ok(ProcessThreads {
+        pid,
+        thread_count: threads.len() as u32,
+        threads,
+        sample_interval_ms: sample_interval.as_millis() as u64,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn list_threads(_pid: u32, _sample_interval: Duration) -> Result<ProcessThreads, String> {
+    Err("Thread listing is only supported on Linux".to_string())
+}
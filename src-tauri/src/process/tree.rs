@@ -0,0 +1,191 @@
+//! 进程树
+//! 按父进程关系组织进程，汇总每棵子树的 CPU 和内存占用；
+//! 过滤时保留匹配进程的祖先链，使结果仍是一棵完整的树
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::users::user_matches;
+use super::ProcessEntry;
+
+// 进程树过滤条件，各条件之间为“与”关系
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ProcessTreeFilter {
+    /// 按进程名或命令行匹配，不区分大小写
+    #[serde(default)]
+    pub name: Option<String>,
+    /// uid 或用户名
+    #[serde(default)]
+    pub user: Option<String>,
+    #[serde(default)]
+    pub min_cpu_usage: Option<f32>,
+    #[serde(default)]
+    pub min_memory_usage: Option<u64>,
+}
+
+impl ProcessTreeFilter {
+    /// 判断单个进程是否匹配，不考虑其子进程
+    pub fn matches(&self, entry: &ProcessEntry) -> bool {
+        if let Some(name) = &self.name {
+            let name = name.to_lowercase();
+            if !entry.name.to_lowercase().contains(&name) && !entry.command_line.to_lowercase().contains(&name) {
+                return false;
+            }
+        }
+        if let Some(user) = &self.user {
+            if !user_matches(user, entry.user_id.as_deref(), entry.user.as_deref()) {
+                return false;
+            }
+        }
+        if self.min_cpu_usage.is_some_and(|min| entry.cpu_usage_percent < min) {
+            return false;
+        }
+        if self.min_memory_usage.is_some_and(|min| entry.memory_usage_bytes < min) {
+            return false;
+        }
+        true
+    }
+}
+
+// 进程树节点
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessTreeNode {
+    #[serde(flatten)]
+    pub process: ProcessEntry,
+    /// 本进程及所有后代进程的合计，不受过滤影响
+    pub subtree_cpu_usage_percent: f32,
+    pub subtree_memory_usage_bytes: u64,
+    pub descendant_count: u32,
+    /// 本进程是否匹配过滤条件；为保留祖先链而加入的进程为 false
+    pub matched: bool,
+    /// 按 PID 排列
+    pub children: Vec<ProcessTreeNode>,
+}
+
+// 进程的祖先与后代
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ProcessLineage {
+    /// 从父进程到根进程依次排列
+    pub ancestors: Vec<ProcessEntry>,
+    /// 以该进程为根的子树
+    pub subtree: ProcessTreeNode,
+}
+
+// 按父进程建立的索引
+struct ProcessIndex<'a> {
+    entries: HashMap<u32, &'a ProcessEntry>,
+    children: HashMap<u32, Vec<u32>>,
+    roots: Vec<u32>,
+}
+
+impl<'a> ProcessIndex<'a> {
+    fn new(entries: &'a [ProcessEntry]) -> Self {
+        let by_pid: HashMap<u32, &ProcessEntry> = entries.iter().map(|entry| (entry.pid, entry)).collect();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        let mut roots = Vec::new();
+
+        for entry in entries {
+            // 父进程已退出或不可见的进程作为根节点
+            match entry.parent_pid.filter(|parent| *parent != entry.pid && by_pid.contains_key(parent)) {
+                Some(parent) => children.entry(parent).or_default().push(entry.pid),
+                None => roots.push(entry.pid),
+            }
+        }
+        for pids in children.values_mut() {
+            pids.sort_unstable();
+        }
+        roots.sort_unstable();
+
+        Self {
+            entries: by_pid,
+            children,
+            roots,
+        }
+    }
+
+    /// 构建以 `pid` 为根的子树，`visited` 防止 PID 复用造成的环
+    fn build(&self, pid: u32, filter: Option<&ProcessTreeFilter>, visited: &mut HashSet<u32>) -> Option<ProcessTreeNode> {
+        if !visited.insert(pid) {
+            return None;
+        }
+        let entry = *self.entries.get(&pid)?;
+
+        let mut subtree_cpu_usage_percent = entry.cpu_usage_percent;
+        let mut subtree_memory_usage_bytes = entry.memory_usage_bytes;
+        let mut descendant_count = 0;
+        let mut children = Vec::new();
+        for child in self.children.get(&pid).into_iter().flatten() {
+            let Some(node) = self.build(*child, filter, visited) else {
+                continue;
+            };
+            subtree_cpu_usage_percent += node.subtree_cpu_usage_percent;
+            subtree_memory_usage_bytes += node.subtree_memory_usage_bytes;
+            descendant_count += node.descendant_count + 1;
+            children.push(node);
+        }
+
+        let matched = match filter {
+            Some(filter) => filter.matches(entry),
+            None => true,
+        };
+        Some(ProcessTreeNode {
+            process: entry.clone(),
+            subtree_cpu_usage_percent,
+            subtree_memory_usage_bytes,
+            descendant_count,
+            matched,
+            children,
+        })
+    }
+
+    /// 去掉既不匹配、也没有匹配后代的节点
+    fn prune(mut node: ProcessTreeNode) -> Option<ProcessTreeNode> {
+        node.children = std::mem::take(&mut node.children).into_iter().filter_map(Self::prune).collect();
+        (node.matched || !node.children.is_empty()).then_some(node)
+    }
+}
+
+/// 构建进程树，返回按 PID 排列的根节点；指定过滤条件时只保留匹配的进程及其祖先
+pub fn build_process_tree(entries: &[ProcessEntry], filter: Option<&ProcessTreeFilter>) -> Vec<ProcessTreeNode> {
+    let index = ProcessIndex::new(entries);
+    let mut visited = HashSet::new();
+
+    let mut roots: Vec<ProcessTreeNode> = index
+        .roots
+        .iter()
+        .filter_map(|pid| index.build(*pid, filter, &mut visited))
+        .collect();
+    // 父进程关系成环（PID 复用）时环上没有根节点，从环上 PID 最小的进程开始构建
+    let mut unvisited: Vec<u32> = entries.iter().map(|entry| entry.pid).filter(|pid| !visited.contains(pid)).collect();
+    unvisited.sort_unstable();
+    for pid in unvisited {
+        if let Some(node) = index.build(pid, filter, &mut visited) {
+            roots.push(node);
+        }
+    }
+
+    if filter.is_some() {
+        roots = roots.into_iter().filter_map(ProcessIndex::prune).collect();
+    }
+    roots
+}
+
+/// 查找进程的祖先链和后代子树
+pub fn process_lineage(entries: &[ProcessEntry], pid: u32) -> Option<ProcessLineage> {
+    let index = ProcessIndex::new(entries);
+    let subtree = index.build(pid, None, &mut HashSet::new())?;
+
+    let mut ancestors = Vec::new();
+    let mut seen = HashSet::from([pid]);
+    let mut current = subtree.process.parent_pid;
+    while let Some(parent) = current.filter(|parent| seen.insert(*parent)) {
+        let Some(entry) = index.entries.get(&parent) else {
+            break;
+        };
+        ancestors.push((*entry).clone());
+        current = entry.parent_pid;
+    }
+
+    Some(ProcessLineage { ancestors, subtree })
+}
@@ -0,0 +1,137 @@
+//! 用户与用户组
+//! 把进程的 uid、gid 解析为名称；用户和组列表读取一次后缓存，过期后重新读取，
+//! 避免每次列出进程都解析 /etc/passwd 和 /etc/group
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use sysinfo::{Groups, Process, Users};
+
+// 缓存的有效期，新建的用户最迟在这段时间后可以解析
+const CACHE_TTL: Duration = Duration::from_secs(300);
+
+static CACHE: Mutex<Option<(Instant, Arc<UserDirectory>)>> = Mutex::new(None);
+
+// 系统中的一个用户
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct UserInfo {
+    pub uid: String,
+    pub name: String,
+    pub primary_group_id: String,
+    pub primary_group: Option<String>,
+    /// 所属的全部用户组名称
+    pub groups: Vec<String>,
+}
+
+// 进程所属的用户和组
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct ProcessOwner {
+    pub user_id: Option<String>,
+    /// 无法解析时为空
+    pub user_name: Option<String>,
+    pub group_id: Option<String>,
+    pub group_name: Option<String>,
+}
+
+impl ProcessOwner {
+    /// 用于显示的用户名，无法解析时退回 uid
+    pub fn display_user(&self) -> Option<String> {
+        self.user_name.clone().or_else(|| self.user_id.clone())
+    }
+}
+
+// uid 和 gid 到名称的映射
+#[derive(Debug, Clone, Default)]
+pub struct UserDirectory {
+    users: HashMap<String, String>,
+    groups: HashMap<String, String>,
+}
+
+impl UserDirectory {
+    /// 由 (id, 名称) 列表构建
+    pub fn from_entries(users: Vec<(String, String)>, groups: Vec<(String, String)>) -> Self {
+        Self {
+            users: users.into_iter().collect(),
+            groups: groups.into_iter().collect(),
+        }
+    }
+
+    /// 读取系统的用户和组列表
+    pub fn load() -> Self {
+        let users = Users::new_with_refreshed_list();
+        let groups = Groups::new_with_refreshed_list();
+        Self::from_entries(
+            users.iter().map(|user| (user.id().to_string(), user.name().to_string())).collect(),
+            groups.iter().map(|group| (group.id().to_string(), group.name().to_string())).collect(),
+        )
+    }
+
+    /// 返回缓存的目录，缓存过期时重新读取
+    pub fn cached() -> Arc<Self> {
+        let mut cache = CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match cache.as_ref() {
+            Some((loaded_at, directory)) if loaded_at.elapsed() < CACHE_TTL => directory.clone(),
+            _ => {
+                let directory = Arc::new(Self::load());
+                *cache = Some((Instant::now(), directory.clone()));
+                directory
+            }
+        }
+    }
+
+    pub fn user_name(&self, uid: &str) -> Option<&str> {
+        self.users.get(uid).map(String::as_str)
+    }
+
+    pub fn group_name(&self, gid: &str) -> Option<&str> {
+        self.groups.get(gid).map(String::as_str)
+    }
+
+    /// 解析进程的用户和组
+    pub fn owner(&self, process: &Process) -> ProcessOwner {
+        let user_id = process.user_id().map(|uid| uid.to_string());
+        let group_id = process.group_id().map(|gid| gid.to_string());
+        ProcessOwner {
+            user_name: user_id.as_deref().and_then(|uid| self.user_name(uid)).map(str::to_string),
+            group_name: group_id.as_deref().and_then(|gid| self.group_name(gid)).map(str::to_string),
+            user_id,
+            group_id,
+        }
+    }
+}
+
+/// 按 uid 或用户名过滤：与 uid 完全相同，或用户名包含过滤文本（不区分大小写）
+pub fn user_matches(filter: &str, user_id: Option<&str>, user_name: Option<&str>) -> bool {
+    let filter = filter.trim();
+    if user_id == Some(filter) {
+        return true;
+    }
+    let filter = filter.to_lowercase();
+    user_name.is_some_and(|name| name.to_lowercase().contains(&filter))
+}
+
+/// 列出系统中的用户及其所属组，按用户名排列
+pub fn list_users() -> Vec<UserInfo> {
+    let directory = UserDirectory::cached();
+    let users = Users::new_with_refreshed_list();
+    let mut list: Vec<UserInfo> = users
+        .iter()
+        .map(|user| {
+            let primary_group_id = user.group_id().to_string();
+            let mut groups: Vec<String> = user.groups().iter().map(|group| group.name().to_string()).collect();
+            groups.sort();
+            groups.dedup();
+            UserInfo {
+                uid: user.id().to_string(),
+                name: user.name().to_string(),
+                primary_group: directory.group_name(&primary_group_id).map(str::to_string),
+                primary_group_id,
+                groups,
+            }
+        })
+        .collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
@@ -0,0 +1,148 @@
+//! 异常检测测试
+//! 覆盖 EWMA 与季节性基线、灵敏度和方向，以及历史扫描
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    validate_configuration, AlertConfiguration, AnomalyDetection, AnomalyDirection, AnomalyMethod,
+    MAX_ANOMALY_LOOKBACK_HOURS,
+};
+
+fn detection(method: AnomalyMethod, sensitivity: f64) -> AnomalyDetection {
+    AnomalyDetection {
+        method,
+        sensitivity,
+        direction: AnomalyDirection::Both,
+        lookback_hours: None,
+        ewma_alpha: None,
+    }
+}
+
+// 每 5 分钟一个样本，在 40 与 50 之间交替
+fn steady_series(start: DateTime<Utc>, count: i64) -> Vec<(DateTime<Utc>, f64)> {
+    (0..count)
+        .map(|i| (start + Duration::minutes(5 * i), if i % 2 == 0 { 40.0 } else { 50.0 }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ewma_baseline_and_sensitivity() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+        let series = steady_series(start, 100);
+        let now = start + Duration::minutes(500);
+
+        let strict = detection(AnomalyMethod::Ewma, 3.0);
+        let baseline = strict.baseline(&series, now).unwrap();
+        assert!((baseline.expected - 45.0).abs() < 3.0);
+        assert_eq!(baseline.samples, 100);
+
+        // 大幅偏离才会触发，期望范围包含正常波动
+        let verdict = strict.evaluate(&baseline, 95.0);
+        assert!(verdict.is_anomaly);
+        assert!(verdict.lower < 40.0 && verdict.upper > 50.0);
+        assert!(!strict.evaluate(&baseline, 50.0).is_anomaly);
+
+        // 降低灵敏度倍数后更容易触发
+        let sensitive = detection(AnomalyMethod::Ewma, 0.5);
+        assert!(sensitive.evaluate(&baseline, 50.0).is_anomaly);
+
+        // 只关注偏高的异常
+        let above = AnomalyDetection {
+            direction: AnomalyDirection::Above,
+            ..strict.clone()
+        };
+        assert!(!above.evaluate(&baseline, 0.0).is_anomaly);
+        assert!(strict.evaluate(&baseline, 0.0).is_anomaly);
+
+        // 样本不足时没有基线
+        assert!(strict.baseline(&series[..5], now).is_none());
+    }
+
+    #[test]
+    fn test_seasonal_baseline_uses_hour_of_week() {
+        let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+        // 过去四个周一 09:00（本地时间）负载较高，其他时间较低
+        let monday_9am = tz.with_ymd_and_hms(2024, 3, 25, 9, 0, 0).unwrap().with_timezone(&Utc);
+        let mut series = Vec::new();
+        for week in 1..=4 {
+            let base = monday_9am - Duration::weeks(week);
+            series.push((base, 80.0 + week as f64));
+            series.push((base + Duration::hours(3), 10.0));
+            series.push((base - Duration::hours(3), 12.0));
+        }
+
+        let seasonal = detection(AnomalyMethod::Seasonal, 3.0);
+        let baseline = seasonal.baseline_in(&series, monday_9am, &tz).unwrap();
+        assert_eq!(baseline.samples, 4);
+        assert!((baseline.expected - 82.5).abs() < 1e-9);
+
+        // 周一上午的高负载属于正常，同样的值在中午则是异常
+        assert!(!seasonal.evaluate(&baseline, 83.0).is_anomaly);
+        let noon = monday_9am + Duration::hours(3);
+        let noon_baseline = seasonal.baseline_in(&series, noon, &tz).unwrap();
+        assert!(seasonal.evaluate(&noon_baseline, 83.0).is_anomaly);
+
+        // 扫描时每个点只和之前同一时段比较，第四周之前样本不足
+        let points = seasonal.scan_in(&series, &tz);
+        assert!(points.is_empty());
+    }
+
+    #[test]
+    fn test_scan_and_validation() {
+        let start = Utc.with_ymd_and_hms(2024, 3, 4, 0, 0, 0).unwrap();
+        let mut series = steady_series(start, 50);
+        let spike_at = start + Duration::minutes(5 * 50);
+        series.push((spike_at, 99.0));
+
+        let points = detection(AnomalyMethod::Ewma, 3.0).scan(&series);
+        assert_eq!(points.len(), 1);
+        assert_eq!(points[0].timestamp, spike_at);
+        assert!(points[0].verdict.z_score > 3.0);
+
+        assert!(detection(AnomalyMethod::Ewma, 0.0).validate().is_err());
+        assert!(AnomalyDetection {
+            ewma_alpha: Some(1.5),
+            ..detection(AnomalyMethod::Ewma, 3.0)
+        }
+        .validate()
+        .is_err());
+        assert!(AnomalyDetection {
+            lookback_hours: Some(24),
+            ..detection(AnomalyMethod::Seasonal, 3.0)
+        }
+        .validate()
+        .is_err());
+        // 回看时长有上限，避免计算起始时间时溢出
+        let unbounded = AnomalyDetection {
+            lookback_hours: Some(u32::MAX),
+            ..detection(AnomalyMethod::Seasonal, 3.0)
+        };
+        assert!(unbounded.validate().unwrap_err().contains("must not exceed"));
+        assert!(AnomalyDetection {
+            lookback_hours: Some(MAX_ANOMALY_LOOKBACK_HOURS),
+            ..detection(AnomalyMethod::Seasonal, 3.0)
+        }
+        .validate()
+        .is_ok());
+        assert!(unbounded.lookback_start(Utc::now()).is_none());
+
+        // 作为规则类型使用时指标必须有历史记录
+        let json = r#"{
+            "id": "swap-anomaly",
+            "metric": "swap_usage",
+            "condition": "greater_than",
+            "threshold": 0,
+            "severity": "high",
+            "enabled": true,
+            "notification_methods": ["visual"],
+            "anomaly": { "method": "ewma", "sensitivity": 3 }
+        }"#;
+        let mut config: AlertConfiguration = serde_json::from_str(json).unwrap();
+        assert!(validate_configuration(&config).is_err());
+        config.metric = "cpu".parse().unwrap();
+        assert!(validate_configuration(&config).is_ok());
+    }
+}
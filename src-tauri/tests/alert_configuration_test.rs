@@ -0,0 +1,174 @@
+//! 警报配置模型测试
+//! 验证指标、比较方式和严重程度的序列化与旧写法兼容，规则校验，以及启动时对已存储规则的迁移
+
+use tauri_react_ai_lib::{
+    validate_configuration, AlertComparison, AlertConfiguration, AlertIssueAction, AlertMetric, AlertSeverity, DatabaseManager,
+};
+
+fn threshold_rule(metric: &str, threshold: f64) -> AlertConfiguration {
+    serde_json::from_value(serde_json::json!({
+        "id": "rule",
+        "metric": metric,
+        "condition": "greater_than",
+        "threshold": threshold,
+        "severity": "high",
+        "enabled": true,
+        "notification_methods": ["visual"]
+    }))
+    .unwrap()
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accepts_frontend_and_legacy_values() {
+        // 前端写法
+        let json = r#"{
+            "id": "",
+            "metric": "cpu_usage",
+            "condition": "greater_than",
+            "threshold": 90.0,
+            "severity": "critical",
+            "enabled": true,
+            "notification_methods": ["visual"]
+        }"#;
+        let config: AlertConfiguration = serde_json::from_str(json).unwrap();
+        assert_eq!(config.metric, AlertMetric::Cpu);
+        assert_eq!(config.condition, AlertComparison::GreaterThan);
+        assert_eq!(config.severity, AlertSeverity::Critical);
+        assert!(config.expression.is_none());
+
+        // 旧后端写法
+        assert_eq!("cpu".parse::<AlertMetric>().unwrap(), AlertMetric::Cpu);
+        assert_eq!(">=".parse::<AlertComparison>().unwrap(), AlertComparison::GreaterOrEqual);
+        assert_eq!(" Warning ".parse::<AlertSeverity>().unwrap(), AlertSeverity::Medium);
+    }
+
+    #[test]
+    fn test_serializes_canonical_values() {
+        let json = serde_json::to_string(&AlertMetric::Memory).unwrap();
+        assert_eq!(json, r#""memory_usage""#);
+        let json = serde_json::to_string(&AlertComparison::LessOrEqual).unwrap();
+        assert_eq!(json, r#""less_or_equal""#);
+        let json = serde_json::to_string(&AlertSeverity::High).unwrap();
+        assert_eq!(json, r#""high""#);
+    }
+
+    #[test]
+    fn test_rejects_unknown_values() {
+        let json = r#"{
+            "id": "",
+            "metric": "cpu_usgae",
+            "condition": ">",
+            "threshold": 90.0,
+            "severity": "high",
+            "enabled": true,
+            "notification_methods": []
+        }"#;
+        let err = serde_json::from_str::<AlertConfiguration>(json).unwrap_err();
+        assert!(err.to_string().contains("unknown metric `cpu_usgae`"));
+
+        assert!("=>".parse::<AlertComparison>().is_err());
+        assert!("urgent".parse::<AlertSeverity>().is_err());
+    }
+
+    #[test]
+    fn test_validate_configuration() {
+        assert!(validate_configuration(&threshold_rule("cpu_usage", 90.0)).is_ok());
+        assert!(validate_configuration(&threshold_rule("cpu_usage", 120.0)).unwrap_err().contains("between 0 and 100"));
+        assert!(validate_configuration(&threshold_rule("system_load", -1.0)).unwrap_err().contains("must not be negative"));
+        let mut rule = threshold_rule("system_load", 1.0);
+        rule.threshold = f64::NAN;
+        assert!(validate_configuration(&rule).unwrap_err().contains("finite"));
+        assert!(validate_configuration(&threshold_rule("cpu_cores", 4.0)).unwrap_err().contains("only be used inside an expression"));
+
+        let mut rule = threshold_rule("cpu_usage", 90.0);
+        rule.notification_methods.push(" ".to_string());
+        assert!(validate_configuration(&rule).unwrap_err().contains("empty entries"));
+
+        // 表达式规则不受阈值范围限制，但表达式必须能解析
+        let mut rule = threshold_rule("cpu_usage", 500.0);
+        rule.expression = Some("cpu > 90 and memory > 80".to_string());
+        assert!(validate_configuration(&rule).is_ok());
+        rule.expression = Some("cpu >".to_string());
+        assert!(validate_configuration(&rule).unwrap_err().starts_with("Invalid alert expression"));
+        rule.expression = Some(" ".to_string());
+        assert!(validate_configuration(&rule).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_startup_migration_only_reports_invalid_rules() {
+        let pool = memory_pool().await;
+        let manager = DatabaseManager::with_pool(pool.clone()).await.unwrap();
+        assert_eq!(manager.startup_migration_report().checked, 0);
+
+        let insert = |id: &'static str, metric: &'static str, condition: &'static str, threshold: f64| {
+            sqlx::query(
+                "INSERT INTO alert_configurations (id, metric, condition, threshold, severity, enabled, notification_methods) \
+                 VALUES (?, ?, ?, ?, 'high', 1, '[]')",
+            )
+            .bind(id)
+            .bind(metric)
+            .bind(condition)
+            .bind(threshold)
+            .execute(&pool)
+        };
+        // 旧写法可以修复，未知指标和超出范围的阈值无法修复
+        insert("legacy", "cpu", ">", 90.0).await.unwrap();
+        insert("unknown-metric", "cpu_usgae", "greater_than", 90.0).await.unwrap();
+        insert("out-of-range", "memory_usage", "greater_than", 150.0).await.unwrap();
+
+        // 无法解析的规则不会出现在列表中
+        let ids: Vec<String> = manager.get_alert_configurations().await.unwrap().into_iter().map(|rule| rule.id).collect();
+        assert!(!ids.contains(&"unknown-metric".to_string()));
+
+        // 启动时只报告问题，不修改规则
+        let restarted = DatabaseManager::with_pool(pool.clone()).await.unwrap();
+        let report = restarted.startup_migration_report();
+        assert_eq!((report.checked, report.repaired, report.disabled), (3, 0, 0));
+        assert!(report.issues.iter().all(|issue| issue.action == AlertIssueAction::Reported));
+        let rules = restarted.get_alert_configurations().await.unwrap();
+        assert!(rules.iter().find(|rule| rule.id == "out-of-range").unwrap().enabled);
+
+        // 迁移命令修复旧写法并禁用无法修复的规则
+        let report = restarted.migrate_alert_configurations(true).await.unwrap();
+        assert_eq!((report.checked, report.repaired, report.disabled), (3, 1, 2));
+        let mut disabled: Vec<&str> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.action == AlertIssueAction::Disabled)
+            .map(|issue| issue.id.as_str())
+            .collect();
+        disabled.sort_unstable();
+        assert_eq!(disabled, vec!["out-of-range", "unknown-metric"]);
+        assert_eq!(serde_json::to_value(AlertIssueAction::Repaired).unwrap(), "repaired");
+
+        let rules = restarted.get_alert_configurations().await.unwrap();
+        let legacy = rules.iter().find(|rule| rule.id == "legacy").unwrap();
+        assert_eq!((legacy.metric, legacy.condition, legacy.enabled), (AlertMetric::Cpu, AlertComparison::GreaterThan, true));
+        assert!(!rules.iter().find(|rule| rule.id == "out-of-range").unwrap().enabled);
+
+        // 再次迁移时不会重复禁用
+        assert_eq!(restarted.migrate_alert_configurations(true).await.unwrap().disabled, 0);
+    }
+
+    #[test]
+    fn test_comparison_apply() {
+        assert!(AlertComparison::GreaterThan.apply(91.0, 90.0));
+        assert!(!AlertComparison::GreaterThan.apply(90.0, 90.0));
+        assert!(AlertComparison::GreaterOrEqual.apply(90.0, 90.0));
+        assert!(AlertComparison::NotEquals.apply(1.0, 2.0));
+        assert!(AlertSeverity::Critical > AlertSeverity::High);
+    }
+}
@@ -0,0 +1,144 @@
+//! 警报升级策略测试
+//! 验证策略校验、升级级别计算和多步骤合并，以及持续触发和恢复后的升级次数
+
+use chrono::{Duration, Utc};
+use tauri_react_ai_lib::{
+    AlertConfiguration, AlertHistoryFilter, AlertSeverity, DatabaseManager, EscalationPolicy, EscalationStep,
+    IncidentGrouping, MessageEnvironment, MessageLocale, MetricSnapshot,
+};
+
+fn policy() -> EscalationPolicy {
+    EscalationPolicy {
+        id: "policy".to_string(),
+        name: "Critical on-call".to_string(),
+        steps: vec![
+            EscalationStep {
+                after_minutes: 5,
+                notification_methods: vec!["sound".to_string()],
+                raise_severity_to: None,
+            },
+            EscalationStep {
+                after_minutes: 15,
+                notification_methods: vec!["system_tray".to_string(), "sound".to_string()],
+                raise_severity_to: Some(AlertSeverity::High),
+            },
+            EscalationStep {
+                after_minutes: 30,
+                notification_methods: vec![],
+                raise_severity_to: Some(AlertSeverity::Critical),
+            },
+        ],
+    }
+}
+
+fn snapshot(cpu: f64) -> MetricSnapshot {
+    MetricSnapshot {
+        cpu,
+        memory: 50.0,
+        disk: 50.0,
+        network: 0.0,
+        load: 0.5,
+        swap: 0.0,
+        cores: 4.0,
+        open_fds: None,
+        open_fds_process: None,
+    }
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_validation() {
+        assert!(policy().validate().is_ok());
+
+        let mut unordered = policy();
+        unordered.steps[1].after_minutes = 5;
+        assert!(unordered.validate().unwrap_err().contains("Step 2"));
+
+        let mut empty_step = policy();
+        empty_step.steps[0].notification_methods.clear();
+        assert!(empty_step.validate().is_err());
+
+        let mut no_steps = policy();
+        no_steps.steps.clear();
+        assert!(no_steps.validate().is_err());
+    }
+
+    #[test]
+    fn test_level_after() {
+        let policy = policy();
+        assert_eq!(policy.level_after(4.9), 0);
+        assert_eq!(policy.level_after(5.0), 1);
+        assert_eq!(policy.level_after(20.0), 2);
+        assert_eq!(policy.level_after(120.0), 3);
+    }
+
+    #[test]
+    fn test_escalate_merges_due_steps() {
+        let policy = policy();
+
+        // 尚未到期或已处于目标级别时不升级
+        assert!(policy.escalate(1, 1, Some(AlertSeverity::Medium)).is_none());
+
+        let (methods, severity) = policy.escalate(0, 2, Some(AlertSeverity::Medium)).unwrap();
+        assert_eq!(methods, vec!["sound".to_string(), "system_tray".to_string()]);
+        assert_eq!(severity, Some(AlertSeverity::High));
+
+        // 不会降低已经更高的严重程度
+        let (_, severity) = policy.escalate(1, 2, Some(AlertSeverity::Critical)).unwrap();
+        assert_eq!(severity, Some(AlertSeverity::Critical));
+    }
+
+    #[tokio::test]
+    async fn test_escalate_once_per_episode_and_stop_after_resolve() {
+        let manager = DatabaseManager::with_pool(memory_pool().await).await.unwrap();
+        let policy_id = manager.add_escalation_policy(&policy()).await.unwrap();
+        let rule: AlertConfiguration = serde_json::from_value(serde_json::json!({
+            "id": "",
+            "metric": "cpu_usage",
+            "condition": "greater_than",
+            "threshold": 90,
+            "severity": "medium",
+            "enabled": true,
+            "notification_methods": ["visual"],
+            "escalation_policy_id": policy_id
+        }))
+        .unwrap();
+        manager.add_alert_configuration(&rule).await.unwrap();
+        let environment = MessageEnvironment {
+            hostname: "build-01".to_string(),
+            locale: MessageLocale::En,
+            top_processes: Vec::new(),
+        };
+        let grouping = IncidentGrouping::default();
+
+        // 连续三次检查都在触发，写入三条记录
+        for _ in 0..3 {
+            let configurations = manager.get_alert_configurations().await.unwrap();
+            manager.check_alerts(configurations, &snapshot(95.0), &environment, &grouping).await.unwrap();
+        }
+        let history = manager.get_alert_history(&AlertHistoryFilter::default(), None, 0).await.unwrap();
+        assert_eq!(history.len(), 3);
+
+        let escalations = manager.process_escalations(Utc::now() + Duration::minutes(6)).await.unwrap();
+        assert_eq!(escalations.len(), 1);
+        assert_eq!(escalations[0].step, 1);
+
+        // 规则恢复后不再升级
+        let configurations = manager.get_alert_configurations().await.unwrap();
+        manager.check_alerts(configurations, &snapshot(10.0), &environment, &grouping).await.unwrap();
+        let escalations = manager.process_escalations(Utc::now() + Duration::minutes(40)).await.unwrap();
+        assert!(escalations.is_empty());
+    }
+}
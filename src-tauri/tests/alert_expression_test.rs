@@ -0,0 +1,165 @@
+//! 警报表达式测试
+//! 覆盖表达式的解析、校验错误和求值
+
+use chrono::{Duration, Utc};
+use tauri_react_ai_lib::{
+    AlertExpression, DiskUsageData, EvaluationContext, HistoricalSystemData, MetricSnapshot,
+    NetworkTrafficData,
+};
+
+fn snapshot() -> MetricSnapshot {
+    MetricSnapshot {
+        cpu: 95.0,
+        memory: 60.0,
+        disk: 40.0,
+        network: 1000.0,
+        load: 20.0,
+        swap: 10.0,
+        cores: 8.0,
+        open_fds: Some(1500.0),
+        open_fds_process: None,
+    }
+}
+
+fn history_record(minutes_ago: i64, cpu_usage: f32) -> HistoricalSystemData {
+    HistoricalSystemData {
+        id: format!("record-{}", minutes_ago),
+        timestamp: Utc::now() - Duration::minutes(minutes_ago),
+        cpu_usage,
+        memory_usage: 50.0,
+        memory_total: 8589934592,
+        disk_usage: vec![DiskUsageData {
+            mount_point: "/".to_string(),
+            used_space: 50,
+            total_space: 100,
+            usage_percent: 50.0,
+        }],
+        network_traffic: NetworkTrafficData {
+            bytes_received: 0,
+            bytes_sent: 0,
+            packets_received: 0,
+            packets_sent: 0,
+        },
+        system_load: 1.0,
+        open_fds: Some(100),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_composite_expressions() {
+        assert!(AlertExpression::parse("cpu > 90 AND load > cores*2").is_ok());
+        assert!(AlertExpression::parse("memory > 85 OR swap > 50").is_ok());
+        assert!(AlertExpression::parse("NOT (cpu < 10) && avg_over(memory, 5m) >= 80").is_ok());
+        assert!(AlertExpression::parse("rate(network, 30s) > 1000000 || max_over(disk, 1h) > 95").is_ok());
+
+        // 前端使用的长指标名同样可用
+        assert!(AlertExpression::parse("cpu_usage > 90").is_ok());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        // 未知指标
+        let err = AlertExpression::parse("gpu > 90").unwrap_err();
+        assert!(err.message.contains("unknown metric `gpu`"));
+        assert_eq!(err.position, Some(0));
+
+        // 结果不是布尔值
+        let err = AlertExpression::parse("cpu * 2").unwrap_err();
+        assert!(err.message.contains("must be a condition"));
+
+        // 逻辑运算符两侧必须是条件
+        let err = AlertExpression::parse("cpu AND memory > 5").unwrap_err();
+        assert!(err.message.contains("AND needs conditions"));
+
+        // 函数参数校验
+        let err = AlertExpression::parse("avg_over(swap, 5m) > 1").unwrap_err();
+        assert!(err.message.contains("no stored history"));
+        let err = AlertExpression::parse("median(cpu, 5m) > 1").unwrap_err();
+        assert!(err.message.contains("unknown function"));
+        let err = AlertExpression::parse("avg_over(cpu, 5x) > 1").unwrap_err();
+        assert!(err.message.contains("unknown duration unit"));
+        let err = AlertExpression::parse("avg_over(cpu, 1000000000d) > 5").unwrap_err();
+        assert!(err.message.contains("must not exceed 31 days"));
+        assert!(AlertExpression::parse("avg_over(cpu, 31d) > 5").is_ok());
+
+        // 语法错误
+        assert!(AlertExpression::parse("cpu > ").is_err());
+        assert!(AlertExpression::parse("(cpu > 90").is_err());
+        assert!(AlertExpression::parse("10 < cpu < 90").is_err());
+        assert!(AlertExpression::parse("cpu > 90 $").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_against_snapshot() {
+        let snapshot = snapshot();
+        let ctx = EvaluationContext {
+            snapshot: &snapshot,
+            history: &[],
+            now: Utc::now(),
+        };
+
+        let expr = AlertExpression::parse("cpu > 90 AND load > cores*2").unwrap();
+        let outcome = expr.evaluate(&ctx).unwrap();
+        assert!(outcome.fired);
+        assert_eq!(outcome.value, 95.0);
+
+        let expr = AlertExpression::parse("memory > 85 OR swap > 50").unwrap();
+        assert!(!expr.evaluate(&ctx).unwrap().fired);
+
+        // 记录的值取第一个成立的比较
+        let expr = AlertExpression::parse("memory > 85 OR load > 10").unwrap();
+        assert_eq!(expr.evaluate(&ctx).unwrap().value, 20.0);
+
+        // 除以零时不触发
+        let expr = AlertExpression::parse("cpu / (cores - 8) > 1").unwrap();
+        assert!(expr.evaluate(&ctx).is_none());
+
+        // 未采集文件描述符数量时无法求值
+        let unsupported = MetricSnapshot {
+            open_fds: None,
+            ..snapshot.clone()
+        };
+        let ctx = EvaluationContext {
+            snapshot: &unsupported,
+            history: &[],
+            now: Utc::now(),
+        };
+        assert!(AlertExpression::parse("fds > 1000").unwrap().evaluate(&ctx).is_none());
+    }
+
+    #[test]
+    fn test_evaluate_window_functions() {
+        let snapshot = snapshot();
+        let history = vec![
+            history_record(30, 10.0),
+            history_record(4, 80.0),
+            history_record(2, 90.0),
+        ];
+        let ctx = EvaluationContext {
+            snapshot: &snapshot,
+            history: &history,
+            now: Utc::now(),
+        };
+
+        let expr = AlertExpression::parse("avg_over(cpu, 5m) > 84").unwrap();
+        assert_eq!(expr.history_window_secs(), 300.0);
+        let outcome = expr.evaluate(&ctx).unwrap();
+        assert!(outcome.fired);
+        assert_eq!(outcome.value, 85.0);
+
+        let expr = AlertExpression::parse("max_over(cpu, 1h) >= 90").unwrap();
+        assert!(expr.evaluate(&ctx).unwrap().fired);
+
+        // 2 分钟内从 80 升到 90，速率为每秒 1/12
+        let expr = AlertExpression::parse("rate(cpu, 5m) > 0.08").unwrap();
+        assert!(expr.evaluate(&ctx).unwrap().fired);
+
+        // 窗口内没有样本时无法求值
+        let expr = AlertExpression::parse("avg_over(cpu, 1m) > 0").unwrap();
+        assert!(expr.evaluate(&ctx).is_none());
+    }
+}
@@ -0,0 +1,131 @@
+//! 资源耗尽预测测试
+//! 覆盖趋势拟合、剩余时间与置信区间，以及预测规则的校验
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    validate_configuration, AlertConfiguration, AlertMetric, ForecastCondition, ResourceForecast, TrendFit,
+    MAX_FORECAST_LOOKBACK_HOURS,
+};
+
+// 每小时一个样本，从 `start` 开始按 `per_hour` 增长，`noise` 交替叠加在样本上
+fn hourly_series(now: DateTime<Utc>, hours: i64, start: f64, per_hour: f64, noise: f64) -> Vec<(DateTime<Utc>, f64)> {
+    (0..=hours)
+        .map(|i| {
+            let jitter = if i % 2 == 0 { noise } else { -noise };
+            (now - Duration::hours(hours - i), start + per_hour * i as f64 + jitter)
+        })
+        .collect()
+}
+
+fn forecast_rule(metric: &str, within_hours: f64) -> AlertConfiguration {
+    let json = format!(
+        r#"{{
+            "id": "disk-full",
+            "metric": "{}",
+            "condition": "greater_than",
+            "threshold": 0,
+            "severity": "critical",
+            "enabled": true,
+            "notification_methods": ["visual"],
+            "target": "/home",
+            "forecast": {{ "within_hours": {} }}
+        }}"#,
+        metric, within_hours
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_to_full_from_linear_trend() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        // 24 小时内从 52% 涨到 76%，每小时 1%
+        let samples = hourly_series(now, 24, 52.0, 1.0, 0.0);
+
+        let trend = TrendFit::fit(&samples, now).unwrap();
+        assert!((trend.slope_per_hour - 1.0).abs() < 1e-9);
+        assert!((trend.fitted_now - 76.0).abs() < 1e-9);
+        assert!((trend.r_squared - 1.0).abs() < 1e-9);
+
+        let forecast = ResourceForecast::compute(AlertMetric::Disk, Some("/home".to_string()), &samples, 100.0, 24, now).unwrap();
+        assert_eq!(forecast.subject(), "disk /home");
+        assert_eq!(forecast.current, 76.0);
+        assert!((forecast.hours_to_threshold.unwrap() - 24.0).abs() < 1e-9);
+        assert_eq!(forecast.estimated_at, Some(now + Duration::hours(24)));
+
+        // 完全线性时置信区间收缩为一点
+        assert!((forecast.earliest_hours.unwrap() - 24.0).abs() < 1e-6);
+        assert!((forecast.latest_hours.unwrap() - 24.0).abs() < 1e-6);
+
+        let condition = ForecastCondition {
+            lookback_hours: 24,
+            threshold: 100.0,
+            within_hours: 48.0,
+        };
+        assert!(forecast.fires(&condition));
+        assert!(!forecast.fires(&ForecastCondition { within_hours: 12.0, ..condition }));
+    }
+
+    #[test]
+    fn test_confidence_bound_and_flat_trends() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+
+        // 有波动时区间包含中心估计
+        let noisy = hourly_series(now, 24, 52.0, 1.0, 2.0);
+        let forecast = ResourceForecast::compute(AlertMetric::Memory, None, &noisy, 100.0, 24, now).unwrap();
+        let hours = forecast.hours_to_threshold.unwrap();
+        assert!(forecast.earliest_hours.unwrap() < hours);
+        assert!(forecast.latest_hours.unwrap() > hours);
+        assert!(forecast.trend.r_squared < 1.0);
+
+        // 使用率下降时不会耗尽
+        let falling = hourly_series(now, 24, 80.0, -1.0, 0.0);
+        let forecast = ResourceForecast::compute(AlertMetric::Memory, None, &falling, 100.0, 24, now).unwrap();
+        assert!(forecast.hours_to_threshold.is_none());
+        assert!(forecast.estimated_at.is_none());
+
+        // 已超过阈值时剩余时间为零
+        let full = hourly_series(now, 24, 90.0, 0.5, 0.0);
+        let forecast = ResourceForecast::compute(AlertMetric::Disk, None, &full, 95.0, 24, now).unwrap();
+        assert_eq!(forecast.hours_to_threshold, Some(0.0));
+
+        // 样本不足时无法拟合
+        assert!(TrendFit::fit(&full[..2], now).is_none());
+
+        // 几乎水平的趋势：剩余时间极长，无法换算为时刻
+        let nearly_flat = hourly_series(now, 24, 0.0, 1e-12, 0.0);
+        let forecast = ResourceForecast::compute(AlertMetric::Disk, None, &nearly_flat, 100.0, 24, now).unwrap();
+        assert!(forecast.hours_to_threshold.unwrap() > 1e12);
+        assert!(forecast.estimated_at.is_none());
+        assert!(!forecast.fires(&ForecastCondition { lookback_hours: 24, threshold: 100.0, within_hours: 48.0 }));
+        let slow = hourly_series(now, 24, 0.0, 1e-9, 0.0);
+        let forecast = ResourceForecast::compute(AlertMetric::Disk, None, &slow, 100.0, 24, now).unwrap();
+        assert!(forecast.estimated_at.is_none());
+    }
+
+    #[test]
+    fn test_forecast_rule_validation() {
+        assert!(validate_configuration(&forecast_rule("disk_usage", 24.0)).is_ok());
+        assert!(validate_configuration(&forecast_rule("memory_usage", 24.0)).is_ok());
+
+        let err = validate_configuration(&forecast_rule("cpu_usage", 24.0)).unwrap_err();
+        assert!(err.contains("only available for disk_usage and memory_usage"));
+        assert!(validate_configuration(&forecast_rule("disk_usage", 0.0)).is_err());
+        assert!(validate_configuration(&forecast_rule("disk_usage", 1e300)).is_err());
+
+        // 回看时长有上限，避免计算起始时间时溢出
+        let mut config = forecast_rule("disk_usage", 24.0);
+        config.forecast.as_mut().unwrap().lookback_hours = MAX_FORECAST_LOOKBACK_HOURS;
+        assert!(validate_configuration(&config).is_ok());
+        config.forecast.as_mut().unwrap().lookback_hours = u32::MAX;
+        assert!(validate_configuration(&config).unwrap_err().contains("must not exceed"));
+
+        // 不能同时使用多种规则类型
+        let mut config = forecast_rule("disk_usage", 24.0);
+        config.expression = Some("disk > 90".to_string());
+        assert!(validate_configuration(&config).is_err());
+    }
+}
@@ -0,0 +1,112 @@
+//! 警报历史查询与统计测试
+//! 覆盖过滤条件的解析与校验，以及触发次数、MTTA、MTTR 的统计
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{AlertHistory, AlertHistoryFilter, AlertSeverity, AlertStatistics};
+
+fn alert(
+    rule_id: &str,
+    triggered_at: DateTime<Utc>,
+    acknowledged_after_secs: Option<i64>,
+    resolved_after_secs: Option<i64>,
+) -> AlertHistory {
+    AlertHistory {
+        id: format!("{}-{}", rule_id, triggered_at.timestamp()),
+        alert_id: rule_id.to_string(),
+        triggered_at,
+        value: 95.0,
+        message: "high alert triggered".to_string(),
+        acknowledged: acknowledged_after_secs.is_some(),
+        acknowledged_at: acknowledged_after_secs.map(|secs| triggered_at + Duration::seconds(secs)),
+        acknowledged_by: acknowledged_after_secs.map(|_| "user".to_string()),
+        suppressed: false,
+        silence_id: None,
+        severity: Some(AlertSeverity::High),
+        escalation_level: 0,
+        resolved_at: resolved_after_secs.map(|secs| triggered_at + Duration::seconds(secs)),
+        incident_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_parsing_and_validation() {
+        let json = r#"{
+            "rule_ids": ["cpu-high"],
+            "severities": ["critical", "warning"],
+            "start_time": "2024-05-01T00:00:00Z",
+            "acknowledged": false
+        }"#;
+        let filter: AlertHistoryFilter = serde_json::from_str(json).unwrap();
+        assert_eq!(filter.severities, vec![AlertSeverity::Critical, AlertSeverity::Medium]);
+        assert_eq!(filter.acknowledged, Some(false));
+        assert!(filter.targets.is_empty());
+        assert!(filter.validate().is_ok());
+
+        let reversed = AlertHistoryFilter {
+            start_time: Some(Utc.with_ymd_and_hms(2024, 5, 2, 0, 0, 0).unwrap()),
+            end_time: Some(Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap()),
+            ..Default::default()
+        };
+        assert!(reversed.validate().is_err());
+
+        let empty_rule = AlertHistoryFilter {
+            rule_ids: vec![" ".to_string()],
+            ..Default::default()
+        };
+        assert!(empty_rule.validate().is_err());
+
+        // 空的过滤条件匹配所有记录，批量确认时需要显式确认
+        assert!(!filter.is_empty());
+        assert!(!reversed.is_empty());
+        let parsed: AlertHistoryFilter = serde_json::from_str("{}").unwrap();
+        assert!(parsed.is_empty());
+    }
+
+    #[test]
+    fn test_statistics_per_rule() {
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        let history = vec![
+            alert("cpu-high", start, Some(60), Some(600)),
+            alert("cpu-high", start + Duration::hours(1), Some(180), None),
+            alert("cpu-high", start + Duration::hours(2), None, Some(300)),
+            alert("disk-full", start, None, None),
+        ];
+
+        let stats = AlertStatistics::from_history(&history, 1);
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.acknowledged, 2);
+        assert_eq!(stats.unacknowledged, 2);
+        assert_eq!(stats.resolved, 2);
+        assert_eq!(stats.mean_time_to_acknowledge_secs, Some(120.0));
+        assert_eq!(stats.mean_time_to_resolve_secs, Some(450.0));
+
+        // 按触发次数排序，最吵的规则只保留一条
+        assert_eq!(stats.rules.len(), 2);
+        assert_eq!(stats.noisiest_rules.len(), 1);
+        let cpu = &stats.noisiest_rules[0];
+        assert_eq!(cpu.rule_id, "cpu-high");
+        assert_eq!(cpu.firing_count, 3);
+        assert_eq!(cpu.acknowledged_count, 2);
+        assert_eq!(cpu.last_triggered_at, Some(start + Duration::hours(2)));
+
+        let disk = &stats.rules[1];
+        assert_eq!(disk.rule_id, "disk-full");
+        assert_eq!(disk.mean_time_to_acknowledge_secs, None);
+        assert_eq!(disk.mean_time_to_resolve_secs, None);
+    }
+
+    #[test]
+    fn test_statistics_ignore_inconsistent_timestamps() {
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+        // 确认时间早于触发时间的旧记录不计入平均值
+        let history = vec![alert("cpu-high", start, Some(-30), None), alert("cpu-high", start, Some(90), None)];
+
+        let stats = AlertStatistics::from_history(&history, 5);
+        assert_eq!(stats.mean_time_to_acknowledge_secs, Some(90.0));
+        assert_eq!(AlertStatistics::from_history(&[], 5), AlertStatistics::default());
+    }
+}
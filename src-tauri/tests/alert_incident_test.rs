@@ -0,0 +1,176 @@
+//! 警报事件测试
+//! 覆盖按时间窗口和目标的分组、事件汇总、分组方式的校验，以及停用和删除规则时恢复警报并解决事件
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    AlertConfiguration, AlertHistory, AlertHistoryFilter, AlertSeverity, DatabaseManager, Incident, IncidentGrouping,
+    IncidentStatus, MessageEnvironment, MessageLocale, MetricSnapshot,
+};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+}
+
+fn alert(rule_id: &str, seconds: i64, severity: AlertSeverity) -> AlertHistory {
+    AlertHistory {
+        id: format!("{}-{}", rule_id, seconds),
+        alert_id: rule_id.to_string(),
+        triggered_at: start() + Duration::seconds(seconds),
+        value: 95.0,
+        message: format!("{} fired", rule_id),
+        acknowledged: false,
+        acknowledged_at: None,
+        acknowledged_by: None,
+        suppressed: false,
+        silence_id: None,
+        severity: Some(severity),
+        escalation_level: 0,
+        resolved_at: None,
+        incident_id: None,
+    }
+}
+
+fn cpu_rule(threshold: f64) -> AlertConfiguration {
+    serde_json::from_value(serde_json::json!({
+        "id": "",
+        "metric": "cpu_usage",
+        "condition": "greater_than",
+        "threshold": threshold,
+        "severity": "high",
+        "enabled": true,
+        "notification_methods": ["visual"]
+    }))
+    .unwrap()
+}
+
+fn snapshot(cpu: f64) -> MetricSnapshot {
+    MetricSnapshot {
+        cpu,
+        memory: 50.0,
+        disk: 50.0,
+        network: 0.0,
+        load: 0.5,
+        swap: 0.0,
+        cores: 4.0,
+        open_fds: None,
+        open_fds_process: None,
+    }
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_alerts_within_window() {
+        let grouping = IncidentGrouping::default();
+        let incidents = vec![Incident::open("cpu_usage".to_string(), &alert("cpu-high", 0, AlertSeverity::High), None)];
+
+        // 窗口内触发的其他规则归入同一事件
+        let selected = grouping.select(&incidents, None, start() + Duration::seconds(120));
+        assert_eq!(selected.map(|incident| incident.id.as_str()), Some(incidents[0].id.as_str()));
+        // 超出窗口后开启新事件
+        assert!(grouping.select(&incidents, None, start() + Duration::seconds(301)).is_none());
+
+        // 窗口从事件打开时起算，持续触发的警报不会延长窗口
+        let mut busy = incidents.clone();
+        busy[0].add_alert(&alert("load-high", 290, AlertSeverity::High), None);
+        assert!(grouping.select(&busy, None, start() + Duration::seconds(310)).is_none());
+
+        // 已解决的事件不再接收新警报
+        let mut resolved = incidents.clone();
+        resolved[0].status = IncidentStatus::Resolved;
+        assert!(grouping.select(&resolved, None, start() + Duration::seconds(60)).is_none());
+    }
+
+    #[test]
+    fn test_group_alerts_sharing_target() {
+        let grouping = IncidentGrouping::default();
+        let disk = Incident::open("disk_usage (/home)".to_string(), &alert("disk-home", 0, AlertSeverity::Medium), Some("/home"));
+        let cpu = Incident::open("cpu_usage".to_string(), &alert("cpu-high", 3500, AlertSeverity::High), None);
+        let incidents = vec![disk, cpu];
+        let now = start() + Duration::seconds(3600);
+
+        // 目标相同时不受时间窗口限制，并优先于窗口内更近的事件
+        let selected = grouping.select(&incidents, Some("/home"), now).unwrap();
+        assert_eq!(selected.title, "disk_usage (/home)");
+        let selected = grouping.select(&incidents, Some("/var"), now).unwrap();
+        assert_eq!(selected.title, "cpu_usage");
+
+        let by_time_only = IncidentGrouping {
+            group_by_target: false,
+            ..Default::default()
+        };
+        assert_eq!(by_time_only.select(&incidents, Some("/home"), now).unwrap().title, "cpu_usage");
+    }
+
+    #[test]
+    fn test_incident_summary() {
+        let mut incident = Incident::open("cpu_usage".to_string(), &alert("cpu-high", 0, AlertSeverity::Medium), None);
+        assert_eq!(incident.status, IncidentStatus::Open);
+
+        // 同一规则重复触发只更新计数，新规则加入时提升严重程度
+        assert!(!incident.add_alert(&alert("cpu-high", 30, AlertSeverity::Medium), None));
+        assert!(incident.add_alert(&alert("load-high", 60, AlertSeverity::Critical), Some("load")));
+        assert_eq!(incident.alert_count, 3);
+        assert_eq!(incident.rule_ids, vec!["cpu-high", "load-high"]);
+        assert_eq!(incident.targets, vec!["load"]);
+        assert_eq!(incident.severity, AlertSeverity::Critical);
+        assert_eq!(incident.last_alert_at, start() + Duration::seconds(60));
+
+        assert!(IncidentGrouping::default().validate().is_ok());
+        let invalid = IncidentGrouping {
+            window_secs: 0,
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let grouping: IncidentGrouping = serde_json::from_str(r#"{ "window_secs": 600 }"#).unwrap();
+        assert!(grouping.group_by_target);
+    }
+
+    #[tokio::test]
+    async fn test_disable_and_delete_rule_resolve_alerts() {
+        let manager = DatabaseManager::with_pool(memory_pool().await).await.unwrap();
+        let environment = MessageEnvironment {
+            hostname: "build-01".to_string(),
+            locale: MessageLocale::En,
+            top_processes: Vec::new(),
+        };
+        let grouping = IncidentGrouping::default();
+        let mut rule = cpu_rule(80.0);
+        let disabled_id = manager.add_alert_configuration(&rule).await.unwrap();
+        let deleted_id = manager.add_alert_configuration(&cpu_rule(70.0)).await.unwrap();
+
+        let configurations = manager.get_alert_configurations().await.unwrap();
+        let fired = manager.check_alerts(configurations, &snapshot(90.0), &environment, &grouping).await.unwrap();
+        assert_eq!(fired.len(), 2);
+        let incident_id = fired[0].incident_id.clone().unwrap();
+        assert!(fired.iter().all(|alert| alert.incident_id.as_deref() == Some(incident_id.as_str())));
+
+        // 停用一条规则后，事件仍有另一条规则的警报未恢复
+        rule.enabled = false;
+        manager.update_alert_configuration(&disabled_id, &rule).await.unwrap();
+        let history = manager.get_alert_history(&AlertHistoryFilter::default(), None, 0).await.unwrap();
+        let remaining: Vec<&AlertHistory> = history.iter().filter(|alert| alert.resolved_at.is_none()).collect();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].alert_id, deleted_id);
+        let incident = manager.get_incident(&incident_id).await.unwrap().unwrap().incident;
+        assert_eq!(incident.status, IncidentStatus::Open);
+
+        // 删除最后一条规则后警报全部恢复，事件随之解决
+        manager.delete_alert_configuration(&deleted_id).await.unwrap();
+        let history = manager.get_alert_history(&AlertHistoryFilter::default(), None, 0).await.unwrap();
+        assert!(history.iter().all(|alert| alert.resolved_at.is_some()));
+        let incident = manager.get_incident(&incident_id).await.unwrap().unwrap().incident;
+        assert_eq!(incident.status, IncidentStatus::Resolved);
+    }
+}
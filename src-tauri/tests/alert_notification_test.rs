@@ -0,0 +1,116 @@
+//! 通知限流与摘要测试
+//! 覆盖全局和按通知方式的频率限制、摘要模式，以及摘要中的计数和最严重值
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    AlertSeverity, DigestSettings, MessageLocale, NotificationDigest, NotificationDisposition, NotificationLimiter,
+    NotificationPolicy, PendingNotification, RateLimit,
+};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+}
+
+fn pending(rule_id: &str, seconds: i64, value: f64, disposition: NotificationDisposition) -> PendingNotification {
+    PendingNotification {
+        alert_history_id: format!("{}-{}", rule_id, seconds),
+        rule_id: rule_id.to_string(),
+        channel: "email".to_string(),
+        disposition,
+        value,
+        severity: Some(AlertSeverity::High),
+        created_at: start() + Duration::seconds(seconds),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limits() {
+        let policy: NotificationPolicy = serde_json::from_str(
+            r#"{
+                "global_limit": { "max_notifications": 3, "period_secs": 3600 },
+                "channel_limits": { "sound": { "max_notifications": 1, "period_secs": 600 } }
+            }"#,
+        )
+        .unwrap();
+        assert!(policy.validate().is_ok());
+
+        // 之前已发送的一条 sound 通知仍在限流周期内
+        let mut limiter = NotificationLimiter::new(&policy, vec![(start() - Duration::minutes(5), "sound".to_string())]);
+        assert_eq!(limiter.decide("sound", start()), NotificationDisposition::Overflow);
+        assert_eq!(limiter.decide("visual", start()), NotificationDisposition::Sent);
+        assert_eq!(limiter.decide("email", start()), NotificationDisposition::Sent);
+        // 全局一小时内已发送三条
+        assert_eq!(limiter.decide("visual", start()), NotificationDisposition::Overflow);
+
+        // 溢出的通知在最长的限流周期后汇总发送
+        assert_eq!(policy.flush_interval("sound"), Duration::hours(1));
+        assert_eq!(policy.lookback(), Duration::hours(1));
+
+        let invalid = NotificationPolicy {
+            global_limit: Some(RateLimit { max_notifications: 0, period_secs: 60 }),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+    }
+
+    #[test]
+    fn test_digest_mode() {
+        let policy = NotificationPolicy {
+            digest: Some(DigestSettings {
+                interval_secs: 600,
+                channels: vec!["email".to_string()],
+            }),
+            ..Default::default()
+        };
+        let mut limiter = NotificationLimiter::new(&policy, Vec::new());
+        assert_eq!(limiter.decide("email", start()), NotificationDisposition::Batched);
+        assert_eq!(limiter.decide("visual", start()), NotificationDisposition::Sent);
+        assert_eq!(policy.flush_interval("email"), Duration::minutes(10));
+        // 没有限制的通知方式不会产生溢出，无需等待
+        assert_eq!(policy.flush_interval("visual"), Duration::zero());
+
+        let all_channels: NotificationPolicy = serde_json::from_str(r#"{ "digest": {} }"#).unwrap();
+        assert!(all_channels.digests_channel("sound"));
+        assert_eq!(all_channels.flush_interval("sound"), Duration::minutes(15));
+    }
+
+    #[test]
+    fn test_build_digest() {
+        let entries = vec![
+            pending("cpu-high", 0, 92.0, NotificationDisposition::Batched),
+            pending("cpu-high", 60, 99.5, NotificationDisposition::Batched),
+            pending("disk-free", 90, 8.0, NotificationDisposition::Overflow),
+            pending("cpu-high", 120, 95.0, NotificationDisposition::Batched),
+            pending("disk-free", 150, 3.5, NotificationDisposition::Overflow),
+        ];
+        let lower_is_worse = HashMap::from([("disk-free".to_string(), true)]);
+        let now = start() + Duration::minutes(15);
+        let digest = NotificationDigest::build("email", &entries, &lower_is_worse, MessageLocale::En, now).unwrap();
+
+        assert_eq!(digest.alert_count, 5);
+        assert_eq!(digest.overflow_count, 2);
+        assert_eq!(digest.period_start, start());
+        assert_eq!(digest.period_end, start() + Duration::seconds(150));
+        assert_eq!(digest.alert_ids.len(), 5);
+
+        assert_eq!(digest.rules[0].rule_id, "cpu-high");
+        assert_eq!(digest.rules[0].count, 3);
+        assert_eq!(digest.rules[0].worst_value, 99.5);
+        assert_eq!(digest.rules[0].worst_alert_id, "cpu-high-60");
+        // 低于阈值触发的规则取最低值
+        assert_eq!(digest.rules[1].worst_value, 3.5);
+        assert_eq!(
+            digest.message,
+            "5 alert(s) from 2 rule(s) between 12:00 and 12:02 UTC, 2 held back by rate limits: \
+             cpu-high ×3 (worst 99.50), disk-free ×2 (worst 3.50)"
+        );
+
+        assert!(NotificationDigest::build("email", &[], &lower_is_worse, MessageLocale::En, now).is_none());
+    }
+}
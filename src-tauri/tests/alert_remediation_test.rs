@@ -0,0 +1,154 @@
+//! 警报自动修复测试
+//! 覆盖修复策略的解析与校验、执行频率限制，以及脚本动作的环境变量、输出和超时
+
+use chrono::{Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    execute_action, script_environment, AlertConfiguration, AlertHistory, AlertMessageContext, MessageLocale,
+    RemediationAction, RemediationPolicy, RemediationStatus,
+};
+
+fn rule(remediation: &str) -> AlertConfiguration {
+    let json = format!(
+        r#"{{
+            "id": "cache-full",
+            "metric": "disk_usage",
+            "condition": "greater_than",
+            "threshold": 90,
+            "severity": "high",
+            "enabled": true,
+            "notification_methods": ["visual"],
+            "target": "/var/cache",
+            "remediation": {}
+        }}"#,
+        remediation
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+fn alert() -> AlertHistory {
+    AlertHistory {
+        id: "history-1".to_string(),
+        alert_id: "cache-full".to_string(),
+        triggered_at: Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap(),
+        value: 93.5,
+        message: "[host] high alert: disk_usage (/var/cache) > 90 (current: 93.50)".to_string(),
+        acknowledged: false,
+        acknowledged_at: None,
+        acknowledged_by: None,
+        suppressed: false,
+        silence_id: None,
+        severity: None,
+        escalation_level: 0,
+        resolved_at: None,
+        incident_id: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_policy_parsing_and_validation() {
+        let config = rule(
+            r#"{
+                "actions": [
+                    { "type": "terminate_process", "process_name": "runaway" },
+                    { "type": "run_script", "path": "/usr/local/bin/clear-cache.sh" },
+                    { "type": "webhook", "url": "https://ops.example.com/hooks/alert", "timeout_secs": 5 }
+                ]
+            }"#,
+        );
+        let policy = config.remediation.as_ref().unwrap();
+        assert!(!policy.dry_run);
+        assert_eq!(policy.max_runs_per_hour, 3);
+        assert_eq!(policy.cooldown_secs, 300);
+        assert_eq!(
+            policy.actions[1],
+            RemediationAction::RunScript {
+                path: "/usr/local/bin/clear-cache.sh".to_string(),
+                args: Vec::new(),
+                timeout_secs: 30,
+            }
+        );
+        assert!(tauri_react_ai_lib::validate_configuration(&config).is_ok());
+
+        for (invalid, message) in [
+            (r#"{ "actions": [] }"#, "at least one action"),
+            (r#"{ "actions": [{ "type": "run_script", "path": "clear.sh" }] }"#, "must be absolute"),
+            (r#"{ "actions": [{ "type": "webhook", "url": "ftp://host" }] }"#, "http"),
+            (r#"{ "actions": [{ "type": "terminate_process", "process_name": " " }], "max_runs_per_hour": 1 }"#, "empty"),
+        ] {
+            let error = tauri_react_ai_lib::validate_configuration(&rule(invalid)).unwrap_err();
+            assert!(error.contains(message), "{}", error);
+        }
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let policy = RemediationPolicy {
+            actions: Vec::new(),
+            dry_run: false,
+            max_runs_per_hour: 2,
+            cooldown_secs: 600,
+        };
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+
+        assert!(policy.check_rate_limit(&[], now).is_ok());
+        // 冷却时间内不再执行
+        let cooling = policy.check_rate_limit(&[now - Duration::minutes(5)], now).unwrap_err();
+        assert!(cooling.contains("cooldown"));
+        assert!(policy.check_rate_limit(&[now - Duration::minutes(15)], now).is_ok());
+        // 一小时内的次数达到上限，超过一小时的记录不计入
+        let runs = [now - Duration::minutes(50), now - Duration::minutes(20)];
+        assert!(policy.check_rate_limit(&runs, now).unwrap_err().contains("limit 2"));
+        let runs = [now - Duration::minutes(70), now - Duration::minutes(20)];
+        assert!(policy.check_rate_limit(&runs, now).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_run_script_with_alert_context() {
+        let alert = alert();
+        let mut context = AlertMessageContext::sample("build-01", MessageLocale::En);
+        context.rule_id = "cache-full".to_string();
+        context.target = Some("/var/cache".to_string());
+
+        let variables = script_environment(&alert, &context);
+        assert!(variables.contains(&("ALERT_ID".to_string(), "history-1".to_string())));
+        assert!(variables.contains(&("ALERT_TARGET".to_string(), "/var/cache".to_string())));
+        assert!(variables.contains(&("ALERT_HOSTNAME".to_string(), "build-01".to_string())));
+
+        if !cfg!(unix) {
+            return;
+        }
+        let script = |command: &str, timeout_secs| RemediationAction::RunScript {
+            path: "/bin/sh".to_string(),
+            args: vec!["-c".to_string(), command.to_string()],
+            timeout_secs,
+        };
+
+        let action = script("echo \"$ALERT_RULE_ID on $ALERT_TARGET\"", 5);
+        let (status, output) = execute_action(&action, &alert, &context, false).await;
+        assert_eq!(status, RemediationStatus::Succeeded);
+        assert!(output.contains("cache-full on /var/cache"), "{}", output);
+
+        let (status, output) = execute_action(&script("echo oops >&2; exit 3", 5), &alert, &context, false).await;
+        assert_eq!(status, RemediationStatus::Failed);
+        assert!(output.contains("stderr: oops"), "{}", output);
+
+        // 大量输出只保留前一部分，脚本不会因管道写满而阻塞
+        let (status, output) = execute_action(&script("head -c 1000000 /dev/zero | tr '\\0' x", 5), &alert, &context, false).await;
+        assert_eq!(status, RemediationStatus::Succeeded);
+        assert!(output.len() < 5000, "{}", output.len());
+        assert!(output.ends_with("(truncated)"));
+
+        let (status, output) = execute_action(&script("sleep 5", 1), &alert, &context, false).await;
+        assert_eq!(status, RemediationStatus::Failed);
+        assert!(output.contains("timed out"));
+
+        // 试运行不会执行脚本
+        let (status, output) = execute_action(&script("exit 1", 5), &alert, &context, true).await;
+        assert_eq!(status, RemediationStatus::DryRun);
+        assert!(output.starts_with("would run /bin/sh"));
+    }
+}
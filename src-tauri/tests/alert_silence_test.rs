@@ -0,0 +1,91 @@
+//! 警报静默测试
+//! 验证循环维护窗口的时间判断和静默配置校验
+
+use chrono::{DateTime, Duration, FixedOffset, TimeZone, Utc, Weekday};
+use tauri_react_ai_lib::{AlertMetric, AlertSilence, SilenceMatcher, SilenceSchedule};
+
+fn recurring(start: &str, end: &str, weekdays: Vec<Weekday>) -> SilenceSchedule {
+    SilenceSchedule::Recurring {
+        start_time: start.to_string(),
+        end_time: end.to_string(),
+        weekdays,
+    }
+}
+
+fn at(tz: &FixedOffset, y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+    tz.with_ymd_and_hms(y, m, d, h, min, 0).unwrap().with_timezone(&Utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recurring_window_uses_local_time() {
+        let tz = FixedOffset::east_opt(8 * 3600).unwrap();
+        let nightly = recurring("02:00", "03:00", vec![]);
+
+        assert!(nightly.is_active_in(at(&tz, 2025, 9, 10, 2, 30), &tz));
+        assert!(!nightly.is_active_in(at(&tz, 2025, 9, 10, 3, 0), &tz));
+        assert!(!nightly.is_active_in(at(&tz, 2025, 9, 10, 1, 59), &tz));
+    }
+
+    #[test]
+    fn test_recurring_window_across_midnight() {
+        let tz = FixedOffset::east_opt(0).unwrap();
+        // 2025-09-12 是星期五
+        let friday_night = recurring("23:00", "01:00", vec![Weekday::Fri]);
+
+        assert!(friday_night.is_active_in(at(&tz, 2025, 9, 12, 23, 30), &tz));
+        assert!(friday_night.is_active_in(at(&tz, 2025, 9, 13, 0, 30), &tz));
+        assert!(!friday_night.is_active_in(at(&tz, 2025, 9, 13, 23, 30), &tz));
+        assert!(!friday_night.is_active_in(at(&tz, 2025, 9, 12, 0, 30), &tz));
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        assert!(recurring("02:00", "03:00", vec![]).validate().is_ok());
+        assert!(recurring("2am", "03:00", vec![]).validate().is_err());
+        assert!(recurring("02:00", "02:00", vec![]).validate().is_err());
+
+        let now = Utc::now();
+        let fixed = SilenceSchedule::Fixed {
+            starts_at: now,
+            ends_at: now - Duration::hours(1),
+        };
+        assert!(fixed.validate().is_err());
+
+        let fixed = SilenceSchedule::Fixed {
+            starts_at: now - Duration::hours(1),
+            ends_at: now + Duration::hours(1),
+        };
+        assert!(fixed.is_active_at(now));
+        assert!(!fixed.is_expired_at(now));
+    }
+
+    #[test]
+    fn test_empty_matcher_requires_all() {
+        let silence = |matcher: SilenceMatcher| AlertSilence {
+            id: String::new(),
+            comment: "maintenance".to_string(),
+            matcher,
+            schedule: recurring("02:00", "03:00", vec![]),
+            enabled: true,
+            created_by: None,
+            created_at: None,
+        };
+
+        // 没有条件的静默会屏蔽所有规则，必须显式确认
+        let error = silence(SilenceMatcher::default()).validate().unwrap_err();
+        assert!(error.contains("set all"), "{}", error);
+        let all: SilenceMatcher = serde_json::from_str(r#"{ "all": true }"#).unwrap();
+        assert!(silence(all).validate().is_ok());
+
+        let cpu = SilenceMatcher {
+            metrics: vec![AlertMetric::Cpu],
+            ..Default::default()
+        };
+        assert!(!cpu.is_empty());
+        assert!(silence(cpu).validate().is_ok());
+    }
+}
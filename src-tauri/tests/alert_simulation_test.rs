@@ -0,0 +1,183 @@
+//! 警报规则模拟测试
+//! 覆盖阈值与表达式规则的回测、触发过程的合并，以及测试触发的消息和通知
+
+use chrono::{DateTime, Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    backtest_rule, test_fire, AlertConfiguration, AlertHistoryFilter, DatabaseManager, DiskUsageData,
+    HistoricalSystemData, MessageEnvironment, MessageLocale, NetworkTrafficData, NotificationPolicy,
+};
+
+fn start() -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap()
+}
+
+fn rule(extra: &str) -> AlertConfiguration {
+    let json = format!(
+        r#"{{
+            "id": "cpu-high",
+            "metric": "cpu_usage",
+            "condition": "greater_than",
+            "threshold": 90,
+            "severity": "high",
+            "enabled": true,
+            "notification_methods": ["visual", "sound"]{}
+        }}"#,
+        extra
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+// 每分钟一条记录，CPU 使用率依次取 `cpu` 中的值
+fn history(cpu: &[f32]) -> Vec<HistoricalSystemData> {
+    cpu.iter()
+        .enumerate()
+        .map(|(minute, cpu_usage)| HistoricalSystemData {
+            id: format!("record-{}", minute),
+            timestamp: start() + Duration::minutes(minute as i64),
+            cpu_usage: *cpu_usage,
+            memory_usage: 50.0,
+            memory_total: 8589934592,
+            disk_usage: vec![DiskUsageData {
+                mount_point: "/".to_string(),
+                used_space: 50,
+                total_space: 100,
+                usage_percent: 50.0,
+            }],
+            network_traffic: NetworkTrafficData {
+                bytes_received: 0,
+                bytes_sent: 0,
+                packets_received: 0,
+                packets_sent: 0,
+            },
+            system_load: 1.0,
+            open_fds: None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backtest_threshold_rule() {
+        let history = history(&[50.0, 95.0, 97.0, 40.0, 60.0, 92.0, 93.0]);
+        let end = start() + Duration::minutes(10);
+        let report = backtest_rule(&rule(""), &history, start(), end, 8.0, MessageLocale::En).unwrap();
+
+        assert_eq!(report.evaluated_samples, 7);
+        assert_eq!(report.firing_samples, 4);
+        assert_eq!(report.episodes.len(), 2);
+
+        let first = &report.episodes[0];
+        assert_eq!(first.fired_at, start() + Duration::minutes(1));
+        assert_eq!(first.resolved_at, Some(start() + Duration::minutes(3)));
+        assert_eq!(first.duration_secs, 120);
+        assert_eq!((first.min_value, first.max_value), (95.0, 97.0));
+        assert_eq!(first.details, "cpu_usage > 90 (current: 95)");
+
+        // 范围结束时仍在触发，持续时间计算到范围结束
+        let second = &report.episodes[1];
+        assert_eq!(second.resolved_at, None);
+        assert_eq!(second.duration_secs, 300);
+        assert_eq!(report.total_firing_secs, 420);
+
+        assert!(backtest_rule(&rule(""), &history, end, start(), 8.0, MessageLocale::En).is_err());
+    }
+
+    #[test]
+    fn test_backtest_expression_rule() {
+        // 窗口平均值需要范围开始前的数据，回测范围从第 3 分钟开始
+        let history = history(&[95.0, 95.0, 95.0, 95.0, 20.0, 20.0, 20.0]);
+        let config = rule(r#", "expression": "avg_over(cpu, 2m) > 90 AND cores >= 4""#);
+        let from = start() + Duration::minutes(3);
+        let report =
+            backtest_rule(&config, &history, from, start() + Duration::minutes(6), 8.0, MessageLocale::En).unwrap();
+
+        assert_eq!(report.evaluated_samples, 4);
+        assert_eq!(report.episodes.len(), 1);
+        assert_eq!(report.episodes[0].fired_at, from);
+        // 第 4 分钟窗口内的平均值降到 70
+        assert_eq!(report.episodes[0].resolved_at, Some(start() + Duration::minutes(4)));
+
+        // 没有历史记录的指标无法回测
+        let swap = rule(r#", "expression": "swap > 50""#);
+        let error = backtest_rule(&swap, &history, from, start() + Duration::minutes(6), 8.0, MessageLocale::En)
+            .unwrap_err();
+        assert!(error.contains("swap_usage"), "{}", error);
+    }
+
+    #[test]
+    fn test_fire_uses_rule_template() {
+        let environment = MessageEnvironment {
+            hostname: "build-01".to_string(),
+            locale: MessageLocale::En,
+            top_processes: Vec::new(),
+        };
+        let fired = test_fire(&rule(""), &environment, start());
+        assert!(fired.alert.id.starts_with("test-"));
+        assert_eq!(fired.alert.alert_id, "cpu-high");
+        assert_eq!(fired.alert.value, 90.0);
+        assert_eq!(fired.notification_methods, vec!["visual", "sound"]);
+        assert_eq!(
+            fired.alert.message,
+            "[build-01] high alert: test notification, rule condition: cpu_usage > 90"
+        );
+
+        let custom = rule(r#", "message_template": "{severity} {rule_id} on {hostname}""#);
+        assert_eq!(test_fire(&custom, &environment, start()).alert.message, "high cpu-high on build-01");
+    }
+
+    #[tokio::test]
+    async fn test_fire_follows_notification_policy() {
+        let manager = DatabaseManager::with_pool(memory_pool().await).await.unwrap();
+        let id = manager.add_alert_configuration(&rule("")).await.unwrap();
+        let config = manager
+            .get_alert_configurations()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|config| config.id == id)
+            .unwrap();
+        let environment = MessageEnvironment {
+            hostname: "build-01".to_string(),
+            locale: MessageLocale::En,
+            top_processes: Vec::new(),
+        };
+        let policy: NotificationPolicy = serde_json::from_str(
+            r#"{ "channel_limits": { "sound": { "max_notifications": 1, "period_secs": 3600 } } }"#,
+        )
+        .unwrap();
+
+        let fired = test_fire(&config, &environment, start());
+        let plan = manager.plan_test_notification(&fired.alert, &config, &policy, start()).await.unwrap();
+        assert_eq!(plan.deliveries.len(), 1);
+        assert!(plan.deliveries[0].test);
+        assert_eq!(plan.deliveries[0].alert.id, fired.alert.id);
+        assert_eq!(plan.deliveries[0].channels, vec!["visual", "sound"]);
+
+        // 已发送的测试通知计入频率限制，超出的不会进入溢出摘要
+        let fired = test_fire(&config, &environment, start());
+        let plan = manager.plan_test_notification(&fired.alert, &config, &policy, start()).await.unwrap();
+        assert_eq!(plan.deliveries[0].channels, vec!["visual"]);
+        assert_eq!(plan.overflowed, 1);
+        let later = manager
+            .plan_notifications(&[], &policy, MessageLocale::En, start() + Duration::hours(2))
+            .await
+            .unwrap();
+        assert!(later.digests.is_empty());
+
+        // 测试警报不写入警报历史
+        let history = manager.get_alert_history(&AlertHistoryFilter::default(), None, 0).await.unwrap();
+        assert!(history.is_empty());
+    }
+}
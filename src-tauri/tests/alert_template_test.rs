@@ -0,0 +1,113 @@
+//! 警报消息模板测试
+//! 覆盖占位符替换、转义与校验，以及不同语言的默认模板
+
+use chrono::{Duration, TimeZone, Utc};
+use tauri_react_ai_lib::{
+    render_template, validate_template, AlertConfiguration, AlertMessageContext, MessageEnvironment, MessageLocale,
+    ProcessSample,
+};
+
+fn disk_rule() -> AlertConfiguration {
+    let json = r#"{
+        "id": "disk-home",
+        "metric": "disk_usage",
+        "condition": "greater_than",
+        "threshold": 90,
+        "severity": "critical",
+        "enabled": true,
+        "notification_methods": ["visual"],
+        "target": "/home"
+    }"#;
+    serde_json::from_str(json).unwrap()
+}
+
+fn environment(locale: MessageLocale) -> MessageEnvironment {
+    MessageEnvironment {
+        hostname: "build-01".to_string(),
+        locale,
+        top_processes: vec![ProcessSample {
+            pid: 812,
+            name: "rsync".to_string(),
+            cpu_usage: 42.3,
+            memory_bytes: 3 * 1024 * 1024,
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_placeholders() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let context = AlertMessageContext::for_rule(
+            &disk_rule(),
+            95.5,
+            "details".to_string(),
+            &environment(MessageLocale::En),
+            Some(now - Duration::seconds(312)),
+            now,
+        );
+
+        let message = render_template(
+            "{{{hostname}}} {metric} on {target} {condition} {threshold}: {value} for {duration} [{ rule_id }] {top_processes}",
+            &context,
+            MessageLocale::En,
+        )
+        .unwrap();
+        assert_eq!(
+            message,
+            "{build-01} disk_usage on /home > 90: 95.50 for 5m 12s [disk-home] rsync (pid 812, 42.3% CPU, 3.0 MB)"
+        );
+
+        // 首次触发没有持续时间；表达式规则没有阈值和比较方式
+        let mut expression_rule = disk_rule();
+        expression_rule.expression = Some("disk > 90 AND load > 4".to_string());
+        let context = AlertMessageContext::for_rule(
+            &expression_rule,
+            1.0,
+            String::new(),
+            &MessageEnvironment::default(),
+            None,
+            now,
+        );
+        let message = render_template("{duration} {threshold} {condition} {top_processes}", &context, MessageLocale::En)
+            .unwrap();
+        assert_eq!(message, "0s - - -");
+    }
+
+    #[test]
+    fn test_validate_template() {
+        assert!(validate_template("{severity}: {details}").is_ok());
+        assert!(validate_template("literal {{braces}}").is_ok());
+
+        let unknown = validate_template("{metric} {cpu}").unwrap_err();
+        assert!(unknown.contains("unknown placeholder `{cpu}`"));
+        assert!(validate_template("{metric").unwrap_err().contains("unclosed"));
+        assert!(validate_template("metric}").unwrap_err().contains("unmatched"));
+        assert!(validate_template("  ").is_err());
+
+        // 规则校验同样拒绝无效模板
+        let mut rule = disk_rule();
+        rule.message_template = Some("{value} {unknown}".to_string());
+        assert!(tauri_react_ai_lib::validate_configuration(&rule).is_err());
+    }
+
+    #[test]
+    fn test_default_templates_per_locale() {
+        for (locale, expected) in [
+            (MessageLocale::En, "[build-01] high alert: cpu_usage > 90 (current: 95.30)"),
+            (MessageLocale::Zh, "[build-01] 高级警报：cpu_usage > 90（当前值：95.30）"),
+        ] {
+            let context = AlertMessageContext::sample("build-01", locale);
+            let message = render_template(locale.default_template(), &context, locale).unwrap();
+            assert_eq!(message, expected);
+            // 阈值只出现一次
+            assert_eq!(message.matches("90").count(), 1);
+        }
+
+        let locale: MessageLocale = serde_json::from_str("\"zh-CN\"").unwrap();
+        assert_eq!(locale, MessageLocale::Zh);
+    }
+}
@@ -0,0 +1,212 @@
+//! 警报规则导入导出测试
+//! 覆盖 JSON/YAML 规则文件的往返转换、Prometheus 告警规则的映射，以及导入的原子性
+
+use tauri_react_ai_lib::{
+    convert_prometheus_rules, export_rule_file, parse_rule_file, AlertComparison, AlertConfiguration, AlertMetric,
+    AlertSeverity, DatabaseManager, ImportConflictMode, RuleFileFormat,
+};
+
+fn rules() -> Vec<AlertConfiguration> {
+    let json = r#"[
+        {
+            "id": "cpu-high",
+            "metric": "cpu_usage",
+            "condition": "greater_than",
+            "threshold": 90,
+            "severity": "high",
+            "enabled": true,
+            "notification_methods": ["visual", "sound"]
+        },
+        {
+            "id": "load-and-cpu",
+            "metric": "cpu_usage",
+            "condition": "greater_than",
+            "threshold": 0,
+            "severity": "critical",
+            "enabled": false,
+            "notification_methods": ["visual"],
+            "expression": "cpu > 90 AND load > cores * 2",
+            "forecast": null
+        }
+    ]"#;
+    serde_json::from_str(json).unwrap()
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+const PROMETHEUS_RULES: &str = r#"
+groups:
+  - name: node
+    rules:
+      - alert: HighCPU
+        expr: 100 - (avg by (instance) (rate(node_cpu_seconds_total{mode="idle"}[5m])) * 100) > 90
+        for: 5m
+        labels:
+          severity: critical
+      - alert: LowMemory
+        expr: node_memory_MemAvailable_bytes / node_memory_MemTotal_bytes * 100 < 10
+        labels:
+          severity: warning
+      - alert: HomeDiskFull
+        expr: (1 - node_filesystem_avail_bytes{mountpoint="/home"} / node_filesystem_size_bytes{mountpoint="/home"}) > 0.95
+      - alert: HighLoad
+        expr: node_load1 > 8
+      - alert: InstanceDown
+        expr: up == 0
+      - alert: CpuAndMemory
+        expr: rate(node_cpu_seconds_total[5m]) > 0.9 and node_memory_MemAvailable_bytes < 1e9
+      - record: instance:cpu:rate5m
+        expr: rate(node_cpu_seconds_total[5m])
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_json_and_yaml() {
+        for format in [RuleFileFormat::Json, RuleFileFormat::Yaml] {
+            let content = export_rule_file(rules(), format).unwrap();
+            assert!(content.contains("cpu-high"));
+
+            // 显式指定格式和自动识别都能解析
+            for parsed in [parse_rule_file(&content, Some(format)).unwrap(), parse_rule_file(&content, None).unwrap()] {
+                assert_eq!(parsed.len(), 2);
+                assert_eq!(parsed[0].id, "cpu-high");
+                assert_eq!(parsed[0].notification_methods, vec!["visual", "sound"]);
+                assert_eq!(parsed[1].expression.as_deref(), Some("cpu > 90 AND load > cores * 2"));
+                assert!(!parsed[1].enabled);
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_bare_lists_and_errors() {
+        let yaml = r#"
+- id: disk-full
+  metric: disk
+  condition: ">"
+  threshold: 95
+  severity: crit
+  enabled: true
+  notification_methods: [visual]
+  target: /home
+"#;
+        let parsed = parse_rule_file(yaml, None).unwrap();
+        assert_eq!(parsed[0].metric, AlertMetric::Disk);
+        assert_eq!(parsed[0].severity, AlertSeverity::Critical);
+        assert_eq!(parsed[0].target.as_deref(), Some("/home"));
+
+        let future = r#"{ "version": 99, "rules": [] }"#;
+        assert!(parse_rule_file(future, None).unwrap_err().contains("newer"));
+
+        let invalid = r#"[{ "id": "x", "metric": "gpu" }]"#;
+        assert!(parse_rule_file(invalid, Some(RuleFileFormat::Json)).is_err());
+    }
+
+    #[test]
+    fn test_prometheus_mapping() {
+        let conversion = convert_prometheus_rules(PROMETHEUS_RULES).unwrap();
+        let rule = |id: &str| conversion.rules.iter().find(|rule| rule.id == id).unwrap();
+
+        let cpu = rule("prometheus-high-cpu");
+        assert_eq!(cpu.metric, AlertMetric::Cpu);
+        assert_eq!(cpu.condition, AlertComparison::GreaterThan);
+        assert_eq!(cpu.threshold, 90.0);
+        assert_eq!(cpu.severity, AlertSeverity::Critical);
+        assert!(conversion
+            .warnings
+            .iter()
+            .any(|(id, warning)| id == "prometheus-high-cpu" && warning.contains("for: 5m")));
+
+        // 剩余内存低于 10% 换算为使用率高于 90%
+        let memory = rule("prometheus-low-memory");
+        assert_eq!(memory.metric, AlertMetric::Memory);
+        assert_eq!(memory.condition, AlertComparison::GreaterThan);
+        assert_eq!(memory.threshold, 90.0);
+        assert_eq!(memory.severity, AlertSeverity::Medium);
+
+        // 比例阈值换算为百分比，挂载点成为目标标签
+        let disk = rule("prometheus-home-disk-full");
+        assert_eq!(disk.metric, AlertMetric::Disk);
+        assert!((disk.threshold - 95.0).abs() < 1e-9);
+        assert_eq!(disk.target.as_deref(), Some("/home"));
+
+        assert_eq!(rule("prometheus-high-load").metric, AlertMetric::Load);
+        assert_eq!(conversion.rules.len(), 4);
+        for rule in &conversion.rules {
+            assert!(tauri_react_ai_lib::validate_configuration(rule).is_ok());
+        }
+
+        let reasons: Vec<(&str, &str)> = conversion
+            .unmapped
+            .iter()
+            .map(|rule| (rule.name.as_str(), rule.reason.as_str()))
+            .collect();
+        assert_eq!(reasons.len(), 3);
+        assert!(reasons.contains(&("InstanceDown", "no supported node_exporter metric found")));
+        assert!(reasons.contains(&("CpuAndMemory", "combining conditions with and/or/unless is not supported")));
+        assert!(reasons.contains(&("instance:cpu:rate5m", "recording rules are not alerts")));
+    }
+
+    #[test]
+    fn test_prometheus_percent_labels_and_duplicate_names() {
+        let content = r#"
+groups:
+  - name: storage
+    rules:
+      - alert: DiskFull
+        expr: (1 - node_filesystem_avail_bytes{mountpoint = "/mnt/Backup 100"} / node_filesystem_size_bytes{mountpoint = "/mnt/Backup 100"}) > 0.9
+      - alert: MemoryUsed
+        expr: 100 * (1 - node_memory_MemAvailable_bytes / node_memory_MemTotal_bytes) > 85
+  - name: storage-replica
+    rules:
+      - alert: DiskFull
+        expr: node_filesystem_avail_bytes{mountpoint="/srv"} / node_filesystem_size_bytes{mountpoint="/srv"} * 100 < 5
+"#;
+        let conversion = convert_prometheus_rules(content).unwrap();
+        let ids: Vec<&str> = conversion.rules.iter().map(|rule| rule.id.as_str()).collect();
+        assert_eq!(ids, vec!["prometheus-disk-full", "prometheus-memory-used", "prometheus-disk-full-2"]);
+
+        // 标签值中的 100 不视为百分比写法，标签值保留原文中的空格
+        let backup = &conversion.rules[0];
+        assert!((backup.threshold - 90.0).abs() < 1e-9);
+        assert_eq!(backup.target.as_deref(), Some("/mnt/Backup 100"));
+
+        let memory = &conversion.rules[1];
+        assert_eq!((memory.metric, memory.condition, memory.threshold), (AlertMetric::Memory, AlertComparison::GreaterThan, 85.0));
+
+        let srv = &conversion.rules[2];
+        assert_eq!((srv.condition, srv.threshold), (AlertComparison::GreaterThan, 95.0));
+        assert_eq!(srv.target.as_deref(), Some("/srv"));
+    }
+
+    #[tokio::test]
+    async fn test_import_is_atomic() {
+        let pool = memory_pool().await;
+        let manager = DatabaseManager::with_pool(pool.clone()).await.unwrap();
+        sqlx::query(
+            "CREATE TRIGGER reject_rule BEFORE INSERT ON alert_configurations WHEN NEW.id = 'load-and-cpu' \
+             BEGIN SELECT RAISE(ABORT, 'rejected'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        // 第二条规则写入失败时，第一条也不导入
+        assert!(manager.import_alert_configurations(rules(), ImportConflictMode::Skip).await.is_err());
+        assert!(manager.get_alert_configurations().await.unwrap().is_empty());
+
+        sqlx::query("DROP TRIGGER reject_rule").execute(&pool).await.unwrap();
+        let report = manager.import_alert_configurations(rules(), ImportConflictMode::Skip).await.unwrap();
+        assert_eq!(report.imported, vec!["cpu-high", "load-and-cpu"]);
+        assert_eq!(manager.get_alert_configurations().await.unwrap().len(), 2);
+    }
+}
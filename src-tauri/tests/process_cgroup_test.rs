@@ -0,0 +1,117 @@
+//! 进程 cgroup 归属测试
+//! 覆盖 v1、v2 与混合模式下的路径选择，各容器运行时的 ID 识别，过滤，以及按单元和容器的合计
+
+use tauri_react_ai_lib::{
+    aggregate_cgroups, container_from_path, container_matches, parse_proc_cgroup, unit_matches, CgroupGrouping, CgroupInfo,
+    ContainerRuntime, ProcessEntry,
+};
+
+const ID: &str = "3f4e8a9b2c1d0e5f6a7b8c9d0e1f2a3b4c5d6e7f8a9b0c1d2e3f4a5b6c7d8e9f";
+
+fn entry(pid: u32, cpu_usage_percent: f32, memory_mb: u64) -> ProcessEntry {
+    ProcessEntry {
+        pid,
+        parent_pid: None,
+        name: format!("proc-{}", pid),
+        command_line: String::new(),
+        user: None,
+        user_id: None,
+        cpu_usage_percent,
+        memory_usage_bytes: memory_mb * 1024 * 1024,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_cgroup() {
+        // cgroup v2 下的 systemd 服务
+        let nginx = parse_proc_cgroup("0::/system.slice/nginx.service\n").unwrap();
+        assert_eq!(nginx.path, "/system.slice/nginx.service");
+        assert_eq!(nginx.systemd_unit.as_deref(), Some("nginx.service"));
+        assert_eq!(nginx.container_id, None);
+
+        // 用户会话中取最深的单元，slice 不算单元
+        let session = parse_proc_cgroup("0::/user.slice/user-1000.slice/user@1000.service/app.slice/app-firefox.scope\n").unwrap();
+        assert_eq!(session.systemd_unit.as_deref(), Some("app-firefox.scope"));
+        assert_eq!(parse_proc_cgroup("0::/user.slice\n").unwrap().systemd_unit, None);
+
+        // v1 混合模式优先使用 name=systemd 层级，容器只出现在部分控制器中
+        let docker_v1 = parse_proc_cgroup(&format!(
+            "12:name=systemd:/system.slice/containerd.service\n4:memory:/docker/{}\n0::/\n",
+            ID
+        ))
+        .unwrap();
+        assert_eq!(docker_v1.path, "/system.slice/containerd.service");
+        assert_eq!(docker_v1.container_id.as_deref(), Some(ID));
+        assert_eq!(docker_v1.container_runtime, Some(ContainerRuntime::Docker));
+
+        assert_eq!(parse_proc_cgroup(""), None);
+    }
+
+    #[test]
+    fn test_container_runtimes_and_filters() {
+        let cases = [
+            (format!("/system.slice/docker-{}.scope", ID), Some(ContainerRuntime::Docker)),
+            (format!("/machine.slice/libpod-{}.scope/container", ID), Some(ContainerRuntime::Podman)),
+            (
+                format!("/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod12.slice/cri-containerd-{}.scope", ID),
+                Some(ContainerRuntime::Containerd),
+            ),
+            (format!("/kubepods.slice/kubepods-pod34.slice/crio-{}.scope", ID), Some(ContainerRuntime::Crio)),
+            (format!("/kubepods/burstable/pod5f1c/{}", ID), Some(ContainerRuntime::Kubernetes)),
+            (format!("/custom/{}", ID), None),
+        ];
+        for (path, runtime) in cases {
+            assert_eq!(container_from_path(&path), Some((ID.to_string(), runtime)), "{}", path);
+        }
+        // 长度不对或含非十六进制字符的名称不是容器 ID
+        assert_eq!(container_from_path("/system.slice/docker-abc123.scope"), None);
+        assert_eq!(container_from_path("/system.slice/docker.service"), None);
+
+        assert!(unit_matches("NGINX", Some("nginx.service")));
+        assert!(!unit_matches("nginx", None));
+        assert!(container_matches(&ID[..12], Some(ID)));
+        assert!(!container_matches(&ID[1..13], Some(ID)));
+        assert!(!container_matches("", Some(ID)));
+    }
+
+    #[test]
+    fn test_aggregate_by_unit_and_container() {
+        let unit = |name: &str, container: Option<&str>| CgroupInfo {
+            path: format!("/system.slice/{}", name),
+            systemd_unit: Some(name.to_string()),
+            container_id: container.map(str::to_string),
+            container_runtime: container.map(|_| ContainerRuntime::Docker),
+        };
+        let container_unit = format!("docker-{}.scope", ID);
+        let processes = vec![
+            (entry(10, 5.0, 100), unit("nginx.service", None)),
+            (entry(11, 2.5, 50), unit("nginx.service", None)),
+            (entry(30, 40.0, 500), unit(&container_unit, Some(ID))),
+            (entry(20, 1.0, 10), unit("sshd.service", None)),
+            (entry(31, 10.0, 20), unit(&container_unit, Some(ID))),
+            // 不属于任何单元的进程不计入
+            (entry(1, 0.5, 8), CgroupInfo::default()),
+        ];
+
+        let by_unit = aggregate_cgroups(&processes, CgroupGrouping::Unit);
+        let keys: Vec<&str> = by_unit.iter().map(|usage| usage.key.as_str()).collect();
+        assert_eq!(keys, vec![container_unit.as_str(), "nginx.service", "sshd.service"]);
+        assert_eq!(by_unit[1].process_count, 2);
+        assert_eq!(by_unit[1].cpu_usage_percent, 7.5);
+        assert_eq!(by_unit[1].memory_usage_bytes, 150 * 1024 * 1024);
+
+        let by_container = aggregate_cgroups(&processes, CgroupGrouping::Container);
+        assert_eq!(by_container.len(), 1);
+        assert_eq!(by_container[0].key, ID);
+        assert_eq!(by_container[0].container_runtime, Some(ContainerRuntime::Docker));
+        assert_eq!(by_container[0].pids, vec![30, 31]);
+        assert_eq!(by_container[0].cpu_usage_percent, 50.0);
+
+        #[cfg(target_os = "linux")]
+        assert!(tauri_react_ai_lib::read_cgroup(std::process::id()).is_some());
+    }
+}
@@ -0,0 +1,147 @@
+//! cgroup v2 层级测试
+//! 覆盖 cpu.stat、io.stat 与单值文件的解析，以及在 tests/fixtures/cgroup 样例层级上的读取、浏览、历史采样和存取
+
+use std::path::PathBuf;
+
+use chrono::{Duration, Utc};
+use tauri_react_ai_lib::{
+    collect_cgroups, normalize_cgroup_path, parse_cpu_stat, parse_io_stat, parse_limit, read_cgroup_node, read_cgroup_stats,
+    CgroupSample, DatabaseManager, HistoricalSystemData, NetworkTrafficData, CGROUP_HISTORY_DEPTH,
+};
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(file!()).parent().unwrap().join("fixtures/cgroup")
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_files() {
+        assert_eq!(parse_limit("536870912\n"), Some(536870912));
+        assert_eq!(parse_limit("max\n"), None);
+
+        let cpu = parse_cpu_stat("usage_usec 400\nuser_usec 300\nsystem_usec 100\nnr_periods 200\nnr_throttled 50\nthrottled_usec 9000\nnr_bursts 0\n");
+        assert_eq!((cpu.usage_usec, cpu.user_usec, cpu.system_usec), (400, 300, 100));
+        assert_eq!((cpu.nr_periods, cpu.nr_throttled, cpu.throttled_usec), (200, 50, 9000));
+        assert_eq!(cpu.throttled_ratio(), 0.25);
+        // 未启用 cpu 控制器时没有限流字段
+        assert_eq!(parse_cpu_stat("usage_usec 10\nuser_usec 5\nsystem_usec 5\n").throttled_ratio(), 0.0);
+
+        let devices = parse_io_stat("8:16 rbytes=1 wbytes=2 rios=3 wios=4 dbytes=0 dios=0\n8:0 rbytes=10 wbytes=20 rios=30 wios=40\n");
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].device, "8:0");
+        assert_eq!((devices[0].read_bytes, devices[0].written_bytes, devices[0].read_ios, devices[0].write_ios), (10, 20, 30, 40));
+        assert!(parse_io_stat("").is_empty());
+    }
+
+    #[test]
+    fn test_read_fixture_tree() {
+        let root = fixture_root();
+        let nginx = read_cgroup_stats(&root, "system.slice/nginx.service/").unwrap();
+        assert_eq!(nginx.path, "/system.slice/nginx.service");
+        assert_eq!((nginx.name.as_str(), nginx.depth, nginx.child_count), ("nginx.service", 2, 0));
+        assert_eq!(nginx.memory_current_bytes, Some(256 * 1024 * 1024));
+        assert_eq!(nginx.memory_max_bytes, Some(512 * 1024 * 1024));
+        let cpu = nginx.cpu.unwrap();
+        assert_eq!((cpu.nr_throttled, cpu.throttled_usec), (3000, 45_000_000));
+        assert_eq!(nginx.io_read_bytes, 10485760 + 4096);
+        assert_eq!(nginx.io_written_bytes, 20971520 + 8192);
+        assert_eq!((nginx.pids_current, nginx.pids_max), (Some(5), Some(100)));
+
+        // 根没有 memory.current 和 pids.current
+        let node = read_cgroup_node(&root, "/").unwrap();
+        assert_eq!((node.cgroup.path.as_str(), node.cgroup.name.as_str(), node.cgroup.depth), ("/", "/", 0));
+        assert_eq!(node.cgroup.memory_current_bytes, None);
+        assert_eq!(node.cgroup.io_read_bytes, 1048576000);
+        let children: Vec<&str> = node.children.iter().map(|child| child.path.as_str()).collect();
+        assert_eq!(children, vec!["/system.slice", "/user.slice"]);
+        assert_eq!(node.children[0].child_count, 2);
+        assert_eq!(node.children[1].memory_max_bytes, Some(4 * 1024 * 1024 * 1024));
+        // 没有 io.stat 的 cgroup
+        assert_eq!(node.children[1].io_read_bytes, 0);
+
+        assert!(read_cgroup_stats(&root, "/missing.slice").unwrap_err().contains("not found"));
+        assert!(read_cgroup_stats(&root, "system.slice/../../etc").is_err());
+        assert_eq!(normalize_cgroup_path("//system.slice//nginx.service/"), Ok("/system.slice/nginx.service".to_string()));
+        assert_eq!(normalize_cgroup_path(""), Ok("/".to_string()));
+    }
+
+    #[test]
+    fn test_collect_for_history() {
+        let root = fixture_root();
+        let paths = |depth: usize| -> Vec<String> { collect_cgroups(&root, depth).into_iter().map(|stats| stats.path).collect() };
+        assert_eq!(paths(0), vec!["/"]);
+        assert_eq!(paths(1), vec!["/", "/system.slice", "/user.slice"]);
+        assert_eq!(
+            paths(CGROUP_HISTORY_DEPTH),
+            vec!["/", "/system.slice", "/system.slice/nginx.service", "/system.slice/sshd.service", "/user.slice"]
+        );
+
+        let timestamp = chrono::Utc::now();
+        let stats = read_cgroup_stats(&root, "/system.slice/nginx.service").unwrap();
+        let sample = CgroupSample::from_stats(timestamp, &stats);
+        assert_eq!(sample.path, "/system.slice/nginx.service");
+        assert_eq!(sample.cpu_usage_usec, Some(400_000_000));
+        assert_eq!(sample.cpu_nr_throttled, Some(3000));
+        assert_eq!(sample.memory_current_bytes, Some(256 * 1024 * 1024));
+        let user = CgroupSample::from_stats(timestamp, &read_cgroup_stats(&root, "/user.slice").unwrap());
+        assert_eq!((user.cpu_nr_periods, user.io_written_bytes), (Some(0), 0));
+
+        // 本机挂载了 cgroup v2 时可以读取根
+        if let Some(root) = tauri_react_ai_lib::cgroup_root() {
+            assert!(read_cgroup_node(&root, "/").unwrap().cgroup.cpu.is_some());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_and_fetch_cgroup_history() {
+        let manager = DatabaseManager::with_pool(memory_pool().await).await.unwrap();
+        let timestamp = Utc::now();
+        let record = HistoricalSystemData {
+            id: "record-1".to_string(),
+            timestamp,
+            cpu_usage: 10.0,
+            memory_usage: 50.0,
+            memory_total: 8589934592,
+            disk_usage: Vec::new(),
+            network_traffic: NetworkTrafficData {
+                bytes_received: 0,
+                bytes_sent: 0,
+                packets_received: 0,
+                packets_sent: 0,
+            },
+            system_load: 1.0,
+            open_fds: None,
+        };
+        manager.store_historical_data(&record).await.unwrap();
+
+        let samples: Vec<CgroupSample> = collect_cgroups(&fixture_root(), CGROUP_HISTORY_DEPTH)
+            .iter()
+            .map(|stats| CgroupSample::from_stats(timestamp, stats))
+            .collect();
+        manager.store_cgroup_samples(&record.id, &samples).await.unwrap();
+
+        let range = (timestamp - Duration::minutes(1), timestamp + Duration::minutes(1));
+        let nginx = manager.fetch_cgroup_history("/system.slice/nginx.service", range.0, range.1).await.unwrap();
+        assert_eq!(nginx.len(), 1);
+        assert_eq!(nginx[0], samples.iter().find(|sample| sample.path == "/system.slice/nginx.service").unwrap().clone());
+
+        // 根没有的单值文件存取后仍为 None
+        let root = manager.fetch_cgroup_history("/", range.0, range.1).await.unwrap();
+        assert_eq!((root[0].memory_current_bytes, root[0].memory_max_bytes, root[0].pids_current), (None, None, None));
+        assert_eq!(root[0].io_read_bytes, 1048576000);
+
+        assert!(manager.fetch_cgroup_history("/missing.slice", range.0, range.1).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,83 @@
+//! 进程环境变量测试
+//! 覆盖 environ 的解析、敏感变量的默认与自定义隐藏规则，以及读取子进程的真实环境
+
+use tauri_react_ai_lib::{parse_environ, EnvMasking, MASKED_VALUE};
+
+const ENVIRON: &[u8] = b"PATH=/usr/bin:/bin\0DB_PASSWORD=hunter2\0HOME=/srv/app\0GITHUB_TOKEN=ghp_x\0EMPTY=\0OPTS=a=b\0\0";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_with_default_masking() {
+        let variables = parse_environ(ENVIRON, &EnvMasking::default());
+        let keys: Vec<&str> = variables.iter().map(|variable| variable.key.as_str()).collect();
+        assert_eq!(keys, vec!["DB_PASSWORD", "EMPTY", "GITHUB_TOKEN", "HOME", "OPTS", "PATH"]);
+
+        let value = |key: &str| variables.iter().find(|variable| variable.key == key).unwrap();
+        assert_eq!(value("DB_PASSWORD").value, MASKED_VALUE);
+        assert!(value("DB_PASSWORD").masked);
+        assert_eq!(value("GITHUB_TOKEN").value, MASKED_VALUE);
+        assert_eq!(value("PATH").value, "/usr/bin:/bin");
+        assert!(!value("PATH").masked);
+        // 只在第一个等号处拆分
+        assert_eq!(value("OPTS").value, "a=b");
+        assert_eq!(value("EMPTY").value, "");
+    }
+
+    #[test]
+    fn test_custom_masking() {
+        let disabled = EnvMasking {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(parse_environ(ENVIRON, &disabled).iter().all(|variable| !variable.masked));
+
+        let custom = EnvMasking {
+            enabled: true,
+            patterns: vec!["home".to_string(), " ".to_string()],
+        };
+        let masked: Vec<String> = parse_environ(ENVIRON, &custom)
+            .into_iter()
+            .filter(|variable| variable.masked)
+            .map(|variable| variable.key)
+            .collect();
+        assert_eq!(masked, vec!["HOME".to_string()]);
+
+        // 未给出的字段使用默认值
+        let parsed: EnvMasking = serde_json::from_str(r#"{"patterns": ["DSN"]}"#).unwrap();
+        assert!(parsed.enabled);
+        assert!(parsed.is_secret("sentry_dsn"));
+        assert!(!parsed.is_secret("API_TOKEN"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_child_environment() {
+        let mut child = std::process::Command::new("sleep")
+            .arg("30")
+            .env_clear()
+            .env("SERVICE_NAME", "worker")
+            .env("SERVICE_SECRET", "s3cr3t")
+            .spawn()
+            .unwrap();
+        // 等待 exec 完成，之前读到的是父进程的环境
+        let pid = child.id();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        let environment = loop {
+            let environment = tauri_react_ai_lib::read_environment(pid, &EnvMasking::default()).unwrap();
+            if environment.variables.len() == 2 || std::time::Instant::now() > deadline {
+                break environment;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        };
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(environment.masked_count, 1);
+        assert_eq!(environment.variables[0].key, "SERVICE_NAME");
+        assert_eq!(environment.variables[0].value, "worker");
+        assert_eq!(environment.variables[1].value, MASKED_VALUE);
+    }
+}
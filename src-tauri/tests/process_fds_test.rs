@@ -0,0 +1,141 @@
+//! 文件描述符测试
+//! 覆盖链接目标分类、路径匹配、fds 告警指标及其警报中的进程，以及对当前进程已删除文件的反向查找
+
+use tauri_react_ai_lib::{
+    classify_fd_target, fd_matches_path, rules_use_metric, AlertConfiguration, AlertExpression, AlertHistoryFilter,
+    AlertMetric, DatabaseManager, FdKind, FdUsage, IncidentGrouping, MessageEnvironment, MessageLocale, MetricSnapshot,
+};
+
+fn rule(metric: &str, expression: Option<&str>) -> AlertConfiguration {
+    serde_json::from_value(serde_json::json!({
+        "id": "",
+        "metric": metric,
+        "condition": "greater_than",
+        "threshold": 1000,
+        "severity": "high",
+        "enabled": true,
+        "notification_methods": ["visual"],
+        "expression": expression
+    }))
+    .unwrap()
+}
+
+// 单连接的内存数据库，连接池中的所有查询共享同一个库
+async fn memory_pool() -> sqlx::SqlitePool {
+    sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_fd_target() {
+        assert_eq!(classify_fd_target("/var/log/app.log"), (FdKind::File, "/var/log/app.log".to_string()));
+        assert_eq!(
+            classify_fd_target("/var/log/app.log.1 (deleted)"),
+            (FdKind::Deleted, "/var/log/app.log.1".to_string())
+        );
+        assert_eq!(classify_fd_target("socket:[123456]").0, FdKind::Socket);
+        assert_eq!(classify_fd_target("pipe:[98765]").0, FdKind::Pipe);
+        assert_eq!(classify_fd_target("anon_inode:[eventfd]").0, FdKind::AnonInode);
+        assert_eq!(classify_fd_target("anon_inode:inotify").0, FdKind::AnonInode);
+        assert_eq!(classify_fd_target("/dev/null").0, FdKind::File);
+        assert_eq!(classify_fd_target("net:[4026531840]").0, FdKind::Other);
+        // 非路径的 " (deleted)" 后缀不视为已删除文件
+        assert_eq!(classify_fd_target("anon_inode:x (deleted)").0, FdKind::AnonInode);
+    }
+
+    #[test]
+    fn test_path_matching_and_metric() {
+        assert!(fd_matches_path("/var/log/app.log", "/var/log/app.log"));
+        assert!(fd_matches_path("/var/log/app.log", "/var/log/"));
+        assert!(fd_matches_path("/var/log/nginx/access.log", "/var/log"));
+        assert!(!fd_matches_path("/var/logs/app.log", "/var/log"));
+        assert!(!fd_matches_path("/var/log", "/var/log/app.log"));
+        assert!(fd_matches_path("/etc/hosts", "/"));
+
+        assert_eq!(AlertMetric::from_name("fds"), Some(AlertMetric::OpenFds));
+        assert_eq!(AlertMetric::OpenFds.as_str(), "open_fds");
+        assert!(AlertMetric::OpenFds.has_history());
+        assert!(!AlertMetric::OpenFds.is_percentage());
+        // 文件描述符持续增长即视为泄漏
+        assert!(AlertExpression::parse("rate(fds, 30m) > 0.5 AND fds > 1000").is_ok());
+
+        // 只有启用的规则使用 fds 时才需要扫描所有进程
+        let mut rules = vec![rule("cpu_usage", None), rule("cpu_usage", Some("rate(fds, 30m) > 0.5"))];
+        assert!(rules_use_metric(&rules, AlertMetric::OpenFds));
+        rules[1].enabled = false;
+        assert!(!rules_use_metric(&rules, AlertMetric::OpenFds));
+        rules.push(rule("fds", None));
+        assert!(rules_use_metric(&rules, AlertMetric::OpenFds));
+    }
+
+    #[tokio::test]
+    async fn test_fd_alert_names_process() {
+        let manager = DatabaseManager::with_pool(memory_pool().await).await.unwrap();
+        manager.add_alert_configuration(&rule("fds", None)).await.unwrap();
+        let configurations = manager.get_alert_configurations().await.unwrap();
+        let environment = MessageEnvironment {
+            hostname: "build-01".to_string(),
+            locale: MessageLocale::En,
+            top_processes: Vec::new(),
+        };
+        let grouping = IncidentGrouping::default();
+
+        // 未采集时无法求值，不触发也不恢复
+        let mut snapshot = MetricSnapshot::default();
+        let fired = manager.check_alerts(configurations.clone(), &snapshot, &environment, &grouping).await.unwrap();
+        assert!(fired.is_empty());
+        assert!(manager.get_alert_history(&AlertHistoryFilter::default(), None, 0).await.unwrap().is_empty());
+
+        snapshot.open_fds = Some(4096.0);
+        snapshot.open_fds_process = Some(FdUsage {
+            pid: 4242,
+            process_name: "leaky-worker".to_string(),
+            fd_count: 4096,
+        });
+        let fired = manager.check_alerts(configurations, &snapshot, &environment, &grouping).await.unwrap();
+        assert_eq!(fired.len(), 1);
+        assert!(fired[0].message.contains("held by leaky-worker (PID 4242)"), "{}", fired[0].message);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_busiest_process() {
+        // 当前进程至少持有标准输入输出，总能找到持有者
+        let busiest = tauri_react_ai_lib::busiest_process().unwrap();
+        assert!(busiest.pid > 0);
+        assert!(busiest.fd_count > 0);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_deleted_file_held_open() {
+        use std::io::Write;
+        use tauri_react_ai_lib::FileHolders;
+
+        let path = std::env::temp_dir().join(format!("fd-test-{}.log", std::process::id()));
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(&[0u8; 4096]).unwrap();
+        let canonical = std::fs::canonicalize(&path).unwrap().to_string_lossy().to_string();
+        std::fs::remove_file(&path).unwrap();
+
+        let pid = std::process::id();
+        let fds = tauri_react_ai_lib::list_fds(pid).unwrap();
+        let deleted = fds.fds.iter().find(|open| open.target == canonical).unwrap();
+        assert_eq!(deleted.kind, FdKind::Deleted);
+        assert_eq!(deleted.size_bytes, Some(4096));
+        assert!(fds.counts_by_kind[&FdKind::Deleted] >= 1);
+        assert_eq!(fds.fd_count as usize, fds.fds.len());
+
+        let holders: FileHolders = tauri_react_ai_lib::find_fd_holders(&canonical);
+        assert!(holders.holders.iter().any(|holder| holder.pid == pid && holder.fd == deleted.fd));
+        drop(file);
+        assert!(tauri_react_ai_lib::find_fd_holders(&canonical).holders.iter().all(|holder| holder.pid != pid));
+    }
+}
@@ -0,0 +1,59 @@
+//! 进程磁盘 I/O 测试
+//! 覆盖速率计算、I/O 排行的排序，以及真实采样中的当前进程
+
+use std::time::Duration;
+
+use tauri_react_ai_lib::{sort_by_io, ProcessIo};
+
+fn io(pid: u32, totals: (u64, u64), deltas: (u64, u64)) -> ProcessIo {
+    ProcessIo::new(pid, format!("proc-{}", pid), totals, deltas, Duration::from_secs(2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_rates() {
+        let entry = io(10, (1_000_000, 4_000_000), (200_000, 1_000_000));
+        assert_eq!(entry.read_bytes, 1_000_000);
+        assert_eq!(entry.written_bytes, 4_000_000);
+        assert_eq!(entry.read_bytes_per_sec, 100_000.0);
+        assert_eq!(entry.written_bytes_per_sec, 500_000.0);
+        assert_eq!(entry.total_bytes_per_sec(), 600_000.0);
+
+        // 间隔为零时不计算速率
+        let instant = ProcessIo::new(11, "x".to_string(), (5, 5), (5, 5), Duration::ZERO);
+        assert_eq!(instant.total_bytes_per_sec(), 0.0);
+    }
+
+    #[test]
+    fn test_sort_by_io() {
+        let mut entries = vec![
+            io(1, (100, 100), (0, 0)),
+            io(2, (10, 10), (0, 4_000)),
+            io(3, (50_000, 0), (0, 0)),
+            io(4, (0, 0), (3_000, 1_000)),
+            io(5, (100, 100), (0, 0)),
+        ];
+        sort_by_io(&mut entries);
+        // 先按速率，再按累计读写量，最后按 PID
+        let order: Vec<u32> = entries.iter().map(|entry| entry.pid).collect();
+        assert_eq!(order, vec![2, 4, 3, 1, 5]);
+    }
+
+    #[test]
+    fn test_sample_own_process() {
+        use tauri_react_ai_lib::{io_top, ProcessEntry};
+
+        let pid = std::process::id();
+        let entries = io_top(Duration::from_millis(200), usize::MAX);
+        let own = entries.iter().find(|entry| entry.pid == pid).unwrap();
+        assert!(own.read_bytes_per_sec >= 0.0 && own.written_bytes_per_sec >= 0.0);
+
+        // 快照中包含命令行，需要在刷新时显式读取
+        let snapshot = ProcessEntry::snapshot();
+        let own = snapshot.iter().find(|entry| entry.pid == pid).unwrap();
+        assert!(!own.command_line.is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! 进程资源限制测试
+//! 基于 tests/fixtures/limits 下的样例覆盖 /proc/<pid>/limits 的解析、提高软限制的校验，
+//! 以及对子进程真实修改软限制
+
+use tauri_react_ai_lib::{parse_limit_value, parse_limits, validate_soft_limit_raise, ResourceLimit, ResourceLimitKind};
+
+const LIMITS: &str = include_str!("fixtures/limits/limits");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_limits() {
+        let limits = parse_limits(LIMITS);
+        assert_eq!(limits.len(), ResourceLimitKind::ALL.len());
+
+        let find = |kind: ResourceLimitKind| limits.iter().find(|limit| limit.kind == kind).unwrap();
+        let nofile = find(ResourceLimitKind::Nofile);
+        assert_eq!((nofile.soft_limit, nofile.hard_limit), (Some(1024), Some(524288)));
+        assert_eq!(nofile.units.as_deref(), Some("files"));
+        let stack = find(ResourceLimitKind::Stack);
+        assert_eq!((stack.soft_limit, stack.hard_limit), (Some(8388608), None));
+        assert_eq!(find(ResourceLimitKind::Core).soft_limit, Some(0));
+        // 没有单位的限制
+        assert_eq!(find(ResourceLimitKind::Nice).units, None);
+        assert_eq!(find(ResourceLimitKind::Rttime).units.as_deref(), Some("us"));
+
+        assert!(parse_limits("").is_empty());
+    }
+
+    #[test]
+    fn test_names_and_raise_validation() {
+        assert_eq!(ResourceLimitKind::from_name("nofile"), Some(ResourceLimitKind::Nofile));
+        assert_eq!(ResourceLimitKind::from_name("RLIMIT_NOFILE"), Some(ResourceLimitKind::Nofile));
+        assert_eq!(ResourceLimitKind::from_name("max open files"), Some(ResourceLimitKind::Nofile));
+        assert_eq!(ResourceLimitKind::from_name("as"), Some(ResourceLimitKind::As));
+        assert_eq!(ResourceLimitKind::from_name("files"), None);
+        assert_eq!(parse_limit_value("unlimited"), Ok(None));
+        assert_eq!(parse_limit_value(" 4096 "), Ok(Some(4096)));
+        assert!(parse_limit_value("-1").is_err());
+
+        let current = ResourceLimit {
+            kind: ResourceLimitKind::Nofile,
+            soft_limit: Some(1024),
+            hard_limit: Some(524288),
+            units: None,
+        };
+        assert!(validate_soft_limit_raise(&current, Some(65536)).is_ok());
+        assert!(validate_soft_limit_raise(&current, Some(524288)).is_ok());
+        assert!(validate_soft_limit_raise(&current, Some(512)).unwrap_err().contains("only raising"));
+        assert!(validate_soft_limit_raise(&current, Some(524289)).unwrap_err().contains("exceeds the hard limit"));
+        assert!(validate_soft_limit_raise(&current, None).is_err());
+        // 硬限制为不限制时可以把软限制也设为不限制
+        let core = ResourceLimit {
+            kind: ResourceLimitKind::Core,
+            soft_limit: Some(0),
+            hard_limit: None,
+            units: None,
+        };
+        assert!(validate_soft_limit_raise(&core, None).is_ok());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_raise_child_soft_limit() {
+        use tauri_react_ai_lib::{raise_soft_limit, read_limits};
+
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+        let before = read_limits(pid)
+            .unwrap()
+            .limits
+            .into_iter()
+            .find(|limit| limit.kind == ResourceLimitKind::Core)
+            .unwrap();
+
+        let result = raise_soft_limit(pid, ResourceLimitKind::Core, before.hard_limit);
+        child.kill().unwrap();
+        child.wait().unwrap();
+
+        let after = result.unwrap();
+        assert_eq!(after.soft_limit, before.hard_limit);
+        assert_eq!(after.hard_limit, before.hard_limit);
+        assert!(raise_soft_limit(u32::MAX, ResourceLimitKind::Core, None).is_err());
+    }
+}
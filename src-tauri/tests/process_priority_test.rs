@@ -0,0 +1,133 @@
+//! 进程优先级与 CPU 亲和性测试
+//! 覆盖 CPU 列表的解析与格式化、/proc/<pid>/stat 解析，以及对子进程的 renice 和亲和性设置
+
+use tauri_react_ai_lib::{format_cpu_list, parse_cpu_list, ProcStat, SchedulingPolicy};
+
+// 进程名中带空格和括号，验证以最后一个 ')' 为界
+const STAT_SAMPLE: &str = "4242 (my (odd) app) S 1 4242 4242 0 -1 4194560 1520 0 0 0 \
+    250 75 0 0 25 5 7 0 123456 24576000 1200 18446744073709551615 1 1 0 0 0 0 0 0 0 0 0 0 17 3 10 2 0 0 0";
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cpu_list() {
+        assert_eq!(parse_cpu_list("0-3,6").unwrap(), vec![0, 1, 2, 3, 6]);
+        assert_eq!(parse_cpu_list(" 5, 1-2 ,2 ").unwrap(), vec![1, 2, 5]);
+        assert!(parse_cpu_list("").is_err());
+        assert!(parse_cpu_list("3-1").is_err());
+        assert!(parse_cpu_list("a").is_err());
+
+        assert_eq!(format_cpu_list(&[0, 1, 2, 3, 6, 8, 9]), "0-3,6,8-9");
+        assert_eq!(format_cpu_list(&[4]), "4");
+        assert_eq!(format_cpu_list(&[]), "");
+    }
+
+    #[test]
+    fn test_parse_proc_stat() {
+        let stat = ProcStat::parse(STAT_SAMPLE).unwrap();
+        assert_eq!(stat.pid, 4242);
+        assert_eq!(stat.comm, "my (odd) app");
+        assert_eq!(stat.state, 'S');
+        assert_eq!(stat.parent_pid, 1);
+        assert_eq!(stat.process_group, 4242);
+        assert_eq!((stat.user_ticks, stat.system_ticks), (250, 75));
+        assert_eq!(stat.nice, 5);
+        assert_eq!(stat.num_threads, 7);
+        assert_eq!(stat.start_ticks, 123456);
+        assert_eq!(stat.realtime_priority, 10);
+        assert_eq!(SchedulingPolicy::from_number(stat.policy), Some(SchedulingPolicy::RoundRobin));
+        assert!(SchedulingPolicy::RoundRobin.is_realtime());
+        assert_eq!(SchedulingPolicy::from_number(4), None);
+
+        assert!(ProcStat::parse("4242 (truncated) S 1").is_none());
+        assert!(ProcStat::parse("").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_renice_and_affinity() {
+        use tauri_react_ai_lib::{get_affinity, read_priority, set_affinity, set_nice};
+
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let pid = child.id();
+
+        let before = read_priority(pid).unwrap();
+        assert_eq!(before.scheduling_policy, Some(SchedulingPolicy::Other));
+        assert!(!before.permission_denied);
+        // 提高 nice 值（降低优先级）不需要特权
+        let target = (before.nice + 5).min(19);
+        assert_eq!(set_nice(pid, target).unwrap().nice, target);
+        assert!(set_nice(pid, 40).is_err());
+        // 普通用户不能重新降低 nice 值，错误需说明原因
+        if let Err(error) = set_nice(pid, before.nice) {
+            assert!(error.starts_with("Permission denied"), "{}", error);
+        }
+
+        let affinity = get_affinity(pid).unwrap();
+        assert!(!affinity.cpus.is_empty());
+        let first = affinity.cpus[0];
+        let pinned = set_affinity(pid, &[first]).unwrap();
+        assert_eq!(pinned.cpus, vec![first]);
+        assert_eq!(pinned.cpu_list, first.to_string());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(read_priority(pid).unwrap_err().contains("not found"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_renice_applies_to_all_threads() {
+        use tauri_react_ai_lib::{read_priority, set_nice};
+
+        // 以子进程运行下面的 renice_target，得到一个有多个线程的进程
+        let mut child = std::process::Command::new(std::env::current_exe().unwrap())
+            .args(["--exact", "tests::renice_target", "--ignored", "--nocapture"])
+            .stdout(std::process::Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let task_dir = format!("/proc/{}/task", pid);
+        let threads = || std::fs::read_dir(&task_dir).map(|entries| entries.count()).unwrap_or(0);
+        for _ in 0..250 {
+            if threads() >= 3 {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+        assert!(threads() >= 3, "child has {} threads", threads());
+
+        let target = (read_priority(pid).unwrap().nice + 3).min(19);
+        set_nice(pid, target).unwrap();
+        for entry in std::fs::read_dir(&task_dir).unwrap() {
+            let tid: u32 = entry.unwrap().file_name().to_str().unwrap().parse().unwrap();
+            let stat = ProcStat::parse(&std::fs::read_to_string(format!("/proc/{}/task/{}/stat", pid, tid)).unwrap())
+                .unwrap();
+            assert_eq!(stat.nice as i32, target, "thread {}", tid);
+        }
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    // 只供 test_renice_applies_to_all_threads 在子进程中运行
+    #[test]
+    #[ignore]
+    fn renice_target() {
+        let worker = std::thread::spawn(|| std::thread::sleep(std::time::Duration::from_secs(30)));
+        worker.join().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_priority_of_other_user() {
+        use tauri_react_ai_lib::read_priority;
+
+        // init 属于 root，普通用户仍能读取，但会标记为无权修改
+        let priority = read_priority(1).unwrap();
+        let is_root = unsafe { libc::geteuid() } == 0;
+        assert_eq!(priority.permission_denied, !is_root);
+    }
+}
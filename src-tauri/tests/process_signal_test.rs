@@ -0,0 +1,100 @@
+//! 进程信号测试
+//! 覆盖信号名解析、进程树的发送顺序，以及对真实进程组的暂停、恢复和逐级终止
+
+use tauri_react_ai_lib::{signal_targets, ProcessEntry, ProcessSignal, SignalScope};
+
+fn entry(pid: u32, parent_pid: Option<u32>, name: &str) -> ProcessEntry {
+    ProcessEntry {
+        pid,
+        parent_pid,
+        name: name.to_string(),
+        command_line: name.to_string(),
+        user: None,
+        user_id: None,
+        cpu_usage_percent: 0.0,
+        memory_usage_bytes: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signal_names() {
+        assert_eq!(ProcessSignal::from_name("SIGTERM"), Some(ProcessSignal::Term));
+        assert_eq!(ProcessSignal::from_name("usr1"), Some(ProcessSignal::Usr1));
+        assert_eq!(ProcessSignal::from_name(" sigcont "), Some(ProcessSignal::Cont));
+        assert_eq!(ProcessSignal::from_name("SIGSEGV"), None);
+        assert_eq!(ProcessSignal::from_name("SIG"), None);
+
+        assert_eq!(serde_json::to_string(&ProcessSignal::Hup).unwrap(), "\"SIGHUP\"");
+        let scope: SignalScope = serde_json::from_str("\"tree\"").unwrap();
+        assert_eq!(scope, SignalScope::Tree);
+    }
+
+    #[test]
+    fn test_tree_targets() {
+        let own_pid = std::process::id();
+        let entries = vec![
+            entry(1, None, "init"),
+            entry(100, Some(1), "supervisor"),
+            entry(110, Some(100), "worker"),
+            entry(111, Some(110), "helper"),
+            entry(120, Some(100), "worker"),
+            entry(own_pid, Some(100), "monitor"),
+        ];
+
+        // 后代先于祖先，本程序自身不在其中
+        assert_eq!(signal_targets(&entries, 100, SignalScope::Tree).unwrap(), vec![111, 110, 120, 100]);
+        assert_eq!(signal_targets(&entries, 110, SignalScope::Process).unwrap(), vec![110]);
+        // init 的子树中也不包含 init 以外受保护的进程
+        let from_init = signal_targets(&entries, 1, SignalScope::Tree).unwrap();
+        assert!(!from_init.contains(&own_pid));
+        assert_eq!(from_init.last(), Some(&1));
+
+        assert!(signal_targets(&entries, 999, SignalScope::Tree).is_none());
+        assert!(signal_targets(&entries, 999, SignalScope::Process).is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_signal_real_process_group() {
+        use std::os::unix::process::CommandExt;
+        use std::time::Duration;
+        use tauri_react_ai_lib::{process_group, signal_process, terminate_gracefully, SignalStatus};
+
+        // 新建进程组：sh 及其两个 sleep 子进程
+        let mut child = std::process::Command::new("sh")
+            .args(["-c", "sleep 30 & sleep 30 & wait"])
+            .process_group(0)
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        std::thread::sleep(Duration::from_millis(300));
+        assert_eq!(process_group(pid), Some(pid));
+
+        let stopped = signal_process(pid, ProcessSignal::Stop, SignalScope::Process).unwrap();
+        assert_eq!(stopped.len(), 1);
+        assert_eq!(stopped[0].status, SignalStatus::Delivered);
+        let resumed = signal_process(pid, ProcessSignal::Cont, SignalScope::Process).unwrap();
+        assert_eq!(resumed[0].status, SignalStatus::Delivered);
+
+        let outcomes = terminate_gracefully(pid, SignalScope::Group, Duration::from_secs(5)).unwrap();
+        assert_eq!(outcomes.len(), 3);
+        // 组长最后处理
+        assert_eq!(outcomes.last().unwrap().pid, pid);
+        assert!(outcomes.iter().all(|outcome| outcome.status == SignalStatus::Terminated), "{:?}", outcomes);
+        assert!(outcomes.iter().all(|outcome| outcome.signal == ProcessSignal::Term));
+
+        child.wait().unwrap();
+        assert!(signal_process(pid, ProcessSignal::Term, SignalScope::Process).is_err());
+
+        // 与本程序同组的进程不能按进程组发送信号
+        let mut sibling = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+        let err = signal_process(sibling.id(), ProcessSignal::Term, SignalScope::Group).unwrap_err();
+        assert!(err.contains("own process group"), "{}", err);
+        sibling.kill().unwrap();
+        sibling.wait().unwrap();
+    }
+}
@@ -0,0 +1,75 @@
+//! 进程内存映射测试
+//! 基于 tests/fixtures/smaps 下的样例文件覆盖 smaps_rollup 与 smaps 的解析和按文件汇总，
+//! 并读取当前进程的真实映射
+
+use tauri_react_ai_lib::{group_mappings, parse_smaps, parse_smaps_rollup, MappingKind};
+
+const SMAPS: &str = include_str!("fixtures/smaps/smaps");
+const SMAPS_ROLLUP: &str = include_str!("fixtures/smaps/smaps_rollup");
+
+const KB: u64 = 1024;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rollup() {
+        let usage = parse_smaps_rollup(SMAPS_ROLLUP);
+        assert_eq!(usage.rss_bytes, 1324 * KB);
+        assert_eq!(usage.pss_bytes, 500 * KB);
+        assert_eq!(usage.uss_bytes, 280 * KB);
+        assert_eq!(usage.swap_bytes, 16 * KB);
+        assert_eq!(usage.anonymous_bytes, 244 * KB);
+        assert_eq!(usage.shared_bytes(), 1044 * KB);
+        assert_eq!(usage.private_bytes(), usage.uss_bytes);
+        assert_eq!(usage.shared_bytes() + usage.private_bytes(), usage.rss_bytes);
+    }
+
+    #[test]
+    fn test_parse_mappings_and_group() {
+        let mappings = parse_smaps(SMAPS);
+        assert_eq!(mappings.len(), 11);
+
+        let text = &mappings[1];
+        assert_eq!(text.permissions, "r-xp");
+        assert_eq!(text.offset, 0x2000);
+        assert_eq!(text.size_bytes, 24 * KB);
+        assert_eq!(text.kind, MappingKind::File);
+        // 路径中的空格和已删除文件的后缀原样保留
+        assert!(mappings.iter().any(|mapping| mapping.pathname == "/opt/My App/data.bin"));
+        assert!(mappings.iter().any(|mapping| mapping.pathname == "/dev/shm/cache (deleted)"));
+        assert_eq!(mappings[3].kind, MappingKind::Anonymous);
+        assert_eq!(mappings[10].kind, MappingKind::Special);
+
+        // 各映射之和与 smaps_rollup 一致
+        let rollup = parse_smaps_rollup(SMAPS_ROLLUP);
+        let groups = group_mappings(&mappings);
+        assert_eq!(groups.iter().map(|group| group.usage.rss_bytes).sum::<u64>(), rollup.rss_bytes);
+        assert_eq!(groups.iter().map(|group| group.usage.pss_bytes).sum::<u64>(), rollup.pss_bytes);
+
+        // 按 PSS 排列：libc 的两段映射合为一组，两段匿名映射合为 [anon]
+        let order: Vec<&str> = groups.iter().map(|group| group.backing.as_str()).collect();
+        assert_eq!(&order[..4], &["/usr/lib/x86_64-linux-gnu/libc.so.6", "[heap]", "[anon]", "[stack]"]);
+        let libc = &groups[0];
+        assert_eq!(libc.mapping_count, 2);
+        assert_eq!(libc.usage.pss_bytes, 208 * KB);
+        assert_eq!(libc.usage.shared_bytes(), 1000 * KB);
+        let anon = &groups[2];
+        assert_eq!((anon.kind, anon.mapping_count, anon.usage.rss_bytes, anon.usage.swap_bytes), (MappingKind::Anonymous, 2, 80 * KB, 4 * KB));
+        assert_eq!(groups.iter().find(|group| group.backing == "/usr/bin/app").unwrap().usage.uss_bytes, 32 * KB);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_read_own_memory_map() {
+        let memory_map = tauri_react_ai_lib::read_memory_map(std::process::id()).unwrap();
+        let summary = memory_map.summary;
+        assert!(summary.rss_bytes > 0);
+        assert!(summary.uss_bytes <= summary.rss_bytes && summary.pss_bytes <= summary.rss_bytes);
+        assert!(memory_map.groups.iter().any(|group| group.kind == MappingKind::File));
+        assert!(memory_map.groups.windows(2).all(|pair| pair[0].usage.pss_bytes >= pair[1].usage.pss_bytes));
+
+        assert!(tauri_react_ai_lib::read_memory_map(u32::MAX).unwrap_err().contains("not found"));
+    }
+}
@@ -0,0 +1,235 @@
+//! 进程守护测试
+//! 覆盖规则的序列化与校验、指数退避、按名称监视进程，以及启动真实命令后的退出码、重启、放弃与停止
+
+use chrono::{Duration, Utc};
+use tauri_react_ai_lib::{
+    LaunchCommand, ProcessEntry, SupervisedState, Supervisor, SupervisorEvent, SupervisorEventKind, SupervisorRule,
+    SupervisorTarget, BACKOFF_RESET_SECS, MAX_BACKOFF_SECS,
+};
+
+fn rule(id: &str, target: SupervisorTarget) -> SupervisorRule {
+    SupervisorRule {
+        id: id.to_string(),
+        name: format!("rule-{}", id),
+        target,
+        enabled: true,
+        initial_backoff_secs: 1,
+        max_backoff_secs: 8,
+        max_restarts: None,
+    }
+}
+
+fn shell(script: &str) -> LaunchCommand {
+    LaunchCommand {
+        program: "/bin/sh".to_string(),
+        args: vec!["-c".to_string(), script.to_string()],
+        working_directory: None,
+    }
+}
+
+fn entry(pid: u32, name: &str) -> ProcessEntry {
+    ProcessEntry {
+        pid,
+        parent_pid: None,
+        name: name.to_string(),
+        command_line: name.to_string(),
+        user: None,
+        user_id: None,
+        cpu_usage_percent: 0.0,
+        memory_usage_bytes: 0,
+    }
+}
+
+fn event(kind: SupervisorEventKind, restart_count: u32, created_at: chrono::DateTime<Utc>) -> SupervisorEvent {
+    SupervisorEvent {
+        id: format!("{}-{}", kind.as_str(), restart_count),
+        rule_id: "restored".to_string(),
+        rule_name: "rule-restored".to_string(),
+        kind,
+        pid: None,
+        exit_code: Some(1),
+        signal: None,
+        message: None,
+        restart_count,
+        created_at,
+    }
+}
+
+// 反复检查直到有事件产生，用于等待启动的命令退出
+fn tick_until_event(supervisor: &Supervisor, rules: &[SupervisorRule], now: chrono::DateTime<Utc>) -> Vec<SupervisorEvent> {
+    for _ in 0..250 {
+        let events = supervisor.tick(rules, &[], now);
+        if !events.is_empty() {
+            return events;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    panic!("supervised command did not exit");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rule_serde_and_validation() {
+        let parsed: SupervisorRule = serde_json::from_str(
+            r#"{"name": "worker", "target": {"type": "command", "program": "/usr/bin/worker", "args": ["--queue", "jobs"]}}"#,
+        )
+        .unwrap();
+        assert!(parsed.enabled);
+        assert_eq!((parsed.initial_backoff_secs, parsed.max_backoff_secs, parsed.max_restarts), (1, 300, None));
+        let command = parsed.target.launch_command().unwrap();
+        assert_eq!(command.command_line(), "/usr/bin/worker --queue jobs");
+        assert!(parsed.validate().is_ok());
+
+        let watch: SupervisorTarget = serde_json::from_str(r#"{"type": "process", "process_name": "nginx"}"#).unwrap();
+        assert_eq!(watch.launch_command(), None);
+        assert!(rule("1", watch).validate().is_ok());
+
+        let relative = rule("2", SupervisorTarget::Command(LaunchCommand { program: "worker".to_string(), args: vec![], working_directory: None }));
+        assert!(relative.validate().unwrap_err().contains("absolute"));
+        let mut empty_name = rule("3", SupervisorTarget::Process { process_name: " ".to_string(), restart_command: None });
+        assert!(empty_name.validate().is_err());
+        empty_name.target = SupervisorTarget::Process { process_name: "nginx".to_string(), restart_command: None };
+        empty_name.name = String::new();
+        assert!(empty_name.validate().is_err());
+        let mut backoff = rule("4", SupervisorTarget::Command(shell("true")));
+        backoff.max_backoff_secs = 0;
+        assert!(backoff.validate().is_err());
+        backoff.max_backoff_secs = MAX_BACKOFF_SECS;
+        assert!(backoff.validate().is_ok());
+        backoff.max_backoff_secs = u64::MAX;
+        assert!(backoff.validate().unwrap_err().contains("must not exceed"));
+        // 未经校验的规则也不会得到超出上限的等待时间
+        assert_eq!(backoff.backoff_delay(200).num_seconds(), MAX_BACKOFF_SECS as i64);
+
+        assert_eq!(SupervisorEventKind::from_name(SupervisorEventKind::RestartFailed.as_str()), Some(SupervisorEventKind::RestartFailed));
+        assert_eq!(SupervisorEventKind::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn test_backoff_and_watch_rule() {
+        let backoff = rule("1", SupervisorTarget::Command(shell("true")));
+        let delays: Vec<i64> = (1..=6).map(|failures| backoff.backoff_delay(failures).num_seconds()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 8, 8]);
+        assert_eq!(backoff.backoff_delay(200).num_seconds(), 8);
+
+        // 没有重启命令的监视规则只记录事件，同名进程任意一个在运行即视为运行中
+        let supervisor = Supervisor::default();
+        let rules = vec![rule("watch", SupervisorTarget::Process { process_name: "NGINX".to_string(), restart_command: None })];
+        let now = Utc::now();
+        assert_eq!(supervisor.status(&rules)[0].state, SupervisedState::Pending);
+        assert!(supervisor.tick(&rules, &[entry(100, "nginx"), entry(101, "nginx")], now).is_empty());
+        assert_eq!(supervisor.status(&rules)[0].state, SupervisedState::Running);
+        assert!(supervisor.tick(&rules, &[entry(101, "nginx")], now).is_empty());
+
+        let events = supervisor.tick(&rules, &[entry(7, "sshd")], now);
+        assert_eq!(events.len(), 1);
+        assert_eq!((events[0].kind, events[0].pid, events[0].exit_code), (SupervisorEventKind::Crashed, Some(101), None));
+        assert_eq!(supervisor.status(&rules)[0].state, SupervisedState::Stopped);
+
+        let events = supervisor.tick(&rules, &[entry(200, "nginx")], now);
+        assert_eq!(events[0].kind, SupervisorEventKind::Recovered);
+        let status = &supervisor.status(&rules)[0];
+        assert_eq!((status.state, status.pid, status.launched), (SupervisedState::Running, Some(200), false));
+
+        // 停用的规则不再跟踪
+        let mut disabled = rules.clone();
+        disabled[0].enabled = false;
+        assert!(supervisor.tick(&disabled, &[], now).is_empty());
+        assert_eq!(supervisor.status(&disabled)[0].state, SupervisedState::Disabled);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_keep_command_running() {
+        let supervisor = Supervisor::default();
+        let mut crashing = rule("crash", SupervisorTarget::Command(shell("exit 3")));
+        crashing.max_restarts = Some(1);
+        let rules = vec![crashing];
+        let start = Utc::now();
+
+        let events = supervisor.tick(&rules, &[], start);
+        assert_eq!(events[0].kind, SupervisorEventKind::Started);
+        assert!(events[0].pid.is_some());
+        assert!(supervisor.status(&rules)[0].launched);
+
+        let events = tick_until_event(&supervisor, &rules, start);
+        assert_eq!((events[0].kind, events[0].exit_code), (SupervisorEventKind::Crashed, Some(3)));
+        let status = &supervisor.status(&rules)[0];
+        assert_eq!((status.state, status.last_exit_code, status.consecutive_failures), (SupervisedState::Backoff, Some(3), 1));
+        assert_eq!(status.next_restart_at, Some(start + Duration::seconds(1)));
+
+        // 退避结束前不重启
+        assert!(supervisor.tick(&rules, &[], start).is_empty());
+        let later = start + Duration::seconds(2);
+        let events = supervisor.tick(&rules, &[], later);
+        assert_eq!((events[0].kind, events[0].restart_count), (SupervisorEventKind::Restarted, 1));
+
+        // 达到重启次数上限后放弃
+        let events = tick_until_event(&supervisor, &rules, later);
+        let kinds: Vec<SupervisorEventKind> = events.iter().map(|event| event.kind).collect();
+        assert_eq!(kinds, vec![SupervisorEventKind::Crashed, SupervisorEventKind::GaveUp]);
+        assert_eq!(supervisor.status(&rules)[0].state, SupervisedState::Failed);
+        assert!(supervisor.tick(&rules, &[], later + Duration::hours(1)).is_empty());
+
+        // 删除规则时停止仍在运行的命令
+        let long_running = vec![rule("sleep", SupervisorTarget::Command(shell("sleep 30")))];
+        let pid = supervisor.tick(&long_running, &[], start)[0].pid.unwrap();
+        assert!(supervisor.tick(&long_running, &[], start).is_empty());
+        let events = supervisor.tick(&[], &[], start);
+        let stopped: Vec<&SupervisorEvent> = events.iter().filter(|event| event.kind == SupervisorEventKind::Stopped).collect();
+        assert_eq!(stopped.len(), 1);
+        assert_eq!((stopped[0].rule_id.as_str(), stopped[0].pid), ("sleep", Some(pid)));
+        let gone = (0..250).any(|_| {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            !std::path::Path::new(&format!("/proc/{}", pid)).exists()
+        });
+        assert!(gone, "stopped command is still running");
+    }
+
+    #[test]
+    fn test_restore_from_events() {
+        let rules = vec![rule("restored", SupervisorTarget::Command(shell("exit 1")))];
+        let start = Utc::now() - Duration::minutes(10);
+        let at = |secs: i64| start + Duration::seconds(secs);
+
+        // 事件从新到旧：两次快速崩溃后放弃
+        let events = vec![
+            event(SupervisorEventKind::GaveUp, 1, at(3)),
+            event(SupervisorEventKind::Crashed, 1, at(3)),
+            event(SupervisorEventKind::Restarted, 1, at(2)),
+            event(SupervisorEventKind::Crashed, 0, at(1)),
+            event(SupervisorEventKind::Started, 0, at(0)),
+        ];
+        let supervisor = Supervisor::default();
+        supervisor.restore(&rules[0], &events);
+        let status = &supervisor.status(&rules)[0];
+        assert_eq!((status.state, status.restart_count, status.consecutive_failures), (SupervisedState::Failed, 1, 2));
+        assert_eq!(status.last_exit_code, Some(1));
+        assert!(supervisor.tick(&rules, &[], Utc::now()).is_empty());
+
+        // 已在跟踪的规则不被覆盖
+        supervisor.restore(&rules[0], &[]);
+        assert_eq!(supervisor.status(&rules)[0].state, SupervisedState::Failed);
+
+        // 运行足够久后退出会重置连续失败次数，停止事件之后重新计数
+        let long_run = vec![
+            event(SupervisorEventKind::Crashed, 3, at(BACKOFF_RESET_SECS + 10)),
+            event(SupervisorEventKind::Restarted, 3, at(5)),
+            event(SupervisorEventKind::Crashed, 2, at(4)),
+        ];
+        let supervisor = Supervisor::default();
+        supervisor.restore(&rules[0], &long_run);
+        let status = &supervisor.status(&rules)[0];
+        assert_eq!((status.state, status.restart_count, status.consecutive_failures), (SupervisedState::Pending, 3, 1));
+
+        let mut stopped = long_run.clone();
+        stopped.insert(0, event(SupervisorEventKind::Stopped, 3, at(BACKOFF_RESET_SECS + 20)));
+        let supervisor = Supervisor::default();
+        supervisor.restore(&rules[0], &stopped);
+        let status = &supervisor.status(&rules)[0];
+        assert_eq!((status.restart_count, status.consecutive_failures), (0, 0));
+    }
+}
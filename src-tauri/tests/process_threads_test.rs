@@ -0,0 +1,85 @@
+//! 进程线程测试
+//! 覆盖线程状态解析、两次采样计算 CPU 使用率，以及读取当前进程的真实线程
+
+use std::time::Duration;
+
+use tauri_react_ai_lib::{build_thread_list, ProcStat, TaskState};
+
+fn task(tid: u32, name: &str, state: char, user_ticks: u64, system_ticks: u64) -> ProcStat {
+    let line = format!(
+        "{} ({}) {} 1 1 1 0 -1 0 0 0 0 0 {} {} 0 0 20 0 1 0 100 0 0",
+        tid, name, state, user_ticks, system_ticks
+    );
+    ProcStat::parse(&line).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_task_state() {
+        assert_eq!(TaskState::from_code('R'), TaskState::Running);
+        assert_eq!(TaskState::from_code('D'), TaskState::DiskSleep);
+        assert_eq!(TaskState::from_code('t'), TaskState::TracingStop);
+        assert_eq!(TaskState::from_code('?'), TaskState::Unknown);
+        assert_eq!(serde_json::to_string(&TaskState::DiskSleep).unwrap(), "\"disk_sleep\"");
+    }
+
+    #[test]
+    fn test_build_thread_list() {
+        let previous = vec![task(100, "main", 'S', 80, 20), task(101, "worker", 'R', 300, 0), task(102, "gone", 'S', 5, 5)];
+        let current = vec![
+            task(100, "main", 'S', 80, 21),
+            task(101, "worker", 'R', 380, 10),
+            // 采样间隔内新建的线程
+            task(103, "fresh", 'R', 15, 5),
+        ];
+        let threads = build_thread_list(&previous, &current, Duration::from_secs(1), 100);
+
+        // 已退出的线程不出现，按使用率排序
+        let order: Vec<u32> = threads.iter().map(|thread| thread.tid).collect();
+        assert_eq!(order, vec![101, 103, 100]);
+        assert_eq!(threads[0].name, "worker");
+        assert_eq!(threads[0].state, TaskState::Running);
+        assert!((threads[0].cpu_usage_percent - 90.0).abs() < 0.01);
+        assert!((threads[0].cpu_time_secs - 3.9).abs() < 1e-9);
+        assert!((threads[0].system_time_secs - 0.1).abs() < 1e-9);
+        assert!((threads[1].cpu_usage_percent - 20.0).abs() < 0.01);
+        assert!((threads[2].cpu_usage_percent - 1.0).abs() < 0.01);
+
+        // 间隔为零时不计算使用率
+        let threads = build_thread_list(&previous, &current, Duration::ZERO, 100);
+        assert!(threads.iter().all(|thread| thread.cpu_usage_percent == 0.0));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_list_own_threads() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        use tauri_react_ai_lib::{list_threads, thread_count};
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = stop.clone();
+        let spinner = std::thread::Builder::new()
+            .name("spinner".to_string())
+            .spawn(move || {
+                while !flag.load(Ordering::Relaxed) {
+                    std::hint::spin_loop();
+                }
+            })
+            .unwrap();
+
+        let pid = std::process::id();
+        let threads = list_threads(pid, Duration::from_millis(300)).unwrap();
+        stop.store(true, Ordering::Relaxed);
+        spinner.join().unwrap();
+
+        assert!(threads.thread_count >= 2);
+        assert!(thread_count(pid) >= 1);
+        let spinning = threads.threads.iter().find(|thread| thread.name == "spinner").unwrap();
+        assert!(spinning.cpu_usage_percent > 20.0, "{:?}", spinning);
+        assert!(list_threads(u32::MAX, Duration::from_millis(100)).unwrap_err().contains("not found"));
+    }
+}
@@ -0,0 +1,113 @@
+//! 进程树测试
+//! 覆盖子树合计、保留祖先链的过滤，以及祖先与后代的查询
+
+use tauri_react_ai_lib::{build_process_tree, process_lineage, ProcessEntry, ProcessTreeFilter, ProcessTreeNode};
+
+fn entry(pid: u32, parent_pid: Option<u32>, name: &str, cpu_usage_percent: f32, memory_mb: u64) -> ProcessEntry {
+    ProcessEntry {
+        pid,
+        parent_pid,
+        name: name.to_string(),
+        command_line: format!("/usr/bin/{}", name),
+        user: Some("dev".to_string()),
+        user_id: Some("1000".to_string()),
+        cpu_usage_percent,
+        memory_usage_bytes: memory_mb * 1024 * 1024,
+    }
+}
+
+// systemd ─┬─ sshd ── bash ── cargo ─┬─ rustc
+//          │                         └─ rustc
+//          └─ nginx ── nginx
+fn processes() -> Vec<ProcessEntry> {
+    vec![
+        entry(1, None, "systemd", 0.5, 10),
+        entry(200, Some(1), "sshd", 0.0, 5),
+        entry(300, Some(200), "bash", 0.1, 4),
+        entry(400, Some(300), "cargo", 2.0, 50),
+        entry(401, Some(400), "rustc", 90.0, 800),
+        entry(402, Some(400), "rustc", 60.0, 600),
+        entry(500, Some(1), "nginx", 1.0, 20),
+        entry(501, Some(500), "nginx", 3.0, 30),
+        // 父进程已退出
+        entry(900, Some(899), "orphan", 0.0, 1),
+    ]
+}
+
+fn find(nodes: &[ProcessTreeNode], pid: u32) -> Option<&ProcessTreeNode> {
+    nodes.iter().find_map(|node| {
+        if node.process.pid == pid {
+            Some(node)
+        } else {
+            find(&node.children, pid)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subtree_totals() {
+        let tree = build_process_tree(&processes(), None);
+        let roots: Vec<u32> = tree.iter().map(|node| node.process.pid).collect();
+        assert_eq!(roots, vec![1, 900]);
+
+        let cargo = find(&tree, 400).unwrap();
+        assert_eq!(cargo.descendant_count, 2);
+        assert_eq!(cargo.subtree_cpu_usage_percent, 152.0);
+        assert_eq!(cargo.subtree_memory_usage_bytes, 1450 * 1024 * 1024);
+
+        let root = &tree[0];
+        assert_eq!(root.descendant_count, 7);
+        assert_eq!(root.children.iter().map(|node| node.process.pid).collect::<Vec<_>>(), vec![200, 500]);
+        assert!(root.matched);
+    }
+
+    #[test]
+    fn test_filter_keeps_ancestors() {
+        let filter = ProcessTreeFilter {
+            name: Some("RUSTC".to_string()),
+            ..Default::default()
+        };
+        let tree = build_process_tree(&processes(), Some(&filter));
+        assert_eq!(tree.len(), 1);
+
+        // nginx 分支被去掉，rustc 的祖先链保留但不标记为匹配
+        let systemd = &tree[0];
+        assert!(!systemd.matched);
+        assert_eq!(systemd.children.len(), 1);
+        assert!(find(&tree, 500).is_none());
+        let cargo = find(&tree, 400).unwrap();
+        assert!(!cargo.matched);
+        assert!(cargo.children.iter().all(|node| node.matched));
+        // 合计不受过滤影响
+        assert_eq!(systemd.descendant_count, 7);
+
+        let busy = ProcessTreeFilter {
+            min_cpu_usage: Some(70.0),
+            ..Default::default()
+        };
+        let tree = build_process_tree(&processes(), Some(&busy));
+        assert_eq!(find(&tree, 400).unwrap().children.len(), 1);
+        assert!(find(&tree, 402).is_none());
+    }
+
+    #[test]
+    fn test_lineage() {
+        let lineage = process_lineage(&processes(), 400).unwrap();
+        let ancestors: Vec<u32> = lineage.ancestors.iter().map(|entry| entry.pid).collect();
+        assert_eq!(ancestors, vec![300, 200, 1]);
+        assert_eq!(lineage.subtree.children.len(), 2);
+
+        let orphan = process_lineage(&processes(), 900).unwrap();
+        assert!(orphan.ancestors.is_empty());
+        assert!(process_lineage(&processes(), 12345).is_none());
+
+        // PID 复用造成的环不会导致死循环
+        let cycle = vec![entry(10, Some(11), "a", 0.0, 1), entry(11, Some(10), "b", 0.0, 1)];
+        assert_eq!(build_process_tree(&cycle, None).len(), 1);
+        assert_eq!(process_lineage(&cycle, 10).unwrap().ancestors.len(), 1);
+    }
+}
@@ -0,0 +1,72 @@
+//! 进程用户测试
+//! 覆盖 uid、gid 到名称的解析、按 uid 或用户名过滤，以及系统用户列表
+
+use tauri_react_ai_lib::{user_matches, ProcessEntry, ProcessTreeFilter, UserDirectory};
+
+fn directory() -> UserDirectory {
+    UserDirectory::from_entries(
+        vec![("0".to_string(), "root".to_string()), ("1000".to_string(), "alice".to_string())],
+        vec![("0".to_string(), "root".to_string()), ("27".to_string(), "sudo".to_string())],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_lookup() {
+        let directory = directory();
+        assert_eq!(directory.user_name("1000"), Some("alice"));
+        assert_eq!(directory.user_name("1001"), None);
+        assert_eq!(directory.group_name("27"), Some("sudo"));
+        assert_eq!(directory.group_name("1000"), None);
+    }
+
+    #[test]
+    fn test_user_matches() {
+        assert!(user_matches("1000", Some("1000"), Some("alice")));
+        assert!(user_matches("Ali", Some("1000"), Some("alice")));
+        assert!(!user_matches(" alice ", Some("1000"), None));
+        // uid 需要完全相同，避免 "0" 匹配到 "1000"
+        assert!(!user_matches("0", Some("1000"), Some("alice")));
+        // 无法解析用户名时仍可按 uid 过滤
+        assert!(user_matches("1001", Some("1001"), None));
+
+        let entry = ProcessEntry {
+            pid: 42,
+            parent_pid: None,
+            name: "worker".to_string(),
+            command_line: "worker".to_string(),
+            user: Some("alice".to_string()),
+            user_id: Some("1000".to_string()),
+            cpu_usage_percent: 0.0,
+            memory_usage_bytes: 0,
+        };
+        for user in ["alice", "1000"] {
+            let filter = ProcessTreeFilter {
+                user: Some(user.to_string()),
+                ..Default::default()
+            };
+            assert!(filter.matches(&entry), "{}", user);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_own_process() {
+        let pid = std::process::id();
+        let snapshot = ProcessEntry::snapshot();
+        let own = snapshot.iter().find(|entry| entry.pid == pid).unwrap();
+        let uid = own.user_id.clone().unwrap();
+        assert_eq!(uid, unsafe { libc::getuid() }.to_string());
+
+        // 有对应用户时显示用户名，否则退回 uid
+        let name = UserDirectory::cached().user_name(&uid).map(str::to_string);
+        assert_eq!(own.user.clone(), Some(name.clone().unwrap_or(uid.clone())));
+        if let Some(name) = name {
+            let users = tauri_react_ai_lib::list_users();
+            assert!(users.iter().any(|user| user.uid == uid && user.name == name));
+        }
+    }
+}
@@ -144,6 +144,27 @@ const AlertSystem: React.FC = () => {
     }
   }, [fetchAlertConfigurations]);
 
+  // 测试触发警报规则，测试警报不写入警报历史
+  const testFireAlert = useCallback(async (id: string) => {
+    setIsLoading(true);
+    setError(null);
+    setSuccess(null);
+    
+    try {
+      const plan = await SystemMonitorAPI.testFireAlert(id);
+      const delivery = plan.deliveries[0];
+      if (delivery) {
+        setSuccess(`[测试] ${delivery.alert.message}（${delivery.channels.join('、')}）`);
+      } else {
+        setSuccess(`测试警报未立即发送：${plan.batched} 条放入摘要，${plan.overflowed} 条超出频率限制`);
+      }
+    } catch (err) {
+      setError(err instanceof Error ? err.message : '测试触发警报失败');
+    } finally {
+      setIsLoading(false);
+    }
+  }, []);
+
   // 编辑警报配置
   const editAlertConfiguration = useCallback((config: AlertConfiguration) => {
     setFormData(config);
@@ -199,6 +220,9 @@ const AlertSystem: React.FC = () => {
       case AlertMetric.MemoryUsage: return '内存使用率';
       case AlertMetric.DiskUsage: return '磁盘使用率';
       case AlertMetric.NetworkTraffic: return '网络流量';
+      case AlertMetric.SystemLoad: return '系统负载';
+      case AlertMetric.SwapUsage: return '交换分区使用率';
+      case AlertMetric.OpenFds: return '进程打开的文件描述符数';
       default: return '未知';
     }
   };
@@ -207,8 +231,11 @@ const AlertSystem: React.FC = () => {
   const formatCondition = (condition: AlertCondition): string => {
     switch (condition) {
       case AlertCondition.GreaterThan: return '大于';
+      case AlertCondition.GreaterOrEqual: return '大于等于';
       case AlertCondition.LessThan: return '小于';
+      case AlertCondition.LessOrEqual: return '小于等于';
       case AlertCondition.Equals: return '等于';
+      case AlertCondition.NotEquals: return '不等于';
       default: return '未知';
     }
   };
@@ -459,6 +486,13 @@ const AlertSystem: React.FC = () => {
                               >
                                 编辑
                               </Button>
+                              <Button
+                                variant="outline"
+                                size="sm"
+                                onClick={() => testFireAlert(config.id)}
+                              >
+                                测试
+                              </Button>
                               <Button
                                 variant="outline"
                                 size="sm"
@@ -182,6 +182,9 @@ const ProcessManager: React.FC = () => {
                   <SelectItem value="cpu">CPU使用率</SelectItem>
                   <SelectItem value="memory">内存使用量</SelectItem>
                   <SelectItem value="pid">PID</SelectItem>
+                  <SelectItem value="disk_io">磁盘I/O</SelectItem>
+                  <SelectItem value="disk_read">磁盘读取</SelectItem>
+                  <SelectItem value="disk_write">磁盘写入</SelectItem>
                 </SelectContent>
               </Select>
             </div>
@@ -278,6 +281,7 @@ const ProcessManager: React.FC = () => {
                   <TableHead>名称</TableHead>
                   <TableHead>CPU使用率</TableHead>
                   <TableHead>内存使用量</TableHead>
+                  <TableHead>磁盘读/写</TableHead>
                   <TableHead>线程数</TableHead>
                   <TableHead>状态</TableHead>
                   <TableHead>用户</TableHead>
@@ -291,6 +295,9 @@ const ProcessManager: React.FC = () => {
                     <TableCell>{process.name}</TableCell>
                     <TableCell>{process.cpu_usage_percent.toFixed(1)}%</TableCell>
                     <TableCell>{formatBytes(process.memory_usage_bytes)}</TableCell>
+                    <TableCell>
+                      {formatBytes(Math.round(process.disk_read_bytes_per_sec))}/s / {formatBytes(Math.round(process.disk_written_bytes_per_sec))}/s
+                    </TableCell>
                     <TableCell>{process.thread_count}</TableCell>
                     <TableCell>
                       <Badge variant={
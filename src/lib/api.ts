@@ -125,6 +125,189 @@ export interface AlertHistory {
   acknowledged: boolean;
   acknowledged_at?: string;
   acknowledged_by?: string;
+  // 触发时处于静默期
+  suppressed?: boolean;
+  silence_id?: string;
+  // 当前严重程度及升级级别
+  severity?: AlertSeverity;
+  escalation_level?: number;
+  // 规则恢复正常的时间
+  resolved_at?: string;
+  // 所属事件
+  incident_id?: string;
+}
+
+// 警报历史过滤条件
+export interface AlertHistoryFilter {
+  rule_ids?: string[];
+  severities?: AlertSeverity[];
+  targets?: string[];
+  start_time?: string;
+  end_time?: string;
+  acknowledged?: boolean;
+  suppressed?: boolean;
+  incident_ids?: string[];
+}
+
+// 通知频率限制
+export interface RateLimit {
+  max_notifications: number;
+  period_secs: number;
+}
+
+// 通知策略：频率限制与摘要模式
+export interface NotificationPolicy {
+  global_limit?: RateLimit;
+  channel_limits?: Record<string, RateLimit>;
+  digest?: {
+    interval_secs?: number;
+    // 为空时所有通知方式都使用摘要
+    channels?: string[];
+  };
+}
+
+// 摘要中单条规则的汇总
+export interface DigestRuleSummary {
+  rule_id: string;
+  count: number;
+  worst_value: number;
+  worst_alert_id: string;
+  severity?: AlertSeverity;
+}
+
+// 通知摘要
+export interface NotificationDigest {
+  id: string;
+  channel: string;
+  created_at: string;
+  period_start: string;
+  period_end: string;
+  alert_count: number;
+  overflow_count: number;
+  rules: DigestRuleSummary[];
+  alert_ids: string[];
+  message: string;
+}
+
+// 本次需要发送的通知和摘要
+export interface NotificationPlan {
+  // test 为测试警报，发送时应标明为测试
+  deliveries: { alert: AlertHistory; channels: string[]; test?: boolean }[];
+  digests: NotificationDigest[];
+  batched: number;
+  overflowed: number;
+}
+
+// 警报分组方式
+export interface IncidentGrouping {
+  window_secs?: number;
+  group_by_target?: boolean;
+}
+
+export type IncidentStatus = 'open' | 'acknowledged' | 'resolved';
+
+// 事件时间线条目
+export interface IncidentEvent {
+  id: string;
+  incident_id: string;
+  kind: 'opened' | 'alert_added' | 'acknowledged' | 'resolved' | 'note';
+  message: string;
+  author?: string;
+  alert_history_id?: string;
+  created_at: string;
+}
+
+// 由相关警报组成的事件
+export interface Incident {
+  id: string;
+  title: string;
+  status: IncidentStatus;
+  severity: AlertSeverity;
+  opened_at: string;
+  updated_at: string;
+  last_alert_at: string;
+  acknowledged_at?: string;
+  acknowledged_by?: string;
+  resolved_at?: string;
+  alert_count: number;
+  rule_ids: string[];
+  targets: string[];
+}
+
+// 事件详情
+export interface IncidentDetail {
+  incident: Incident;
+  alerts: AlertHistory[];
+  timeline: IncidentEvent[];
+}
+
+// 单条规则的警报统计
+export interface AlertRuleStatistics {
+  rule_id: string;
+  firing_count: number;
+  acknowledged_count: number;
+  suppressed_count: number;
+  mean_time_to_acknowledge_secs?: number;
+  mean_time_to_resolve_secs?: number;
+  last_triggered_at?: string;
+}
+
+// 警报统计
+export interface AlertStatistics {
+  total: number;
+  acknowledged: number;
+  unacknowledged: number;
+  suppressed: number;
+  resolved: number;
+  mean_time_to_acknowledge_secs?: number;
+  mean_time_to_resolve_secs?: number;
+  rules: AlertRuleStatistics[];
+  noisiest_rules: AlertRuleStatistics[];
+}
+
+// 已存储警报配置中的问题
+export interface AlertConfigurationIssue {
+  id: string;
+  field: string;
+  value: string;
+  message: string;
+  action: AlertIssueAction;
+}
+
+// 已修复、已禁用，或仅报告未修改
+export type AlertIssueAction = 'repaired' | 'disabled' | 'reported';
+
+export interface AlertMigrationReport {
+  checked: number;
+  repaired: number;
+  disabled: number;
+  issues: AlertConfigurationIssue[];
+}
+
+// 警报规则导入导出
+export type RuleFileFormat = 'json' | 'yaml';
+export type ImportConflictMode = 'skip' | 'overwrite' | 'rename';
+
+export interface AlertImportIssue {
+  id: string;
+  message: string;
+}
+
+export interface UnmappedPrometheusRule {
+  group: string;
+  name: string;
+  expr: string;
+  reason: string;
+}
+
+export interface AlertImportReport {
+  imported: string[];
+  overwritten: string[];
+  renamed: { original_id: string; new_id: string }[];
+  skipped: string[];
+  failed: AlertImportIssue[];
+  warnings: AlertImportIssue[];
+  unmapped: UnmappedPrometheusRule[];
 }
 
 // 音频设备类型
@@ -158,6 +341,7 @@ export interface HistoricalSystemData {
   disk_usage: DiskUsageData[];
   network_traffic: NetworkTrafficData;
   system_load: number;
+  open_fds?: number;
 }
 
 // 磁盘使用数据接口
@@ -185,6 +369,150 @@ export interface AlertConfiguration {
   severity: AlertSeverity;
   enabled: boolean;
   notification_methods: NotificationMethod[];
+  // 组合条件表达式，例如 "cpu > 90 AND load > cores * 2"
+  expression?: string;
+  // 目标标签，例如挂载点或服务名
+  target?: string;
+  // 未确认时使用的升级策略
+  escalation_policy_id?: string;
+  // 异常检测规则，设置后按偏离基线的程度触发
+  anomaly?: AnomalyDetection;
+  // 耗尽预测条件，例如剩余时间少于 24 小时时触发
+  forecast?: ForecastCondition;
+  // 自定义消息模板，例如 "[{hostname}] {metric} = {value}"，未设置时使用默认模板
+  message_template?: string;
+  // 触发时自动执行的修复动作
+  remediation?: RemediationPolicy;
+}
+
+// 修复动作
+export type RemediationAction =
+  | { type: 'terminate_process'; process_name: string; force?: boolean }
+  | { type: 'run_script'; path: string; args?: string[]; timeout_secs?: number }
+  | { type: 'webhook'; url: string; timeout_secs?: number };
+
+// 修复策略
+export interface RemediationPolicy {
+  actions: RemediationAction[];
+  dry_run?: boolean;
+  max_runs_per_hour?: number;
+  cooldown_secs?: number;
+}
+
+// 修复动作执行记录
+export interface RemediationResult {
+  id: string;
+  alert_history_id: string;
+  rule_id: string;
+  action_index?: number;
+  action?: string;
+  status: 'running' | 'succeeded' | 'failed' | 'skipped' | 'dry_run' | 'rate_limited';
+  output: string;
+  executed_at: string;
+  finished_at?: string;
+}
+
+// 回测中的一次触发过程
+export interface AlertFiringEpisode {
+  fired_at: string;
+  resolved_at?: string;
+  duration_secs: number;
+  firing_samples: number;
+  min_value: number;
+  max_value: number;
+  details: string;
+}
+
+// 警报规则回测结果
+export interface AlertBacktestReport {
+  start_time: string;
+  end_time: string;
+  evaluated_samples: number;
+  firing_samples: number;
+  unknown_samples: number;
+  total_firing_secs: number;
+  episodes: AlertFiringEpisode[];
+}
+
+// 警报消息语言
+export type MessageLocale = 'en' | 'zh';
+
+// 触发时占用资源最多的进程
+export interface ProcessSample {
+  pid: number;
+  name: string;
+  cpu_usage: number;
+  memory_bytes: number;
+}
+
+// 预览消息模板使用的上下文
+export interface AlertMessageContext {
+  rule_id: string;
+  severity: string;
+  metric: string;
+  condition?: string;
+  value: number;
+  threshold?: number;
+  target?: string;
+  hostname?: string;
+  duration_secs?: number;
+  top_processes?: ProcessSample[];
+  details?: string;
+  triggered_at: string;
+}
+
+// 耗尽预测条件
+export interface ForecastCondition {
+  lookback_hours?: number;
+  // 视为耗尽的使用率，默认 100
+  threshold?: number;
+  within_hours: number;
+}
+
+// 资源耗尽预测结果
+export interface ResourceForecast {
+  metric: AlertMetric;
+  target?: string;
+  current: number;
+  threshold: number;
+  lookback_hours: number;
+  trend: {
+    slope_per_hour: number;
+    slope_std_error: number;
+    fitted_now: number;
+    r_squared: number;
+    samples: number;
+  };
+  hours_to_threshold?: number;
+  earliest_hours?: number;
+  latest_hours?: number;
+  estimated_at?: string;
+}
+
+// 异常检测规则
+export interface AnomalyDetection {
+  method: 'ewma' | 'seasonal';
+  // 允许偏离的标准差倍数，默认 3
+  sensitivity?: number;
+  direction?: 'above' | 'below' | 'both';
+  lookback_hours?: number;
+  ewma_alpha?: number;
+}
+
+// 异常判定结果
+export interface AnomalyVerdict {
+  value: number;
+  expected: number;
+  lower: number;
+  upper: number;
+  z_score: number;
+  is_anomaly: boolean;
+}
+
+// 历史数据中的异常点
+export interface AnomalyPoint {
+  timestamp: string;
+  verdict: AnomalyVerdict;
 }
 
 // 警报指标枚举
@@ -192,14 +520,21 @@ export enum AlertMetric {
   CpuUsage = 'cpu_usage',
   MemoryUsage = 'memory_usage',
   DiskUsage = 'disk_usage',
-  NetworkTraffic = 'network_traffic'
+  NetworkTraffic = 'network_traffic',
+  SystemLoad = 'system_load',
+  SwapUsage = 'swap_usage',
+  // 单个进程持有的最大文件描述符数量
+  OpenFds = 'open_fds'
 }
 
 // 警报条件枚举
 export enum AlertCondition {
   GreaterThan = 'greater_than',
+  GreaterOrEqual = 'greater_or_equal',
   LessThan = 'less_than',
-  Equals = 'equals'
+  LessOrEqual = 'less_or_equal',
+  Equals = 'equals',
+  NotEquals = 'not_equals'
 }
 
 // 警报严重程度枚举
@@ -245,6 +580,15 @@ export interface ProcessInfo {
   user?: string;
   parent_pid?: string;
   working_directory?: string;
+  disk_read_bytes: number;
+  disk_written_bytes: number;
+  disk_read_bytes_per_sec: number;
+  disk_written_bytes_per_sec: number;
+  // 所属的 cgroup、systemd 单元和容器
+  cgroup_path?: string;
+  systemd_unit?: string;
+  container_id?: string;
+  container_runtime?: ContainerRuntime;
 }
 
 // 进程详细信息类型
@@ -257,24 +601,440 @@ export interface ProcessDetails {
   cpu_usage_percent: number;
   memory_usage_bytes: number;
   thread_count: number;
+  // nice 值
   priority: number;
+  scheduling_policy?: SchedulingPolicy;
   status: string;
   start_time?: string;
+  // 用户名，无法解析时为 uid
   user?: string;
+  user_id?: string;
+  group?: string;
+  group_id?: string;
   parent_pid?: string;
   working_directory?: string;
+  cgroup_path?: string;
+  systemd_unit?: string;
+  container_id?: string;
+  container_runtime?: ContainerRuntime;
 }
 
 // 进程排序选项类型
 export interface ProcessSortOptions {
+  // name、cpu、memory、pid，或按磁盘速率排序的 disk_read、disk_write、disk_io
   sort_by: string;
   sort_order: string; // "asc" or "desc"
   filter_name?: string;
+  // uid 或用户名
   filter_user?: string;
+  // systemd 单元名的一部分
+  filter_unit?: string;
+  // 容器 ID 或其前缀
+  filter_container?: string;
   min_cpu_usage?: number;
   max_memory_usage?: number;
 }
 
+// 进程快照中的一条记录
+export interface ProcessEntry {
+  pid: number;
+  parent_pid?: number;
+  name: string;
+  command_line: string;
+  // 用户名，无法解析时为 uid
+  user?: string;
+  user_id?: string;
+  cpu_usage_percent: number;
+  memory_usage_bytes: number;
+}
+
+// cpu.stat 中的累计值，单位为微秒
+export interface CgroupCpuStat {
+  usage_usec: number;
+  user_usec: number;
+  system_usec: number;
+  nr_periods: number;
+  nr_throttled: number;
+  throttled_usec: number;
+}
+
+// io.stat 中一个块设备的累计值
+export interface CgroupIoDevice {
+  // "major:minor"
+  device: string;
+  read_bytes: number;
+  written_bytes: number;
+  read_ios: number;
+  write_ios: number;
+}
+
+// cgroup v2 中一个 cgroup 的资源用量
+export interface CgroupStats {
+  // 相对于层级根的路径，根为 "/"
+  path: string;
+  name: string;
+  depth: number;
+  memory_current_bytes?: number;
+  // 为空表示不限制
+  memory_max_bytes?: number;
+  cpu?: CgroupCpuStat;
+  io_read_bytes: number;
+  io_written_bytes: number;
+  io_devices: CgroupIoDevice[];
+  pids_current?: number;
+  pids_max?: number;
+  child_count: number;
+}
+
+// 浏览时的一个节点：当前 cgroup 及其直接子 cgroup
+export interface CgroupNode {
+  cgroup: CgroupStats;
+  children: CgroupStats[];
+}
+
+// 历史数据中的一个 cgroup 采样，CPU 与 I/O 为累计值
+export interface CgroupSample {
+  timestamp: string;
+  path: string;
+  memory_current_bytes?: number;
+  memory_max_bytes?: number;
+  cpu_usage_usec?: number;
+  cpu_nr_periods?: number;
+  cpu_nr_throttled?: number;
+  cpu_throttled_usec?: number;
+  io_read_bytes: number;
+  io_written_bytes: number;
+  pids_current?: number;
+}
+
+// 由守护进程启动的命令，不经过 shell
+export interface LaunchCommand {
+  // 绝对路径
+  program: string;
+  args?: string[];
+  working_directory?: string;
+}
+
+// 守护的对象：保持命令运行，或按名称监视进程
+export type SupervisorTarget =
+  | ({ type: 'command' } & LaunchCommand)
+  | { type: 'process'; process_name: string; restart_command?: LaunchCommand };
+
+export interface SupervisorRule {
+  id?: string;
+  name: string;
+  target: SupervisorTarget;
+  enabled?: boolean;
+  // 第一次重启前等待的秒数，之后每次连续失败翻倍
+  initial_backoff_secs?: number;
+  max_backoff_secs?: number;
+  // 累计重启次数上限，为空时不限制
+  max_restarts?: number;
+}
+
+export type SupervisorEventKind =
+  | 'started'
+  | 'restarted'
+  | 'exited'
+  | 'crashed'
+  | 'recovered'
+  | 'restart_failed'
+  | 'gave_up'
+  | 'stopped';
+
+export interface SupervisorEvent {
+  id: string;
+  rule_id: string;
+  rule_name: string;
+  kind: SupervisorEventKind;
+  pid?: number;
+  // 仅由应用启动的进程有退出码
+  exit_code?: number;
+  signal?: number;
+  message?: string;
+  restart_count: number;
+  created_at: string;
+}
+
+export type SupervisedState = 'pending' | 'running' | 'backoff' | 'stopped' | 'failed' | 'disabled' | 'invalid';
+
+export interface SupervisorStatus {
+  rule_id: string;
+  rule_name: string;
+  state: SupervisedState;
+  pid?: number;
+  // 进程由应用启动
+  launched: boolean;
+  restart_count: number;
+  consecutive_failures: number;
+  last_exit_code?: number;
+  running_since?: string;
+  next_restart_at?: string;
+  // 规则无法解析时的原因
+  error?: string;
+}
+
+export type ContainerRuntime = 'docker' | 'podman' | 'containerd' | 'crio' | 'kubernetes';
+
+export type CgroupGrouping = 'unit' | 'container';
+
+// 一个单元或容器中所有进程的合计
+export interface CgroupUsage {
+  // 单元名或容器 ID
+  key: string;
+  container_runtime?: ContainerRuntime;
+  process_count: number;
+  cpu_usage_percent: number;
+  memory_usage_bytes: number;
+  pids: number[];
+}
+
+// 内存用量，单位均为字节
+export interface MemoryUsage {
+  rss_bytes: number;
+  // 按共享进程数分摊后的用量
+  pss_bytes: number;
+  // 仅本进程使用的内存
+  uss_bytes: number;
+  swap_bytes: number;
+  swap_pss_bytes: number;
+  shared_clean_bytes: number;
+  shared_dirty_bytes: number;
+  private_clean_bytes: number;
+  private_dirty_bytes: number;
+  anonymous_bytes: number;
+}
+
+export type MappingKind = 'file' | 'heap' | 'stack' | 'anonymous' | 'special';
+
+// 映射同一文件（或同为堆、栈、匿名内存）的映射的合计
+export interface MappingGroup {
+  // 文件路径或 [heap]、[stack]、[anon] 等名称
+  backing: string;
+  kind: MappingKind;
+  mapping_count: number;
+  size_bytes: number;
+  usage: MemoryUsage;
+}
+
+export interface ProcessMemoryMap {
+  pid: number;
+  summary: MemoryUsage;
+  // 按 PSS 从高到低排列
+  groups: MappingGroup[];
+}
+
+// 敏感环境变量的隐藏规则
+export interface EnvMasking {
+  enabled?: boolean;
+  // 键名包含其中任一片段（不区分大小写）时隐藏值
+  patterns?: string[];
+}
+
+export interface EnvVariable {
+  key: string;
+  // 被隐藏时为 "********"
+  value: string;
+  masked: boolean;
+}
+
+// 进程启动时的环境变量
+export interface ProcessEnvironment {
+  pid: number;
+  variables: EnvVariable[];
+  masked_count: number;
+}
+
+export type ResourceLimitKind =
+  | 'cpu' | 'fsize' | 'data' | 'stack' | 'core' | 'rss' | 'nproc' | 'nofile'
+  | 'memlock' | 'as' | 'locks' | 'sigpending' | 'msgqueue' | 'nice' | 'rtprio' | 'rttime';
+
+// 一项资源限制，null 表示不限制
+export interface ResourceLimit {
+  kind: ResourceLimitKind;
+  soft_limit: number | null;
+  hard_limit: number | null;
+  units?: string;
+}
+
+export interface ProcessLimits {
+  pid: number;
+  limits: ResourceLimit[];
+}
+
+// 系统用户
+export interface UserInfo {
+  uid: string;
+  name: string;
+  primary_group_id: string;
+  primary_group?: string;
+  groups: string[];
+}
+
+// 进程树过滤条件
+export interface ProcessTreeFilter {
+  name?: string;
+  user?: string;
+  min_cpu_usage?: number;
+  min_memory_usage?: number;
+}
+
+// 进程树节点，合计值包含所有后代进程
+export interface ProcessTreeNode extends ProcessEntry {
+  subtree_cpu_usage_percent: number;
+  subtree_memory_usage_bytes: number;
+  descendant_count: number;
+  // 为保留祖先链而加入的进程为 false
+  matched: boolean;
+  children: ProcessTreeNode[];
+}
+
+// 进程的祖先链与后代子树
+export interface ProcessLineage {
+  ancestors: ProcessEntry[];
+  subtree: ProcessTreeNode;
+}
+
+export type ProcessSignal =
+  | 'SIGTERM'
+  | 'SIGKILL'
+  | 'SIGINT'
+  | 'SIGHUP'
+  | 'SIGSTOP'
+  | 'SIGCONT'
+  | 'SIGUSR1'
+  | 'SIGUSR2';
+
+// 信号作用范围：单个进程、进程及其后代、整个进程组
+export type SignalScope = 'process' | 'tree' | 'group';
+
+export type SignalStatus =
+  | 'delivered'
+  | 'terminated'
+  | 'killed'
+  | 'not_found'
+  | 'permission_denied'
+  | 'unsupported'
+  | 'failed';
+
+// 进程磁盘 I/O，速率为采样间隔内的每秒字节数
+export interface ProcessIo {
+  pid: number;
+  name: string;
+  read_bytes: number;
+  written_bytes: number;
+  read_bytes_per_sec: number;
+  written_bytes_per_sec: number;
+}
+
+export type FdKind = 'file' | 'socket' | 'pipe' | 'anon_inode' | 'deleted' | 'other';
+
+export interface OpenFd {
+  fd: number;
+  kind: FdKind;
+  target: string;
+  size_bytes?: number;
+}
+
+// 进程打开的文件描述符
+export interface ProcessFds {
+  pid: number;
+  fd_count: number;
+  counts_by_kind: Partial<Record<FdKind, number>>;
+  fds: OpenFd[];
+}
+
+export interface FdHolder {
+  pid: number;
+  process_name: string;
+  fd: number;
+  kind: FdKind;
+  target: string;
+}
+
+// 打开了某个路径的进程；inaccessible_processes 不为 0 时结果可能不完整
+export interface FileHolders {
+  path: string;
+  holders: FdHolder[];
+  inaccessible_processes: number;
+}
+
+export interface FdUsage {
+  pid: number;
+  process_name: string;
+  fd_count: number;
+}
+
+export type TaskState =
+  | 'running'
+  | 'sleeping'
+  | 'disk_sleep'
+  | 'stopped'
+  | 'tracing_stop'
+  | 'zombie'
+  | 'dead'
+  | 'idle'
+  | 'unknown';
+
+export interface ThreadInfo {
+  tid: number;
+  name: string;
+  state: TaskState;
+  cpu_time_secs: number;
+  user_time_secs: number;
+  system_time_secs: number;
+  // 采样间隔内的使用率，100 表示占满一个核心
+  cpu_usage_percent: number;
+}
+
+// 进程的线程列表，按 CPU 使用率从高到低排列
+export interface ProcessThreads {
+  pid: number;
+  thread_count: number;
+  threads: ThreadInfo[];
+  sample_interval_ms: number;
+}
+
+export type SchedulingPolicy = 'other' | 'fifo' | 'round_robin' | 'batch' | 'idle' | 'deadline';
+
+export interface ProcessPriority {
+  pid: number;
+  nice: number;
+  scheduling_policy?: SchedulingPolicy;
+  realtime_priority: number;
+  // 进程属于其他用户，只能读取不能修改
+  permission_denied: boolean;
+}
+
+export interface CpuAffinity {
+  pid: number;
+  cpus: number[];
+  // 例如 "0-3,6"
+  cpu_list: string;
+}
+
+// 进程操作审计记录
+export interface ProcessAuditEntry {
+  id: string;
+  pid: number;
+  process_name?: string;
+  action: 'renice' | 'set_affinity' | 'set_limit';
+  previous_value?: string;
+  requested_value: string;
+  success: boolean;
+  error?: string;
+  created_at: string;
+}
+
+// 单个进程的信号发送结果
+export interface SignalOutcome {
+  pid: number;
+  name?: string;
+  signal: ProcessSignal;
+  status: SignalStatus;
+  error?: string;
+}
+
 // API服务类
 export class SystemMonitorAPI {
   // 基本问候命令
@@ -352,11 +1112,164 @@ export class SystemMonitorAPI {
     return await invoke('get_process_details', { pid });
   }
 
+  // 获取进程树
+  static async getProcessTree(filter?: ProcessTreeFilter): Promise<ProcessTreeNode[]> {
+    return await invoke('get_process_tree', { filter });
+  }
+
+  // 获取进程的祖先与后代
+  static async getProcessLineage(pid: string): Promise<ProcessLineage> {
+    return await invoke('get_process_lineage', { pid });
+  }
+
   // 终止进程
   static async terminateProcess(pid: string, force: boolean = false): Promise<string> {
     return await invoke('terminate_process', { pid, force });
   }
 
+  // 向进程、进程树或进程组发送信号
+  static async sendProcessSignal(
+    pid: string,
+    signal: ProcessSignal,
+    scope: SignalScope = 'process'
+  ): Promise<SignalOutcome[]> {
+    return await invoke('send_process_signal', { pid, signal, scope });
+  }
+
+  // 逐级终止：先 SIGTERM，等待期后仍在运行的进程发送 SIGKILL
+  static async terminateProcessGracefully(
+    pid: string,
+    scope: SignalScope = 'process',
+    gracePeriodSecs?: number
+  ): Promise<SignalOutcome[]> {
+    return await invoke('terminate_process_gracefully', { pid, scope, grace_period_secs: gracePeriodSecs });
+  }
+
+  // 列出进程的线程及 CPU 使用率
+  static async getProcessThreads(pid: string, sampleIntervalMs?: number): Promise<ProcessThreads> {
+    return await invoke('get_process_threads', { pid, sample_interval_ms: sampleIntervalMs });
+  }
+
+  // 进程内存映射：RSS、PSS、USS 及按映射文件汇总的用量
+  static async getProcessMemoryMap(pid: string): Promise<ProcessMemoryMap> {
+    return await invoke('get_process_memory_map', { pid });
+  }
+
+  // 读取进程的环境变量，masking 省略时使用默认的隐藏规则
+  static async getProcessEnvironment(pid: string, masking?: EnvMasking): Promise<ProcessEnvironment> {
+    return await invoke('get_process_environment', { pid, masking });
+  }
+
+  // 读取进程的资源限制
+  static async getProcessLimits(pid: string): Promise<ProcessLimits> {
+    return await invoke('get_process_limits', { pid });
+  }
+
+  // 提高进程的软限制，softLimit 为数值或 "unlimited"
+  static async raiseProcessSoftLimit(pid: string, resource: ResourceLimitKind, softLimit: string): Promise<ResourceLimit> {
+    return await invoke('raise_process_soft_limit', { pid, resource, soft_limit: softLimit });
+  }
+
+  // 浏览 cgroup v2 层级，path 省略时为根
+  static async getCgroupNode(path?: string): Promise<CgroupNode> {
+    return await invoke('get_cgroup_node', { path });
+  }
+
+  // 获取一个 cgroup 在时间范围内的用量序列
+  static async fetchCgroupHistory(path: string, startTime: string, endTime: string): Promise<CgroupSample[]> {
+    return await invoke('fetch_cgroup_history', { path, start_time: startTime, end_time: endTime });
+  }
+
+  // 按 systemd 单元或容器合计 CPU 和内存
+  static async getCgroupUsage(groupBy?: CgroupGrouping): Promise<CgroupUsage[]> {
+    return await invoke('get_cgroup_usage', { group_by: groupBy });
+  }
+
+  // 获取所有进程守护规则
+  static async getSupervisorRules(): Promise<SupervisorRule[]> {
+    return await invoke('get_supervisor_rules');
+  }
+
+  // 添加进程守护规则，返回新规则的 ID
+  static async addSupervisorRule(rule: SupervisorRule): Promise<string> {
+    return await invoke('add_supervisor_rule', { rule });
+  }
+
+  // 更新进程守护规则
+  static async updateSupervisorRule(id: string, rule: SupervisorRule): Promise<void> {
+    return await invoke('update_supervisor_rule', { id, rule });
+  }
+
+  // 删除进程守护规则
+  static async deleteSupervisorRule(id: string): Promise<void> {
+    return await invoke('delete_supervisor_rule', { id });
+  }
+
+  // 立即执行一次进程守护检查，后台任务也会周期性检查
+  static async runSupervisor(): Promise<SupervisorEvent[]> {
+    return await invoke('run_supervisor');
+  }
+
+  // 获取各进程守护规则的当前状态
+  static async getSupervisorStatus(): Promise<SupervisorStatus[]> {
+    return await invoke('get_supervisor_status');
+  }
+
+  // 获取最近的进程守护事件，可按规则过滤
+  static async getSupervisorEvents(ruleId?: string, limit?: number): Promise<SupervisorEvent[]> {
+    return await invoke('get_supervisor_events', { rule_id: ruleId, limit });
+  }
+
+  // 列出系统用户及其所属组
+  static async getSystemUsers(): Promise<UserInfo[]> {
+    return await invoke('get_system_users');
+  }
+
+  // 磁盘 I/O 排行
+  static async getIoTop(limit?: number, sampleIntervalMs?: number): Promise<ProcessIo[]> {
+    return await invoke('get_io_top', { limit, sample_interval_ms: sampleIntervalMs });
+  }
+
+  // 列出进程打开的文件描述符
+  static async getProcessOpenFiles(pid: string): Promise<ProcessFds> {
+    return await invoke('get_process_open_files', { pid });
+  }
+
+  // 查找打开了某个文件或目录的进程
+  static async findFileHolders(path: string): Promise<FileHolders> {
+    return await invoke('find_file_holders', { path });
+  }
+
+  // 按文件描述符数量列出进程
+  static async getFdUsage(limit?: number): Promise<FdUsage[]> {
+    return await invoke('get_fd_usage', { limit });
+  }
+
+  // 获取进程的 nice 值和调度策略
+  static async getProcessPriority(pid: string): Promise<ProcessPriority> {
+    return await invoke('get_process_priority', { pid });
+  }
+
+  // 修改进程的 nice 值（-20 到 19）
+  static async setProcessPriority(pid: string, nice: number): Promise<ProcessPriority> {
+    return await invoke('set_process_priority', { pid, nice });
+  }
+
+  // 获取进程的 CPU 亲和性
+  static async getProcessAffinity(pid: string): Promise<CpuAffinity> {
+    return await invoke('get_process_affinity', { pid });
+  }
+
+  // 设置进程的 CPU 亲和性，cpus 形如 "0-3,6"
+  static async setProcessAffinity(pid: string, cpus: string): Promise<CpuAffinity> {
+    return await invoke('set_process_affinity', { pid, cpus });
+  }
+
+  // 获取进程操作审计记录
+  static async getProcessAuditLog(pid?: string, limit?: number): Promise<ProcessAuditEntry[]> {
+    return await invoke('get_process_audit_log', { pid, limit });
+  }
+
   // 获取GPU信息
   static async getGpuInfo(): Promise<GpuInfo[]> {
     return await invoke('get_gpu_info');
@@ -439,8 +1352,8 @@ export class SystemMonitorAPI {
   }
 
   // 获取警报历史
-  static async getAlertHistory(limit?: number, offset?: number): Promise<AlertHistory[]> {
-    return await invoke('get_alert_history', { limit, offset });
+  static async getAlertHistory(limit?: number, offset?: number, filter?: AlertHistoryFilter): Promise<AlertHistory[]> {
+    return await invoke('get_alert_history', { limit, offset, filter });
   }
 
   // 确认警报
@@ -448,20 +1361,138 @@ export class SystemMonitorAPI {
     return await invoke('acknowledge_alert', { id, acknowledged_by: acknowledgedBy });
   }
 
+  // 批量确认匹配过滤条件的警报，过滤条件为空时需传入 all 才会确认所有警报
+  static async acknowledgeAlerts(filter: AlertHistoryFilter, acknowledgedBy: string, all?: boolean): Promise<number> {
+    return await invoke('acknowledge_alerts', { filter, acknowledged_by: acknowledgedBy, all });
+  }
+
+  // 按通知策略决定警报的发送方式，并获取到期的摘要
+  static async planNotifications(
+    alerts: AlertHistory[],
+    policy?: NotificationPolicy,
+    locale?: MessageLocale
+  ): Promise<NotificationPlan> {
+    return await invoke('plan_notifications', { alerts, policy, locale });
+  }
+
+  // 获取最近的通知摘要
+  static async getNotificationDigests(limit?: number): Promise<NotificationDigest[]> {
+    return await invoke('get_notification_digests', { limit });
+  }
+
+  // 获取事件列表
+  static async getIncidents(statuses?: IncidentStatus[], limit?: number): Promise<Incident[]> {
+    return await invoke('get_incidents', { statuses, limit });
+  }
+
+  // 获取事件详情
+  static async getIncident(id: string): Promise<IncidentDetail> {
+    return await invoke('get_incident', { id });
+  }
+
+  // 确认事件及其所有警报
+  static async acknowledgeIncident(id: string, acknowledgedBy: string): Promise<number> {
+    return await invoke('acknowledge_incident', { id, acknowledged_by: acknowledgedBy });
+  }
+
+  // 手动解决事件
+  static async resolveIncident(id: string, resolvedBy: string): Promise<IncidentEvent> {
+    return await invoke('resolve_incident', { id, resolved_by: resolvedBy });
+  }
+
+  // 为事件添加备注
+  static async addIncidentNote(id: string, author: string, note: string): Promise<IncidentEvent> {
+    return await invoke('add_incident_note', { id, author, note });
+  }
+
+  // 获取警报统计
+  static async getAlertStatistics(filter?: AlertHistoryFilter, noisiestLimit?: number): Promise<AlertStatistics> {
+    return await invoke('get_alert_statistics', { filter, noisiest_limit: noisiestLimit });
+  }
+
+  // 获取警报的修复动作执行记录
+  static async getRemediationResults(alertHistoryId: string): Promise<RemediationResult[]> {
+    return await invoke('get_remediation_results', { alert_history_id: alertHistoryId });
+  }
+
+  // 用历史数据回测警报规则
+  static async backtestAlertConfiguration(
+    config: AlertConfiguration,
+    startTime: string,
+    endTime: string,
+    locale?: MessageLocale
+  ): Promise<AlertBacktestReport> {
+    return await invoke('backtest_alert_configuration', {
+      config,
+      start_time: startTime,
+      end_time: endTime,
+      locale,
+    });
+  }
+
+  // 测试触发警报规则，按通知策略返回需要发送的测试通知，不写入警报历史
+  static async testFireAlert(
+    ruleId: string,
+    policy?: NotificationPolicy,
+    locale?: MessageLocale
+  ): Promise<NotificationPlan> {
+    return await invoke('test_fire_alert', { rule_id: ruleId, policy, locale });
+  }
+
+  // 应用启动时警报配置的迁移结果，包括被禁用的规则
+  static async getStartupMigrationReport(): Promise<AlertMigrationReport> {
+    return await invoke('get_startup_migration_report');
+  }
+
+  // 导出警报规则
+  static async exportAlertConfigurations(format?: RuleFileFormat): Promise<string> {
+    return await invoke('export_alert_configurations', { format });
+  }
+
+  // 导入警报规则
+  static async importAlertConfigurations(
+    content: string,
+    format?: RuleFileFormat,
+    conflictMode?: ImportConflictMode
+  ): Promise<AlertImportReport> {
+    return await invoke('import_alert_configurations', { content, format, conflict_mode: conflictMode });
+  }
+
+  // 导入 Prometheus 告警规则
+  static async importPrometheusRules(content: string, conflictMode?: ImportConflictMode): Promise<AlertImportReport> {
+    return await invoke('import_prometheus_rules', { content, conflict_mode: conflictMode });
+  }
+
   // 检查警报
   static async checkAlerts(metrics: {
     cpu_usage: number;
     memory_usage: number;
     disk_usage: number;
     network_traffic: number;
-  }): Promise<AlertHistory[]> {
+  }, locale?: MessageLocale, incidentGrouping?: IncidentGrouping): Promise<AlertHistory[]> {
     return await invoke('check_alerts', {
       cpu_usage: metrics.cpu_usage,
       memory_usage: metrics.memory_usage,
       disk_usage: metrics.disk_usage,
       network_traffic: metrics.network_traffic,
+      locale,
+      incident_grouping: incidentGrouping,
     });
   }
+
+  // 获取默认警报消息模板
+  static async getDefaultAlertTemplate(locale?: MessageLocale): Promise<string> {
+    return await invoke('get_default_alert_template', { locale });
+  }
+
+  // 预览警报消息模板
+  static async previewAlertMessage(
+    template?: string,
+    locale?: MessageLocale,
+    sample?: AlertMessageContext
+  ): Promise<string> {
+    return await invoke('preview_alert_message', { template, locale, sample });
+  }
 }
 
 // 导出所有类型和API服务
@@ -12,7 +12,8 @@ vi.mock('@/lib/api', () => ({
     addAlertConfiguration: vi.fn(),
     updateAlertConfiguration: vi.fn(),
     deleteAlertConfiguration: vi.fn(),
-    acknowledgeAlert: vi.fn()
+    acknowledgeAlert: vi.fn(),
+    testFireAlert: vi.fn()
   }
 }));
 
@@ -61,6 +62,7 @@ describe('AlertSystem', () => {
     (SystemMonitorAPI.updateAlertConfiguration as any).mockResolvedValue(undefined);
     (SystemMonitorAPI.deleteAlertConfiguration as any).mockResolvedValue(undefined);
     (SystemMonitorAPI.acknowledgeAlert as any).mockResolvedValue(undefined);
+    (SystemMonitorAPI.testFireAlert as any).mockResolvedValue({ deliveries: [], digests: [], batched: 0, overflowed: 0 });
   });
 
   it('renders without crashing', async () => {
@@ -179,4 +181,17 @@ describe('AlertSystem', () => {
     // 验证 acknowledgeAlert 被调用
     expect(SystemMonitorAPI.acknowledgeAlert).toHaveBeenCalledWith('alert-1', 'user');
   });
-});
\ No newline at end of file
+
+  it('calls testFireAlert when test button is clicked', async () => {
+    render(<AlertSystem />);
+    
+    // 等待数据加载完成
+    await screen.findByText('警报系统');
+    
+    // 点击测试按钮
+    fireEvent.click(screen.getAllByText('测试')[0]);
+    
+    // 验证 testFireAlert 被调用
+    expect(SystemMonitorAPI.testFireAlert).toHaveBeenCalledWith('1');
+  });
+});